@@ -590,6 +590,232 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn delegate() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+		let delegate: T::AccountId = account("seed", 2, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(test_account), delegate);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn undelegate() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+		let delegate: T::AccountId = account("seed", 2, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+
+		assert_ok!(BbBNC::<T>::delegate(
+			RawOrigin::Signed(test_account.clone()).into(),
+			delegate
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(test_account));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn split_position() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(test_account),
+			0,
+			BalanceOf::<T>::unique_saturated_from(5_000_000_000_000u128),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn merge_positions() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(test_account), 0, 1);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_position() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+		let to: T::AccountId = account("seed", 2, 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(test_account), 0, to);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_position_transfer() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+		let to: T::AccountId = account("seed", 2, 1);
+		assert_ok!(BbBNC::<T>::transfer_position(
+			RawOrigin::Signed(test_account.clone()).into(),
+			0,
+			to.clone()
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(to), 0);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_position_transfer() -> Result<(), BenchmarkError> {
+		let test_account: T::AccountId = account("seed", 1, 1);
+
+		assert_ok!(BbBNC::<T>::set_config(
+			RawOrigin::Root.into(),
+			Some((4 * 365 * 86400 / 12u32).into()),
+			Some((7 * 86400 / 12u32).into())
+		));
+
+		T::MultiCurrency::deposit(
+			CurrencyId::Native(TokenSymbol::BNC),
+			&test_account,
+			BalanceOf::<T>::unique_saturated_from(100_000_000_000_000u128),
+		)?;
+
+		assert_ok!(BbBNC::<T>::create_lock(
+			RawOrigin::Signed(test_account.clone()).into(),
+			BalanceOf::<T>::unique_saturated_from(10_000_000_000_000u128),
+			(365 * 86400 / 12u32).into()
+		));
+		let to: T::AccountId = account("seed", 2, 1);
+		assert_ok!(BbBNC::<T>::transfer_position(
+			RawOrigin::Signed(test_account.clone()).into(),
+			0,
+			to.clone()
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(test_account), 0);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext_benchmark(),
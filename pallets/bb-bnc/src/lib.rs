@@ -78,6 +78,16 @@ pub struct LockedBalance<Balance, BlockNumber> {
 	end: BlockNumber,
 }
 
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PositionTransfer<AccountId, Balance, BlockNumber> {
+	/// Owner of the position
+	from: AccountId,
+	/// New owner, who has to accept the transfer
+	to: AccountId,
+	/// Locked balance of the position when the transfer was proposed
+	locked: LockedBalance<Balance, BlockNumber>,
+}
+
 #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
 pub struct Point<Balance, BlockNumber> {
 	bias: i128,  // i128
@@ -255,6 +265,71 @@ pub mod pallet {
 			who: AccountIdOf<T>,
 			currency_id: CurrencyIdOf<T>,
 		},
+		/// The voting power of a user has been delegated.
+		Delegated {
+			/// The user who delegated
+			who: AccountIdOf<T>,
+			/// The delegate
+			delegate: AccountIdOf<T>,
+		},
+		/// The voting power of a user is no longer delegated.
+		Undelegated {
+			/// The user who undelegated
+			who: AccountIdOf<T>,
+			/// The former delegate
+			delegate: AccountIdOf<T>,
+		},
+		/// A position was split.
+		PositionSplit {
+			/// Position owner
+			who: AccountIdOf<T>,
+			/// Position ID
+			position: u128,
+			/// ID of the position split off
+			new_position: u128,
+			/// Value moved to the new position
+			value: BalanceOf<T>,
+		},
+		/// A position was merged into another one.
+		PositionMerged {
+			/// Position owner
+			who: AccountIdOf<T>,
+			/// ID of the merged position
+			position: u128,
+			/// ID of the position merged into
+			into: u128,
+			/// New locked value
+			total_value: BalanceOf<T>,
+			/// New withdrawable time
+			unlock_time: BlockNumberFor<T>,
+		},
+		/// A position transfer was proposed and waits for acceptance by the new owner.
+		PositionTransferProposed {
+			/// Position owner
+			who: AccountIdOf<T>,
+			/// New position owner
+			to: AccountIdOf<T>,
+			/// ID of the position
+			position: u128,
+		},
+		/// A pending position transfer was cancelled.
+		PositionTransferCancelled {
+			/// ID of the position
+			position: u128,
+		},
+		/// A position was transferred to another user.
+		PositionTransferred {
+			/// Former position owner
+			who: AccountIdOf<T>,
+			/// New position owner
+			to: AccountIdOf<T>,
+			/// ID of the transferred position
+			position: u128,
+			/// ID of the position of the new owner
+			new_position: u128,
+			/// Transferred value
+			value: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -275,6 +350,16 @@ pub mod pallet {
 		ExceedsMaxPositions,
 		/// No controller
 		NoController,
+		/// Voting power is already delegated
+		AlreadyDelegated,
+		/// Voting power is not delegated
+		NotDelegated,
+		/// No pending transfer of the position
+		NoPendingTransfer,
+		/// Position changed after the transfer was proposed
+		PositionChanged,
+		/// Positions unlock at different times
+		UnlockTimeMismatch,
 	}
 
 	/// Total supply of locked tokens
@@ -397,6 +482,62 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Positions no longer owned by the user, kept for its historical voting power.
+	/// [who => position => block closed]
+	#[pallet::storage]
+	pub type UserClosedPositions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		PositionId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// The delegate of the voting power of a user. [who => delegate]
+	#[pallet::storage]
+	pub type Delegates<T: Config> = StorageMap<_, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>>;
+
+	/// Delegated voting power epoch. [who => epoch]
+	#[pallet::storage]
+	pub type DelegatedPointEpoch<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, U256, ValueQuery>;
+
+	/// Delegated voting power history, positive for received and negative for delegated power.
+	/// [(who, epoch) => Point]
+	#[pallet::storage]
+	pub type DelegatedPointHistory<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		U256,
+		Point<BalanceOf<T>, BlockNumberFor<T>>,
+		ValueQuery,
+	>;
+
+	/// Delegated voting power slope changes. [(who, block) => slope]
+	#[pallet::storage]
+	pub type DelegatedSlopeChanges<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		i128,
+		ValueQuery,
+	>;
+
+	/// Position transfers waiting for acceptance by the new owner. [position => PositionTransfer]
+	#[pallet::storage]
+	pub type PendingPositionTransfers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		PositionId,
+		PositionTransfer<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
@@ -650,6 +791,162 @@ pub mod pallet {
 			let _exchanger = ensure_signed(origin)?;
 			Self::refresh_inner(currency_id)
 		}
+
+		/// Delegate voting power.
+		///
+		/// Move the decaying voting power of all positions of the signer, including positions
+		/// created or changed later, to the delegate until undelegated. `balance_of` of both
+		/// accounts and their farming boost votes, which are refreshed, follow the delegation.
+		/// Rewards and gauges stay with the signer. Delegated power is not delegated again by the
+		/// delegate.
+		///
+		/// - `delegate`: The account to receive the voting power
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(origin: OriginFor<T>, delegate: AccountIdOf<T>) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			Self::delegate_inner(&exchanger, &delegate)
+		}
+
+		/// Undelegate voting power.
+		///
+		/// Return the voting power of all positions of the signer from its delegate.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			Self::undelegate_inner(&exchanger)
+		}
+
+		/// Split a position.
+		///
+		/// Move `value` out of the position into a new position of the signer with the same unlock
+		/// time. The voting power of both positions adds up to that of the original position.
+		///
+		/// - `position`: The lock position
+		/// - `value`: The value of the new position
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::split_position())]
+		pub fn split_position(
+			origin: OriginFor<T>,
+			position: PositionId,
+			value: BalanceOf<T>,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			let user_positions = UserPositions::<T>::get(&exchanger);
+			ensure!(user_positions.contains(&position), Error::<T>::LockNotExist);
+			Self::split_position_inner(&exchanger, position, value)
+		}
+
+		/// Merge a position into another one.
+		///
+		/// Both positions must be owned by the signer and unlock at the same time. The merged
+		/// position is removed and its value is added to `into`.
+		///
+		/// - `position`: The lock position to be merged
+		/// - `into`: The lock position to merge into
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::merge_positions())]
+		pub fn merge_positions(
+			origin: OriginFor<T>,
+			position: PositionId,
+			into: PositionId,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			let user_positions = UserPositions::<T>::get(&exchanger);
+			ensure!(user_positions.contains(&position), Error::<T>::LockNotExist);
+			ensure!(user_positions.contains(&into), Error::<T>::LockNotExist);
+			Self::merge_positions_inner(&exchanger, position, into)
+		}
+
+		/// Propose to transfer a position to another user.
+		///
+		/// The transfer takes place once `to` accepts it with `accept_position_transfer`. A new
+		/// proposal for the position replaces the pending one.
+		///
+		/// - `position`: The lock position
+		/// - `to`: The new owner
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::transfer_position())]
+		pub fn transfer_position(
+			origin: OriginFor<T>,
+			position: PositionId,
+			to: AccountIdOf<T>,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			let user_positions = UserPositions::<T>::get(&exchanger);
+			ensure!(user_positions.contains(&position), Error::<T>::LockNotExist);
+			ensure!(exchanger != to, Error::<T>::ArgumentsError);
+			let locked = Locked::<T>::get(position);
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(locked.end > current_block_number, Error::<T>::Expired);
+
+			PendingPositionTransfers::<T>::insert(
+				position,
+				PositionTransfer {
+					from: exchanger.clone(),
+					to: to.clone(),
+					locked,
+				},
+			);
+			Self::deposit_event(Event::PositionTransferProposed {
+				who: exchanger,
+				to,
+				position,
+			});
+			Ok(())
+		}
+
+		/// Accept a position transfer.
+		///
+		/// The locked tokens are transferred to the signer and locked in a new position of the
+		/// signer with the same unlock time. The voting power before the transfer stays with the
+		/// former owner. Fails if the position changed after the transfer was proposed.
+		///
+		/// - `position`: The lock position
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::accept_position_transfer())]
+		pub fn accept_position_transfer(
+			origin: OriginFor<T>,
+			position: PositionId,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			let transfer = PendingPositionTransfers::<T>::get(position)
+				.ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(transfer.to == exchanger, Error::<T>::NoPendingTransfer);
+			ensure!(
+				UserPositions::<T>::get(&transfer.from).contains(&position)
+					&& Locked::<T>::get(position) == transfer.locked,
+				Error::<T>::PositionChanged
+			);
+			Self::transfer_position_inner(&transfer.from, position, &exchanger)?;
+			PendingPositionTransfers::<T>::remove(position);
+			Ok(())
+		}
+
+		/// Cancel a pending position transfer.
+		///
+		/// Either the owner or the proposed new owner can cancel the transfer.
+		///
+		/// - `position`: The lock position
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::cancel_position_transfer())]
+		pub fn cancel_position_transfer(
+			origin: OriginFor<T>,
+			position: PositionId,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+			let transfer = PendingPositionTransfers::<T>::get(position)
+				.ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(
+				transfer.from == exchanger || transfer.to == exchanger,
+				Error::<T>::NoPendingTransfer
+			);
+			PendingPositionTransfers::<T>::remove(position);
+			Self::deposit_event(Event::PositionTransferCancelled { position });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -661,43 +958,26 @@ pub mod pallet {
 		) -> DispatchResult {
 			Self::update_reward_all(who)?;
 
-			let mut u_old = Point::<BalanceOf<T>, BlockNumberFor<T>>::default();
-			let mut u_new = Point::<BalanceOf<T>, BlockNumberFor<T>>::default();
 			let mut new_dslope = 0_i128;
 			let mut g_epoch: U256 = Epoch::<T>::get();
 			let current_block_number: BlockNumberFor<T> =
 				T::BlockNumberProvider::current_block_number();
 
-			if old_locked.end > current_block_number && old_locked.amount > BalanceOf::<T>::zero() {
-				u_old.slope = U256::from(old_locked.amount.saturated_into::<u128>())
-					.checked_div(U256::from(T::MaxBlock::get().saturated_into::<u128>()))
-					.map(|x| u128::try_from(x))
-					.ok_or(ArithmeticError::Overflow)?
-					.map_err(|_| ArithmeticError::Overflow)?
-					.unique_saturated_into();
-				u_old.bias = u_old
-					.slope
-					.checked_mul(
-						(old_locked.end.saturated_into::<u128>() as i128)
-							- (current_block_number.saturated_into::<u128>() as i128),
-					)
-					.ok_or(ArithmeticError::Overflow)?;
-			}
-			if new_locked.end > current_block_number && new_locked.amount > BalanceOf::<T>::zero() {
-				u_new.slope = U256::from(new_locked.amount.saturated_into::<u128>())
-					.checked_div(U256::from(T::MaxBlock::get().saturated_into::<u128>()))
-					.map(|x| u128::try_from(x))
-					.ok_or(ArithmeticError::Overflow)?
-					.map_err(|_| ArithmeticError::Overflow)?
-					.unique_saturated_into();
-				u_new.bias = u_new
-					.slope
-					.checked_mul(
-						(new_locked.end.saturated_into::<u128>() as i128)
-							- (current_block_number.saturated_into::<u128>() as i128),
-					)
-					.ok_or(ArithmeticError::Overflow)?;
+			let u_old = Self::locked_point(&old_locked, current_block_number)?;
+			let mut u_new = Self::locked_point(&new_locked, current_block_number)?;
+			if let Some(delegate) = Delegates::<T>::get(who) {
+				// Move the change of the position's voting power on to the delegate.
+				let changes = [
+					(-u_old.bias, -u_old.slope, old_locked.end),
+					(u_new.bias, u_new.slope, new_locked.end),
+				];
+				Self::checkpoint_delegated(&delegate, &changes)?;
+				Self::checkpoint_delegated(
+					who,
+					&changes.map(|(bias, slope, end)| (-bias, -slope, end)),
+				)?;
 			}
+
 			let mut old_dslope = SlopeChanges::<T>::get(old_locked.end);
 			if new_locked.end != Zero::zero() {
 				if new_locked.end == old_locked.end {
@@ -833,6 +1113,438 @@ pub mod pallet {
 			Ok(())
 		}
 
+		// The point of `locked` at `current_block_number`, zero if it has expired.
+		fn locked_point(
+			locked: &LockedBalance<BalanceOf<T>, BlockNumberFor<T>>,
+			current_block_number: BlockNumberFor<T>,
+		) -> Result<Point<BalanceOf<T>, BlockNumberFor<T>>, DispatchError> {
+			let mut point = Point::<BalanceOf<T>, BlockNumberFor<T>>::default();
+			if locked.end > current_block_number && locked.amount > BalanceOf::<T>::zero() {
+				point.slope = U256::from(locked.amount.saturated_into::<u128>())
+					.checked_div(U256::from(T::MaxBlock::get().saturated_into::<u128>()))
+					.map(|x| u128::try_from(x))
+					.ok_or(ArithmeticError::Overflow)?
+					.map_err(|_| ArithmeticError::Overflow)?
+					.unique_saturated_into();
+				point.bias = point
+					.slope
+					.checked_mul(
+						(locked.end.saturated_into::<u128>() as i128)
+							- (current_block_number.saturated_into::<u128>() as i128),
+					)
+					.ok_or(ArithmeticError::Overflow)?;
+			}
+			Ok(point)
+		}
+
+		pub fn delegate_inner(who: &AccountIdOf<T>, delegate: &AccountIdOf<T>) -> DispatchResult {
+			ensure!(who != delegate, Error::<T>::ArgumentsError);
+			ensure!(
+				!Delegates::<T>::contains_key(who),
+				Error::<T>::AlreadyDelegated
+			);
+
+			let changes = Self::position_points(who)?;
+			Self::checkpoint_delegated(delegate, &changes)?;
+			Self::checkpoint_delegated(
+				who,
+				&changes
+					.iter()
+					.map(|&(bias, slope, end)| (-bias, -slope, end))
+					.collect::<Vec<_>>(),
+			)?;
+			Delegates::<T>::insert(who, delegate);
+
+			// Gauges count only own voting power, so just the boost votes follow the delegation.
+			T::FarmingInfo::refresh_boost_votes(who)?;
+			T::FarmingInfo::refresh_boost_votes(delegate)?;
+			Self::deposit_event(Event::Delegated {
+				who: who.clone(),
+				delegate: delegate.clone(),
+			});
+			Ok(())
+		}
+
+		pub fn undelegate_inner(who: &AccountIdOf<T>) -> DispatchResult {
+			let delegate = Delegates::<T>::take(who).ok_or(Error::<T>::NotDelegated)?;
+
+			let changes = Self::position_points(who)?;
+			Self::checkpoint_delegated(who, &changes)?;
+			Self::checkpoint_delegated(
+				&delegate,
+				&changes
+					.iter()
+					.map(|&(bias, slope, end)| (-bias, -slope, end))
+					.collect::<Vec<_>>(),
+			)?;
+
+			T::FarmingInfo::refresh_boost_votes(who)?;
+			T::FarmingInfo::refresh_boost_votes(&delegate)?;
+			Self::deposit_event(Event::Undelegated {
+				who: who.clone(),
+				delegate,
+			});
+			Ok(())
+		}
+
+		#[transactional]
+		pub fn split_position_inner(
+			who: &AccountIdOf<T>,
+			position: PositionId,
+			value: BalanceOf<T>,
+		) -> DispatchResult {
+			let locked = Locked::<T>::get(position);
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(locked.end > current_block_number, Error::<T>::Expired);
+			ensure!(
+				!value.is_zero() && value < locked.amount,
+				Error::<T>::ArgumentsError
+			);
+			let bb_config = BbConfigs::<T>::get();
+			ensure!(
+				value >= bb_config.min_mint && locked.amount - value >= bb_config.min_mint,
+				Error::<T>::BelowMinimumMint
+			);
+
+			let new_position = Self::new_position(who)?;
+			let remaining_locked = LockedBalance {
+				amount: locked.amount - value,
+				end: locked.end,
+			};
+			let new_locked = LockedBalance {
+				amount: value,
+				end: locked.end,
+			};
+			Locked::<T>::insert(position, remaining_locked.clone());
+			Locked::<T>::insert(new_position, new_locked.clone());
+
+			let user_markup_info = Self::current_markup_info(who);
+			Self::markup_calc(
+				who,
+				position,
+				locked,
+				remaining_locked,
+				user_markup_info.as_ref(),
+			)?;
+			Self::markup_calc(
+				who,
+				new_position,
+				LockedBalance::default(),
+				new_locked,
+				user_markup_info.as_ref(),
+			)?;
+
+			T::FarmingInfo::refresh_gauge_pool(who)?;
+			Self::deposit_event(Event::PositionSplit {
+				who: who.clone(),
+				position,
+				new_position,
+				value,
+			});
+			Ok(())
+		}
+
+		#[transactional]
+		pub fn merge_positions_inner(
+			who: &AccountIdOf<T>,
+			position: PositionId,
+			into: PositionId,
+		) -> DispatchResult {
+			ensure!(position != into, Error::<T>::ArgumentsError);
+			let locked = Locked::<T>::get(position);
+			let into_locked = Locked::<T>::get(into);
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(locked.end > current_block_number, Error::<T>::Expired);
+			// Extending the lock of either position would raise the voting power.
+			ensure!(
+				locked.end == into_locked.end,
+				Error::<T>::UnlockTimeMismatch
+			);
+
+			let merged_locked = LockedBalance {
+				amount: into_locked
+					.amount
+					.checked_add(locked.amount)
+					.ok_or(ArithmeticError::Overflow)?,
+				end: into_locked.end,
+			};
+			Locked::<T>::insert(position, LockedBalance::default());
+			Locked::<T>::insert(into, merged_locked.clone());
+			UserPositions::<T>::mutate(who, |positions| {
+				positions.retain(|&x| x != position);
+			});
+			// The point history of the position ends with a zero checkpoint.
+			UserClosedPositions::<T>::insert(who, position, current_block_number);
+
+			let user_markup_info = Self::current_markup_info(who);
+			Self::markup_calc(
+				who,
+				position,
+				locked,
+				LockedBalance::default(),
+				user_markup_info.as_ref(),
+			)?;
+			Self::markup_calc(
+				who,
+				into,
+				into_locked,
+				merged_locked.clone(),
+				user_markup_info.as_ref(),
+			)?;
+
+			T::FarmingInfo::refresh_gauge_pool(who)?;
+			Self::deposit_event(Event::PositionMerged {
+				who: who.clone(),
+				position,
+				into,
+				total_value: merged_locked.amount,
+				unlock_time: merged_locked.end,
+			});
+			Ok(())
+		}
+
+		#[transactional]
+		pub fn transfer_position_inner(
+			who: &AccountIdOf<T>,
+			position: PositionId,
+			to: &AccountIdOf<T>,
+		) -> DispatchResult {
+			ensure!(who != to, Error::<T>::ArgumentsError);
+			let locked = Locked::<T>::get(position);
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(locked.end > current_block_number, Error::<T>::Expired);
+
+			// Remove the position from `who`.
+			Locked::<T>::insert(position, LockedBalance::default());
+			UserPositions::<T>::mutate(who, |positions| {
+				positions.retain(|&x| x != position);
+			});
+			// The point history of the position ends with a zero checkpoint.
+			UserClosedPositions::<T>::insert(who, position, current_block_number);
+			let new_locked_balance = UserLocked::<T>::get(who)
+				.checked_sub(locked.amount)
+				.ok_or(ArithmeticError::Underflow)?;
+			Self::set_ve_locked(who, new_locked_balance)?;
+			Self::markup_calc(
+				who,
+				position,
+				locked.clone(),
+				LockedBalance::default(),
+				Self::current_markup_info(who).as_ref(),
+			)?;
+
+			// Lock the transferred tokens in a new position of `to`.
+			T::MultiCurrency::transfer(T::TokenType::get(), who, to, locked.amount)?;
+			let new_position = Self::new_position(to)?;
+			Locked::<T>::insert(new_position, locked.clone());
+			let new_locked_balance = UserLocked::<T>::get(to)
+				.checked_add(locked.amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				new_locked_balance <= T::MultiCurrency::free_balance(T::TokenType::get(), to),
+				Error::<T>::NotEnoughBalance
+			);
+			Self::set_ve_locked(to, new_locked_balance)?;
+			Self::markup_calc(
+				to,
+				new_position,
+				LockedBalance::default(),
+				locked.clone(),
+				Self::current_markup_info(to).as_ref(),
+			)?;
+
+			T::FarmingInfo::refresh_gauge_pool(who)?;
+			T::FarmingInfo::refresh_gauge_pool(to)?;
+			Self::deposit_event(Event::PositionTransferred {
+				who: who.clone(),
+				to: to.clone(),
+				position,
+				new_position,
+				value: locked.amount,
+			});
+			Ok(())
+		}
+
+		// Allocate a new position ID for `who`.
+		fn new_position(who: &AccountIdOf<T>) -> Result<PositionId, DispatchError> {
+			let new_position = Position::<T>::get();
+			UserPositions::<T>::try_mutate(who, |user_positions| {
+				user_positions
+					.try_push(new_position)
+					.map_err(|_| Error::<T>::ExceedsMaxPositions)
+			})?;
+			Position::<T>::set(new_position + 1);
+			Ok(new_position)
+		}
+
+		// The markup info of `who` applying its current markup coefficient to both the old and the
+		// new locked balance.
+		fn current_markup_info(who: &AccountIdOf<T>) -> Option<UserMarkupInfo> {
+			UserMarkupInfos::<T>::get(who).map(|info| UserMarkupInfo {
+				old_markup_coefficient: info.markup_coefficient,
+				markup_coefficient: info.markup_coefficient,
+			})
+		}
+
+		// The current `(bias, slope, end)` of the positions of `who` that have not expired,
+		// including the markup as checkpointed by `markup_calc`.
+		fn position_points(
+			who: &AccountIdOf<T>,
+		) -> Result<Vec<(i128, i128, BlockNumberFor<T>)>, DispatchError> {
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			let user_markup_info = UserMarkupInfos::<T>::get(who);
+			let mut points = Vec::new();
+			for position in UserPositions::<T>::get(who) {
+				let mut locked = Locked::<T>::get(position);
+				if let Some(info) = user_markup_info.as_ref() {
+					locked.amount = info
+						.markup_coefficient
+						.checked_mul_int(locked.amount)
+						.and_then(|x| x.checked_add(locked.amount))
+						.ok_or(ArithmeticError::Overflow)?;
+				}
+				let point = Self::locked_point(&locked, current_block_number)?;
+				if point.slope != 0_i128 {
+					points.push((point.bias, point.slope, locked.end));
+				}
+			}
+			Ok(points)
+		}
+
+		// Add `(bias, slope, end)` changes to the delegated voting power of `who` at the current
+		// block. Each slope ends at its `end`.
+		fn checkpoint_delegated(
+			who: &AccountIdOf<T>,
+			changes: &[(i128, i128, BlockNumberFor<T>)],
+		) -> DispatchResult {
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			let mut point = Self::delegated_point_at(who, current_block_number)?;
+			for &(bias, slope, end) in changes {
+				if slope == 0_i128 || end <= current_block_number {
+					continue;
+				}
+				point.bias = point
+					.bias
+					.checked_add(bias)
+					.ok_or(ArithmeticError::Overflow)?;
+				point.slope = point
+					.slope
+					.checked_add(slope)
+					.ok_or(ArithmeticError::Overflow)?;
+				DelegatedSlopeChanges::<T>::try_mutate(who, end, |dslope| -> DispatchResult {
+					*dslope = dslope.checked_sub(slope).ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+			}
+
+			let epoch = DelegatedPointEpoch::<T>::get(who)
+				.checked_add(U256::one())
+				.ok_or(ArithmeticError::Overflow)?;
+			DelegatedPointEpoch::<T>::insert(who, epoch);
+			DelegatedPointHistory::<T>::insert(who, epoch, point);
+			Ok(())
+		}
+
+		// The delegated voting power point of `who` at block height `block`
+		pub(crate) fn delegated_point_at(
+			who: &AccountIdOf<T>,
+			block: BlockNumberFor<T>,
+		) -> Result<Point<BalanceOf<T>, BlockNumberFor<T>>, DispatchError> {
+			// Binary search
+			let mut _min = U256::zero();
+			let mut _max = DelegatedPointEpoch::<T>::get(who);
+			for _i in 0..128 {
+				if _min >= _max {
+					break;
+				}
+				let _mid = (_min
+					.checked_add(_max)
+					.ok_or(ArithmeticError::Overflow)?
+					.checked_add(U256::one())
+					.ok_or(ArithmeticError::Overflow)?)
+				.checked_div(U256::from(2_u128))
+				.ok_or(ArithmeticError::Overflow)?;
+
+				if DelegatedPointHistory::<T>::get(who, _mid).block <= block {
+					_min = _mid
+				} else {
+					_max = _mid
+						.checked_sub(U256::one())
+						.ok_or(ArithmeticError::Overflow)?
+				}
+			}
+
+			let mut last_point = DelegatedPointHistory::<T>::get(who, _min);
+			if _min == U256::zero() {
+				last_point.block = block;
+				return Ok(last_point);
+			}
+			let mut t_i: BlockNumberFor<T> = last_point
+				.block
+				.checked_div(&T::Week::get())
+				.ok_or(ArithmeticError::Overflow)?
+				.checked_mul(&T::Week::get())
+				.ok_or(ArithmeticError::Overflow)?;
+			for _i in 0..255 {
+				t_i = t_i
+					.checked_add(&T::Week::get())
+					.ok_or(ArithmeticError::Overflow)?;
+				let mut d_slope = 0_i128;
+				if t_i > block {
+					t_i = block
+				} else {
+					d_slope = DelegatedSlopeChanges::<T>::get(who, t_i)
+				}
+				last_point.bias = last_point
+					.bias
+					.checked_sub(
+						last_point
+							.slope
+							.checked_mul(
+								t_i.checked_sub(&last_point.block)
+									.ok_or(ArithmeticError::Overflow)?
+									.saturated_into::<u128>()
+									.unique_saturated_into(),
+							)
+							.ok_or(ArithmeticError::Overflow)?,
+					)
+					.ok_or(ArithmeticError::Overflow)?;
+				last_point.slope = last_point
+					.slope
+					.checked_add(d_slope)
+					.ok_or(ArithmeticError::Overflow)?;
+				last_point.block = t_i;
+				if t_i == block {
+					break;
+				}
+			}
+			Ok(last_point)
+		}
+
+		// Add the delegated voting power of `who` at block height `block` to `balance`
+		fn with_delegated_power(
+			who: &AccountIdOf<T>,
+			balance: BalanceOf<T>,
+			block: BlockNumberFor<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let bias = Self::delegated_point_at(who, block)?.bias;
+			let delegated = T::VoteWeightMultiplier::get()
+				.checked_mul(bias.unsigned_abs().unique_saturated_into())
+				.ok_or(ArithmeticError::Overflow)?;
+			if bias >= 0_i128 {
+				Ok(balance
+					.checked_add(delegated)
+					.ok_or(ArithmeticError::Overflow)?)
+			} else {
+				Ok(balance.saturating_sub(delegated))
+			}
+		}
+
 		pub fn deposit_for_inner(
 			who: &AccountIdOf<T>,
 			position: PositionId,
@@ -993,14 +1705,19 @@ pub mod pallet {
 			block: BlockNumberFor<T>,
 		) -> Result<BalanceOf<T>, DispatchError> {
 			let mut balance = BalanceOf::<T>::zero();
-			UserPositions::<T>::get(who).into_iter().try_for_each(
-				|position| -> DispatchResult {
+			// Positions closed after `block` were still owned by `who` at `block`.
+			let closed_positions = UserClosedPositions::<T>::iter_prefix(who)
+				.filter(|(_, closed_block)| *closed_block > block)
+				.map(|(position, _)| position);
+			UserPositions::<T>::get(who)
+				.into_iter()
+				.chain(closed_positions)
+				.try_for_each(|position| -> DispatchResult {
 					balance = balance
 						.checked_add(Self::balance_of_position_at(position, block)?)
 						.ok_or(ArithmeticError::Overflow)?;
 					Ok(())
-				},
-			)?;
+				})?;
 			Ok(balance)
 		}
 
@@ -1019,6 +1736,30 @@ pub mod pallet {
 			Ok(balance)
 		}
 
+		// Measure voting power of `who` at the current block, including delegated voting power
+		pub(crate) fn voting_power_current_block(
+			who: &AccountIdOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			Self::with_delegated_power(
+				who,
+				Self::balance_of_current_block(who)?,
+				current_block_number,
+			)
+		}
+
+		// Measure voting power of `who` at block height `block`, including delegated voting power
+		pub(crate) fn voting_power_at(
+			who: &AccountIdOf<T>,
+			block: BlockNumberFor<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(block <= current_block_number, Error::<T>::Expired);
+			Self::with_delegated_power(who, Self::balance_of_at(who, block)?, block)
+		}
+
 		pub fn markup_calc(
 			who: &AccountIdOf<T>,
 			position: PositionId,
@@ -1384,13 +2125,7 @@ impl<T: Config> BbBNCInterface<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, Bl
 		value: BalanceOf<T>,
 		unlock_time: BlockNumberFor<T>,
 	) -> DispatchResult {
-		let new_position = Position::<T>::get();
-		UserPositions::<T>::try_mutate(who, |user_positions| {
-			user_positions
-				.try_push(new_position)
-				.map_err(|_| Error::<T>::ExceedsMaxPositions)
-		})?;
-		Position::<T>::set(new_position + 1);
+		let new_position = Self::new_position(who)?;
 
 		let bb_config = BbConfigs::<T>::get();
 		ensure!(value >= bb_config.min_mint, Error::<T>::BelowMinimumMint);
@@ -1530,6 +2265,16 @@ impl<T: Config> BbBNCInterface<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, Bl
 	fn balance_of(
 		who: &AccountIdOf<T>,
 		time: Option<BlockNumberFor<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match time {
+			Some(_t) => Self::voting_power_at(who, _t),
+			None => Self::voting_power_current_block(who),
+		}
+	}
+
+	fn own_balance_of(
+		who: &AccountIdOf<T>,
+		time: Option<BlockNumberFor<T>>,
 	) -> Result<BalanceOf<T>, DispatchError> {
 		match time {
 			Some(_t) => Self::balance_of_at(who, _t),
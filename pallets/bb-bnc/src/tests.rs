@@ -1968,3 +1968,376 @@ fn unlock_time_edge_cases_should_work() {
 			);
 		});
 }
+
+#[test]
+fn delegate_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			asset_registry();
+			System::set_block_number(System::block_number() + 20);
+
+			assert_ok!(BbBNC::set_config(
+				RuntimeOrigin::root(),
+				Some(0),
+				Some(7 * 86400 / 12)
+			));
+			assert_ok!(BbBNC::create_lock_inner(
+				&BOB,
+				10_000_000_000_000,
+				System::block_number() + (4 * 365 * 86400 - 5 * 86400) / 12,
+			));
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(9972575751740));
+
+			assert_noop!(
+				BbBNC::delegate(RuntimeOrigin::signed(BOB), BOB),
+				Error::<Runtime>::ArgumentsError
+			);
+			assert_noop!(
+				BbBNC::undelegate(RuntimeOrigin::signed(BOB)),
+				Error::<Runtime>::NotDelegated
+			);
+
+			System::set_block_number(System::block_number() + 10);
+			let delegated_block = System::block_number();
+			let bob_balance = BbBNC::balance_of(&BOB, None).unwrap();
+			assert_ok!(BbBNC::delegate(RuntimeOrigin::signed(BOB), CHARLIE));
+			System::assert_last_event(RuntimeEvent::BbBNC(Event::Delegated {
+				who: BOB,
+				delegate: CHARLIE,
+			}));
+			assert_noop!(
+				BbBNC::delegate(RuntimeOrigin::signed(BOB), ALICE),
+				Error::<Runtime>::AlreadyDelegated
+			);
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(0));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(bob_balance));
+			// Gauges and rewards keep the own voting power.
+			assert_eq!(BbBNC::own_balance_of(&BOB, None), Ok(bob_balance));
+			assert_eq!(BbBNC::own_balance_of(&CHARLIE, None), Ok(0));
+			// Voting power before the delegation is unchanged.
+			assert_eq!(BbBNC::balance_of(&BOB, Some(20)), Ok(9972575751740));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, Some(20)), Ok(0));
+
+			// Delegated voting power decays with the lock.
+			System::set_block_number(System::block_number() + 2 * 7 * 86400 / 12);
+			let bob_balance = BbBNC::balance_of_current_block(&BOB).unwrap();
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(0));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(bob_balance));
+
+			// Changes of the lock while delegated move to the delegate.
+			assert_ok!(BbBNC::increase_amount(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID0,
+				10_000_000_000_000
+			));
+			let bob_balance = BbBNC::balance_of_current_block(&BOB).unwrap();
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(0));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(bob_balance));
+			assert_eq!(
+				BbBNC::balance_of(&CHARLIE, Some(delegated_block)),
+				BbBNC::balance_of_at(&BOB, delegated_block)
+			);
+
+			System::set_block_number(System::block_number() + 10);
+			let undelegated_block = System::block_number();
+			assert_ok!(BbBNC::undelegate(RuntimeOrigin::signed(BOB)));
+			System::assert_last_event(RuntimeEvent::BbBNC(Event::Undelegated {
+				who: BOB,
+				delegate: CHARLIE,
+			}));
+			assert_eq!(
+				BbBNC::balance_of(&BOB, None),
+				BbBNC::balance_of_current_block(&BOB)
+			);
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(0));
+			assert_eq!(
+				BbBNC::balance_of(&CHARLIE, Some(undelegated_block - 1)),
+				BbBNC::balance_of_at(&BOB, undelegated_block - 1)
+			);
+			assert_eq!(
+				BbBNC::total_supply(System::block_number()),
+				BbBNC::balance_of(&BOB, None)
+			);
+		});
+}
+
+#[test]
+fn delegate_with_markup_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			asset_registry();
+			System::set_block_number(System::block_number() + 20);
+
+			assert_ok!(BbBNC::set_config(
+				RuntimeOrigin::root(),
+				Some(0),
+				Some(7 * 86400 / 12)
+			));
+			assert_ok!(BbBNC::set_markup_coefficient(
+				RuntimeOrigin::root(),
+				VBNC,
+				FixedU128::from_inner(100_000_000_000_000_000), // 0.1
+				FixedU128::saturating_from_integer(1),
+				RWI,
+			));
+			assert_ok!(BbBNC::deposit_markup(
+				RuntimeOrigin::signed(BOB),
+				VBNC,
+				10_000_000_000_000
+			));
+			assert_ok!(BbBNC::create_lock_inner(
+				&BOB,
+				10_000_000_000_000,
+				System::block_number() + 365 * 86400 / 12,
+			));
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(2796030953200));
+
+			// The marked up voting power is delegated.
+			assert_ok!(BbBNC::delegate(RuntimeOrigin::signed(BOB), CHARLIE));
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(0));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(2796030953200));
+
+			assert_ok!(BbBNC::increase_amount(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID0,
+				10_000_000_000_000
+			));
+			let bob_balance = BbBNC::balance_of_current_block(&BOB).unwrap();
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(bob_balance));
+
+			// No voting power is left with the delegate.
+			assert_ok!(BbBNC::undelegate(RuntimeOrigin::signed(BOB)));
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(bob_balance));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(0));
+		});
+}
+
+#[test]
+fn split_merge_and_transfer_position_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			asset_registry();
+			System::set_block_number(System::block_number() + 20);
+
+			assert_ok!(BbBNC::set_config(
+				RuntimeOrigin::root(),
+				Some(0),
+				Some(7 * 86400 / 12)
+			));
+			assert_ok!(BbBNC::create_lock_inner(
+				&BOB,
+				10_000_000_000_000,
+				System::block_number() + (4 * 365 * 86400 - 5 * 86400) / 12,
+			));
+			System::set_block_number(System::block_number() + 10);
+			let balance = BbBNC::balance_of(&BOB, None).unwrap();
+			let supply = BbBNC::total_supply(System::block_number()).unwrap();
+
+			assert_noop!(
+				BbBNC::split_position(RuntimeOrigin::signed(CHARLIE), POSITIONID0, 1),
+				Error::<Runtime>::LockNotExist
+			);
+			assert_noop!(
+				BbBNC::split_position(RuntimeOrigin::signed(BOB), POSITIONID0, 10_000_000_000_000),
+				Error::<Runtime>::ArgumentsError
+			);
+
+			// Both parts are multiples of MaxBlock, so no slope is lost to rounding.
+			assert_ok!(BbBNC::split_position(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID0,
+				5_256_000_000_000
+			));
+			assert_eq!(
+				UserPositions::<Runtime>::get(BOB).into_inner(),
+				vec![POSITIONID0, POSITIONID1]
+			);
+			assert_eq!(
+				Locked::<Runtime>::get(POSITIONID0).amount,
+				4_744_000_000_000
+			);
+			assert_eq!(
+				Locked::<Runtime>::get(POSITIONID1).amount,
+				5_256_000_000_000
+			);
+			assert_eq!(
+				Locked::<Runtime>::get(POSITIONID0).end,
+				Locked::<Runtime>::get(POSITIONID1).end
+			);
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(balance));
+			assert_eq!(BbBNC::total_supply(System::block_number()), Ok(supply));
+			assert_eq!(UserLocked::<Runtime>::get(BOB), 10_000_000_000_000);
+			assert_eq!(Supply::<Runtime>::get(), 10_000_000_000_000);
+
+			assert_noop!(
+				BbBNC::merge_positions(RuntimeOrigin::signed(BOB), POSITIONID1, POSITIONID1),
+				Error::<Runtime>::ArgumentsError
+			);
+			assert_ok!(BbBNC::increase_unlock_time(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID1,
+				86400 / 12
+			));
+			assert_noop!(
+				BbBNC::merge_positions(RuntimeOrigin::signed(BOB), POSITIONID0, POSITIONID1),
+				Error::<Runtime>::UnlockTimeMismatch
+			);
+			assert_ok!(BbBNC::increase_unlock_time(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID0,
+				86400 / 12
+			));
+			let balance = BbBNC::balance_of(&BOB, None).unwrap();
+			let supply = BbBNC::total_supply(System::block_number()).unwrap();
+			let slope = PointHistory::<Runtime>::get(Epoch::<Runtime>::get()).slope;
+			assert_ok!(BbBNC::merge_positions(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID1,
+				POSITIONID0
+			));
+			assert_eq!(
+				PointHistory::<Runtime>::get(Epoch::<Runtime>::get()).slope,
+				slope
+			);
+			assert_eq!(
+				UserPositions::<Runtime>::get(BOB).into_inner(),
+				vec![POSITIONID0]
+			);
+			assert_eq!(
+				Locked::<Runtime>::get(POSITIONID0).amount,
+				10_000_000_000_000
+			);
+			assert_eq!(Locked::<Runtime>::get(POSITIONID1).amount, 0);
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(balance));
+			assert_eq!(BbBNC::total_supply(System::block_number()), Ok(supply));
+
+			assert_noop!(
+				BbBNC::transfer_position(RuntimeOrigin::signed(BOB), POSITIONID0, BOB),
+				Error::<Runtime>::ArgumentsError
+			);
+			let bob_free = Tokens::free_balance(VBNC, &BOB);
+			let charlie_free = Tokens::free_balance(VBNC, &CHARLIE);
+			let before_transfer = System::block_number();
+			System::set_block_number(System::block_number() + 10);
+			let balance = BbBNC::balance_of(&BOB, None).unwrap();
+			let supply = BbBNC::total_supply(System::block_number()).unwrap();
+			assert_ok!(BbBNC::transfer_position(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID0,
+				CHARLIE
+			));
+			System::assert_last_event(RuntimeEvent::BbBNC(Event::PositionTransferProposed {
+				who: BOB,
+				to: CHARLIE,
+				position: POSITIONID0,
+			}));
+			// Nothing moves until CHARLIE accepts the transfer.
+			assert_eq!(
+				UserPositions::<Runtime>::get(BOB).into_inner(),
+				vec![POSITIONID0]
+			);
+			assert_noop!(
+				BbBNC::accept_position_transfer(RuntimeOrigin::signed(ALICE), POSITIONID0),
+				Error::<Runtime>::NoPendingTransfer
+			);
+			assert_noop!(
+				BbBNC::cancel_position_transfer(RuntimeOrigin::signed(ALICE), POSITIONID0),
+				Error::<Runtime>::NoPendingTransfer
+			);
+			assert_ok!(BbBNC::cancel_position_transfer(
+				RuntimeOrigin::signed(CHARLIE),
+				POSITIONID0
+			));
+			System::assert_last_event(RuntimeEvent::BbBNC(Event::PositionTransferCancelled {
+				position: POSITIONID0,
+			}));
+			assert_noop!(
+				BbBNC::accept_position_transfer(RuntimeOrigin::signed(CHARLIE), POSITIONID0),
+				Error::<Runtime>::NoPendingTransfer
+			);
+
+			assert_ok!(BbBNC::transfer_position(
+				RuntimeOrigin::signed(BOB),
+				POSITIONID0,
+				CHARLIE
+			));
+			assert_ok!(BbBNC::accept_position_transfer(
+				RuntimeOrigin::signed(CHARLIE),
+				POSITIONID0
+			));
+			assert!(PendingPositionTransfers::<Runtime>::get(POSITIONID0).is_none());
+			System::assert_last_event(RuntimeEvent::BbBNC(Event::PositionTransferred {
+				who: BOB,
+				to: CHARLIE,
+				position: POSITIONID0,
+				new_position: 2,
+				value: 10_000_000_000_000,
+			}));
+			assert!(UserPositions::<Runtime>::get(BOB).is_empty());
+			assert_eq!(UserPositions::<Runtime>::get(CHARLIE).into_inner(), vec![2]);
+			assert_eq!(UserLocked::<Runtime>::get(BOB), 0);
+			assert_eq!(UserLocked::<Runtime>::get(CHARLIE), 10_000_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(VBNC, &BOB),
+				bob_free - 10_000_000_000_000
+			);
+			assert_eq!(
+				Tokens::free_balance(VBNC, &CHARLIE),
+				charlie_free + 10_000_000_000_000
+			);
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(0));
+			assert_eq!(BbBNC::balance_of(&CHARLIE, None), Ok(balance));
+			assert_eq!(BbBNC::total_supply(System::block_number()), Ok(supply));
+			// The voting power of BOB before the transfer is kept.
+			assert_eq!(
+				BbBNC::balance_of(&BOB, Some(before_transfer)),
+				BbBNC::balance_of_position_at(POSITIONID0, before_transfer)
+			);
+			assert!(BbBNC::balance_of(&BOB, Some(before_transfer)).unwrap() > balance);
+			assert_eq!(BbBNC::balance_of(&CHARLIE, Some(before_transfer)), Ok(0));
+
+			// Neither split nor transfer can exceed MaxPositions.
+			for _ in 1..MaxPositions::get() {
+				assert_ok!(BbBNC::create_lock_inner(
+					&CHARLIE,
+					10_000_000_000,
+					System::block_number() + 7 * 86400 / 12,
+				));
+			}
+			assert_noop!(
+				BbBNC::split_position(RuntimeOrigin::signed(CHARLIE), 2, 5_256_000_000_000),
+				Error::<Runtime>::ExceedsMaxPositions
+			);
+			assert_ok!(BbBNC::create_lock_inner(
+				&BOB,
+				10_000_000_000,
+				System::block_number() + 7 * 86400 / 12,
+			));
+			assert_ok!(BbBNC::transfer_position(
+				RuntimeOrigin::signed(BOB),
+				12,
+				CHARLIE
+			));
+			assert_noop!(
+				BbBNC::accept_position_transfer(RuntimeOrigin::signed(CHARLIE), 12),
+				Error::<Runtime>::ExceedsMaxPositions
+			);
+
+			// The transfer can't be accepted once the position changed.
+			assert_ok!(BbBNC::increase_amount(
+				RuntimeOrigin::signed(BOB),
+				12,
+				10_000_000_000
+			));
+			assert_noop!(
+				BbBNC::accept_position_transfer(RuntimeOrigin::signed(CHARLIE), 12),
+				Error::<Runtime>::PositionChanged
+			);
+		});
+}
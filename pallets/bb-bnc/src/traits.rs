@@ -24,7 +24,11 @@ use crate::*;
 pub trait BbBNCInterface<AccountId, CurrencyId, Balance, BlockNumber> {
 	fn deposit_for(_who: &AccountId, position: u128, value: Balance) -> DispatchResult;
 	fn withdraw_inner(who: &AccountId, position: u128) -> DispatchResult;
+	// Voting power of `who`, including the voting power delegated to and by `who`
 	fn balance_of(who: &AccountId, time: Option<BlockNumber>) -> Result<Balance, DispatchError>;
+	// Voting power of the positions of `who`, ignoring delegations
+	fn own_balance_of(who: &AccountId, time: Option<BlockNumber>)
+		-> Result<Balance, DispatchError>;
 	fn total_supply(t: BlockNumber) -> Result<Balance, DispatchError>;
 	fn supply_at(
 		point: Point<Balance, BlockNumber>,
@@ -112,6 +116,13 @@ where
 		Ok(Zero::zero())
 	}
 
+	fn own_balance_of(
+		_who: &AccountId,
+		_time: Option<BlockNumber>,
+	) -> Result<Balance, DispatchError> {
+		Ok(Zero::zero())
+	}
+
 	fn find_block_epoch(_block: BlockNumber, _max_epoch: U256) -> U256 {
 		U256::zero()
 	}
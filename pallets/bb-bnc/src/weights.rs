@@ -66,6 +66,13 @@ pub trait WeightInfo {
 	fn withdraw_markup() -> Weight;
 	fn redeem_unlock() -> Weight;
 	fn refresh() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn split_position() -> Weight;
+	fn merge_positions() -> Weight;
+	fn transfer_position() -> Weight;
+	fn accept_position_transfer() -> Weight;
+	fn cancel_position_transfer() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -608,4 +615,39 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(22_u64))
 			.saturating_add(RocksDbWeight::get().writes(12_u64))
 	}
+	fn delegate() -> Weight {
+		Weight::from_parts(62_934_000, 7282)
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	fn undelegate() -> Weight {
+		Weight::from_parts(64_371_000, 7427)
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	fn split_position() -> Weight {
+		Weight::from_parts(106_312_000, 7902)
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	fn merge_positions() -> Weight {
+		Weight::from_parts(103_486_000, 8044)
+			.saturating_add(RocksDbWeight::get().reads(22_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	fn transfer_position() -> Weight {
+		Weight::from_parts(20_000_000, 3700)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn accept_position_transfer() -> Weight {
+		Weight::from_parts(169_843_000, 8317)
+			.saturating_add(RocksDbWeight::get().reads(35_u64))
+			.saturating_add(RocksDbWeight::get().writes(27_u64))
+	}
+	fn cancel_position_transfer() -> Weight {
+		Weight::from_parts(15_000_000, 3700)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
@@ -298,6 +298,7 @@ impl bifrost_vtoken_minting::Config for Runtime {
 	type RelayChainToken = RelayCurrencyId;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = ();
 	type XcmTransfer = XTokens;
 	type MoonbeamChainId = MoonbeamChainId;
 	type ChannelCommission = ();
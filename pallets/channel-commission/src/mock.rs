@@ -202,6 +202,10 @@ impl VtokenMintingInterface<AccountId, CurrencyId, Balance> for SimpleVtokenMint
 		unimplemented!("method do not need to be implemented yet");
 	}
 
+	fn get_mint_fee(_token_amount: Balance) -> Balance {
+		unimplemented!("method do not need to be implemented yet");
+	}
+
 	fn get_moonbeam_parachain_id() -> u32 {
 		unimplemented!("method do not need to be implemented yet");
 	}
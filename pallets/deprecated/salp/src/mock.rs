@@ -431,6 +431,7 @@ impl bifrost_vtoken_minting::Config for Test {
 	type CurrencyIdRegister = AssetIdMaps<Test>;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = ();
 	type XcmTransfer = XTokens;
 	type MoonbeamChainId = MoonbeamChainId;
 	type BifrostSlpx = SlpxInterface;
@@ -624,7 +624,7 @@ pub mod pallet {
 			Self::add_share(&exchanger, pid, &mut pool_info, add_value);
 			let gauge_pid = pid + GAUGE_BASE_ID;
 			if let Some(mut gauge_pool_info) = PoolInfos::<T>::get(gauge_pid) {
-				let gauge_new_value = T::BbBNC::balance_of(&exchanger, None)?
+				let gauge_new_value = T::BbBNC::own_balance_of(&exchanger, None)?
 					.checked_mul(&add_value)
 					.ok_or(ArithmeticError::Overflow)?;
 				if let Some(share_info) = SharesAndWithdrawnRewards::<T>::get(gauge_pid, &exchanger)
@@ -696,7 +696,7 @@ pub mod pallet {
 			let gauge_pid = pid + GAUGE_BASE_ID;
 			if let Some(mut gauge_pool_info) = PoolInfos::<T>::get(gauge_pid) {
 				let native_remove_value = remove_value.unwrap_or(share_info.share);
-				let gauge_new_value = T::BbBNC::balance_of(&exchanger, None)?
+				let gauge_new_value = T::BbBNC::own_balance_of(&exchanger, None)?
 					.checked_mul(&share_info.share.saturating_sub(native_remove_value))
 					.ok_or(ArithmeticError::Overflow)?;
 				if let Some(gauge_share_info) =
@@ -1176,7 +1176,7 @@ impl<T: Config> FarmingInfo<BalanceOf<T>, CurrencyIdOf<T>, T::AccountId> for Pal
 			let gauge_pid = pid + GAUGE_BASE_ID;
 			if let Some(share_info) = SharesAndWithdrawnRewards::<T>::get(&pid, &exchanger) {
 				if let Some(mut gauge_pool_info) = PoolInfos::<T>::get(gauge_pid) {
-					let gauge_new_value = T::BbBNC::balance_of(&exchanger, None)?
+					let gauge_new_value = T::BbBNC::own_balance_of(&exchanger, None)?
 						.checked_mul(&share_info.share)
 						.ok_or(ArithmeticError::Overflow)?;
 					if let Some(share_info) =
@@ -1208,4 +1208,8 @@ impl<T: Config> FarmingInfo<BalanceOf<T>, CurrencyIdOf<T>, T::AccountId> for Pal
 
 		Ok(())
 	}
+
+	fn refresh_boost_votes(who: &T::AccountId) -> DispatchResult {
+		Self::refresh_vebnc_farming(who)
+	}
 }
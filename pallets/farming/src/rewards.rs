@@ -493,7 +493,7 @@ impl<T: Config> Pallet<T> {
 		let gauge_pid = pid + GAUGE_BASE_ID;
 		if let Some(share_info) = SharesAndWithdrawnRewards::<T>::get(&pid, &exchanger) {
 			if let Some(mut gauge_pool_info) = PoolInfos::<T>::get(gauge_pid) {
-				let gauge_new_value = T::BbBNC::balance_of(&exchanger, None)?
+				let gauge_new_value = T::BbBNC::own_balance_of(&exchanger, None)?
 					.checked_mul(&share_info.share)
 					.ok_or(ArithmeticError::Overflow)?;
 				if let Some(share_info) = SharesAndWithdrawnRewards::<T>::get(gauge_pid, &exchanger)
@@ -683,6 +683,64 @@ fn vote() {
 		})
 }
 
+#[test]
+fn delegate_refreshes_boost_votes() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, _tokens) = init_gauge();
+			let vote_list = vec![(pid, Percent::from_percent(100))];
+			let whitelist = vec![pid];
+			assert_ok!(Farming::set_next_round_whitelist(
+				RuntimeOrigin::signed(ALICE),
+				whitelist.clone()
+			));
+			assert_ok!(Farming::add_boost_pool_whitelist(
+				RuntimeOrigin::signed(ALICE),
+				whitelist.clone()
+			));
+			assert_ok!(Farming::start_boost_round(
+				RuntimeOrigin::signed(ALICE),
+				100
+			));
+			assert_ok!(Farming::vote(
+				RuntimeOrigin::signed(ALICE),
+				vote_list.clone()
+			));
+			let alice_votes = UserBoostInfos::<Runtime>::get(ALICE).unwrap().vote_amount;
+			assert_eq!(BoostPoolInfos::<Runtime>::get().total_votes, alice_votes);
+
+			// The votes of ALICE are voided and BOB votes with the delegated power.
+			assert_ok!(BbBNC::delegate(RuntimeOrigin::signed(ALICE), BOB));
+			assert_eq!(
+				UserBoostInfos::<Runtime>::get(ALICE).unwrap().vote_amount,
+				0
+			);
+			assert_eq!(BoostPoolInfos::<Runtime>::get().total_votes, 0);
+			assert_eq!(BoostVotingPools::<Runtime>::get(pid), Some(0));
+			assert_ok!(Farming::vote(RuntimeOrigin::signed(BOB), vote_list.clone()));
+			assert_eq!(
+				UserBoostInfos::<Runtime>::get(BOB).unwrap().vote_amount,
+				alice_votes
+			);
+			assert_eq!(BoostPoolInfos::<Runtime>::get().total_votes, alice_votes);
+			assert_eq!(BbBNC::balance_of(&ALICE, None), Ok(0));
+			assert_eq!(BbBNC::balance_of(&BOB, None), Ok(alice_votes));
+			// The gauge keeps the own voting power of ALICE.
+			assert_eq!(BbBNC::own_balance_of(&ALICE, None), Ok(alice_votes));
+
+			assert_ok!(BbBNC::undelegate(RuntimeOrigin::signed(ALICE)));
+			assert_eq!(UserBoostInfos::<Runtime>::get(BOB).unwrap().vote_amount, 0);
+			assert_eq!(
+				UserBoostInfos::<Runtime>::get(ALICE).unwrap().vote_amount,
+				alice_votes
+			);
+			assert_eq!(BoostPoolInfos::<Runtime>::get().total_votes, alice_votes);
+			assert_eq!(BoostVotingPools::<Runtime>::get(pid), Some(alice_votes));
+		})
+}
+
 #[test]
 fn charge_boost() {
 	ExtBuilder::default()
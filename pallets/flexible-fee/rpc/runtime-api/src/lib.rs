@@ -18,9 +18,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::{Balance, CurrencyId};
+use bifrost_primitives::{Balance, CurrencyId, FeeOption};
 use parity_scale_codec::Codec;
 use sp_api::decl_runtime_apis;
+use sp_std::vec::Vec;
 
 decl_runtime_apis! {
 	pub trait FlexibleFeeRuntimeApi<AccountId> where
@@ -28,5 +29,8 @@ decl_runtime_apis! {
 	{
 		/// get flexible fee token and amount to be deducted
 		fn get_fee_token_and_amount(who: AccountId, fee: Balance, utx: Block::Extrinsic) -> (CurrencyId, Balance);
+
+		/// get the fee and the extra fees in every fee currency, in the order they are tried
+		fn get_fee_options(who: AccountId, fee: Balance, utx: Block::Extrinsic) -> Vec<FeeOption<AccountId, Balance>>;
 	}
 }
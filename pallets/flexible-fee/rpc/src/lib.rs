@@ -19,7 +19,7 @@
 use std::{convert::TryInto, marker::PhantomData, sync::Arc};
 
 pub use bifrost_flexible_fee_rpc_runtime_api::FlexibleFeeRuntimeApi as FeeRuntimeApi;
-use bifrost_primitives::{Balance, CurrencyId};
+use bifrost_primitives::{Balance, CurrencyId, FeeOption};
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
@@ -58,6 +58,16 @@ pub trait FeeRpcApi<BlockHash, AccountId> {
 		encoded_xt: Bytes,
 		at: Option<BlockHash>,
 	) -> RpcResult<(CurrencyId, NumberOrHex)>;
+
+	/// rpc method get the fee and the extra fees of an extrinsic in every fee currency of an
+	/// account, in the order they are tried
+	#[method(name = "flexibleFee_getFeeOptions")]
+	fn get_fee_options(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<FeeOption<AccountId, NumberOrHex>>>;
 }
 
 /// Error type of this RPC api.
@@ -77,29 +87,19 @@ impl From<Error> for i32 {
 	}
 }
 
-#[async_trait]
-impl<C, Block, AccountId> FeeRpcApiServer<<Block as BlockT>::Hash, AccountId>
-	for FlexibleFeeRpc<C, Block>
+impl<C, Block> FlexibleFeeRpc<C, Block>
 where
 	Block: BlockT,
-	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: FeeRuntimeApi<Block, AccountId> + TransactionPaymentRuntimeApi<Block, Balance>,
-	AccountId: Codec,
-	Balance: Codec + std::fmt::Display + std::ops::Add<Output = Balance> + sp_runtime::traits::Zero,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
 {
-	fn get_fee_token_and_amount(
+	/// Decodes the extrinsic and queries its inclusion fee.
+	fn decode_and_query_inclusion_fee(
 		&self,
-		who: AccountId,
 		encoded_xt: Bytes,
-		at: Option<<Block as BlockT>::Hash>,
-	) -> RpcResult<(CurrencyId, NumberOrHex)> {
-		// Ok((
-		//     CurrencyId::Native(TokenSymbol::BNC),
-		//     sp_rpc::number::NumberOrHex::Number(1200),
-		// ))
-
+		at: <Block as BlockT>::Hash,
+	) -> RpcResult<(Block::Extrinsic, Balance)> {
 		let api = self.client.runtime_api();
-		let at = at.unwrap_or_else(|| self.client.info().best_hash);
 		let encoded_len = encoded_xt.len() as u32;
 
 		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| {
@@ -132,17 +132,46 @@ where
 			}
 		};
 
-		let rs = api.get_fee_token_and_amount(at, who, total_inclusion_fee, uxt);
+		Ok((uxt, total_inclusion_fee))
+	}
+}
 
-		let try_into_rpc_balance = |value: Balance| {
-			value.try_into().map_err(|e| {
-				ErrorObject::owned(
-					Error::RuntimeError.into(),
-					format!("{} doesn't fit in NumberOrHex representation", value),
-					Some(format!("{:?}", e)),
-				)
-			})
-		};
+fn try_into_rpc_balance(value: Balance) -> RpcResult<NumberOrHex> {
+	value.try_into().map_err(|e| {
+		ErrorObject::owned(
+			Error::RuntimeError.into(),
+			format!("{} doesn't fit in NumberOrHex representation", value),
+			Some(format!("{:?}", e)),
+		)
+	})
+}
+
+#[async_trait]
+impl<C, Block, AccountId> FeeRpcApiServer<<Block as BlockT>::Hash, AccountId>
+	for FlexibleFeeRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: FeeRuntimeApi<Block, AccountId> + TransactionPaymentRuntimeApi<Block, Balance>,
+	AccountId: Codec,
+	Balance: Codec + std::fmt::Display + std::ops::Add<Output = Balance> + sp_runtime::traits::Zero,
+{
+	fn get_fee_token_and_amount(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(CurrencyId, NumberOrHex)> {
+		// Ok((
+		//     CurrencyId::Native(TokenSymbol::BNC),
+		//     sp_rpc::number::NumberOrHex::Number(1200),
+		// ))
+
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (uxt, total_inclusion_fee) = self.decode_and_query_inclusion_fee(encoded_xt, at)?;
+
+		let rs = api.get_fee_token_and_amount(at, who, total_inclusion_fee, uxt);
 
 		match rs {
 			Ok((id, val)) => try_into_rpc_balance(val).map(|value| (id, value)),
@@ -153,4 +182,42 @@ where
 			)),
 		}
 	}
+
+	fn get_fee_options(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<FeeOption<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (uxt, total_inclusion_fee) = self.decode_and_query_inclusion_fee(encoded_xt, at)?;
+
+		let fee_options = api
+			.get_fee_options(at, who, total_inclusion_fee, uxt)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query fee options.",
+					Some(format!("{:?}", e)),
+				)
+			})?;
+
+		fee_options
+			.into_iter()
+			.map(|fee_option| {
+				Ok(FeeOption {
+					currency_id: fee_option.currency_id,
+					fee_amount: try_into_rpc_balance(fee_option.fee_amount)?,
+					extra_fees: fee_option
+						.extra_fees
+						.into_iter()
+						.map(|(amount, receiver)| Ok((try_into_rpc_balance(amount)?, receiver)))
+						.collect::<RpcResult<_>>()?,
+					affordable: fee_option.affordable,
+					sponsor: fee_option.sponsor,
+				})
+			})
+			.collect()
+	}
 }
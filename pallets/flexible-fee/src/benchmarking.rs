@@ -21,11 +21,13 @@
 use frame_benchmarking::v2::*;
 use frame_support::BoundedVec;
 
-use bifrost_primitives::{CurrencyId, TokenSymbol};
+use bifrost_primitives::{CurrencyId, TokenSymbol, BNC};
 use frame_system::RawOrigin;
+use orml_traits::MultiCurrency;
+use sp_runtime::{Percent, Permill};
 use sp_std::vec;
 
-use crate::{Call, Config, Pallet};
+use crate::{Call, Config, ExtraFee, ExtraFeeFilter, Pallet};
 
 #[benchmarks]
 mod benchmarks {
@@ -53,6 +55,76 @@ mod benchmarks {
 
 		Ok(())
 	}
+
+	#[benchmark]
+	fn set_dex_fee_conversion() -> Result<(), BenchmarkError> {
+		#[extrinsic_call]
+		_(RawOrigin::Root, Some(Percent::from_percent(5)));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn fund_sponsor_budget() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		T::MultiCurrency::deposit(BNC, &caller, 1_000_000_000_000_000)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 1_000_000_000_000);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw_sponsor_budget() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		T::MultiCurrency::deposit(BNC, &caller, 1_000_000_000_000_000)?;
+		Pallet::<T>::fund_sponsor_budget(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000_000_000_000,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 500_000_000_000);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_sponsorship_rule() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		T::MultiCurrency::deposit(BNC, &caller, 1_000_000_000_000_000)?;
+		let raw_call_name = BoundedVec::try_from(vec![10, 0]).unwrap();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			raw_call_name,
+			Some(1_000_000_000_000),
+		);
+
+		Ok(())
+	}
+	#[benchmark]
+	fn set_extra_fee_rule() -> Result<(), BenchmarkError> {
+		let receiver: T::AccountId = whitelisted_caller();
+		let raw_call_name = BoundedVec::try_from(vec![10]).unwrap();
+		let filter = ExtraFeeFilter {
+			currency_id: Some(BNC),
+			destination: None,
+		};
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			raw_call_name,
+			filter,
+			Some((ExtraFee::Proportional(Permill::from_percent(1)), receiver)),
+		);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mocks::kusama_mock::new_test_ext_benchmark(),
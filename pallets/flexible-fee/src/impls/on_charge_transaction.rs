@@ -16,21 +16,39 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Config, ExtraFeeByCall, Pallet};
+use crate::{Config, DexFeeConversionPremium, Error, Event, FeeSwapRoute, Pallet, WeightInfo};
 use bifrost_primitives::{Balance, CurrencyId, OraclePriceProvider, Price, BNC};
+use frame_support::{dispatch::DispatchClass, storage::with_storage_layer};
 use orml_traits::MultiCurrency;
 use pallet_transaction_payment::OnChargeTransaction;
-use parity_scale_codec::Encode;
 use sp_core::Get;
 use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
 	traits::{DispatchInfoOf, PostDispatchInfoOf, Zero},
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	DispatchResult, PerThing, Rounding,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum PaymentInfo {
+pub enum PaymentInfo<AccountId> {
 	Native(Balance),
 	NonNative(Balance, CurrencyId, Price, Price),
+	/// Paid in another currency which is swapped into the BNC fee through the DEX
+	Swapped(Balance, CurrencyId, Balance, FeeSwapRoute),
+	/// Paid in BNC from the budget of a sponsor
+	Sponsored(Balance, AccountId),
+}
+
+impl<AccountId> PaymentInfo<AccountId> {
+	pub fn fee_currency_and_amount(&self) -> (CurrencyId, Balance) {
+		match self {
+			PaymentInfo::Native(fee_amount) | PaymentInfo::Sponsored(fee_amount, _) => {
+				(BNC, *fee_amount)
+			}
+			PaymentInfo::NonNative(fee_amount, fee_currency, _, _)
+			| PaymentInfo::Swapped(fee_amount, fee_currency, _, _) => (*fee_currency, *fee_amount),
+		}
+	}
 }
 
 /// Default implementation for a Currency and an OnUnbalanced handler.
@@ -40,7 +58,7 @@ where
 	T::MultiCurrency: MultiCurrency<T::AccountId, CurrencyId = CurrencyId>,
 {
 	type Balance = Balance;
-	type LiquidityInfo = Option<PaymentInfo>;
+	type LiquidityInfo = Option<PaymentInfo<T::AccountId>>;
 
 	/// Withdraw the predicted fee from the transaction origin.
 	///
@@ -56,48 +74,37 @@ where
 			return Ok(None);
 		}
 
-		let (fee_currency, fee_amount, bnc_price, fee_currency_price) =
-			Self::get_fee_currency_and_fee_amount(who, fee)
-				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+		let payment = match Self::get_fee_sponsor(who, call, fee) {
+			Some(sponsor) => {
+				// withdraw extrinsic fee from the budget of the sponsor
+				Self::charge_sponsor(&sponsor, who, fee)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				PaymentInfo::Sponsored(fee, sponsor)
+			}
+			None => {
+				let payment = Self::get_fee_currency_and_fee_amount(who, fee)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				let (fee_currency, fee_amount) = payment.fee_currency_and_amount();
 
-		// withdraw normal extrinsic fee
-		T::MultiCurrency::withdraw(fee_currency, who, fee_amount)
-			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				// withdraw normal extrinsic fee
+				T::MultiCurrency::withdraw(fee_currency, who, fee_amount)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				payment
+			}
+		};
 
-		for (call_name, (extra_fee_currency, extra_fee_amount, extra_fee_receiver)) in
-			ExtraFeeByCall::<T>::iter()
+		for (extra_fee_currency, extra_fee_amount, extra_fee_receiver) in Self::get_extra_fees(call)
 		{
-			let raw_call_name = call_name.to_vec();
-			let raw_call_name_len = raw_call_name.len();
-			if call.encode().len() >= raw_call_name_len {
-				if call.encode()[0..raw_call_name_len].eq(&raw_call_name) {
-					match Self::charge_extra_fee(
-						who,
-						extra_fee_currency,
-						extra_fee_amount,
-						&extra_fee_receiver,
-					) {
-						Ok(_) => {}
-						Err(_) => {
-							return Err(TransactionValidityError::Invalid(
-								InvalidTransaction::Payment,
-							));
-						}
-					}
-				};
-			}
+			Self::charge_extra_fee(
+				who,
+				extra_fee_currency,
+				extra_fee_amount,
+				&extra_fee_receiver,
+			)
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
 		}
 
-		if fee_currency == BNC {
-			Ok(Some(PaymentInfo::Native(fee_amount)))
-		} else {
-			Ok(Some(PaymentInfo::NonNative(
-				fee_amount,
-				fee_currency,
-				bnc_price,
-				fee_currency_price,
-			)))
-		}
+		Ok(Some(payment))
 	}
 
 	/// Hand the fee and the tip over to the `[OnUnbalanced]` implementation.
@@ -114,53 +121,144 @@ where
 		already_withdrawn: Self::LiquidityInfo,
 	) -> Result<(), TransactionValidityError> {
 		if let Some(paid) = already_withdrawn {
-			// Calculate how much refund we should return
-			let (currency, refund, fee, tip) = match paid {
-				PaymentInfo::Native(paid_fee) => (
-					BNC,
-					paid_fee.saturating_sub(corrected_fee),
-					corrected_fee.saturating_sub(tip),
-					tip,
-				),
-				PaymentInfo::NonNative(paid_fee, fee_currency, bnc_price, fee_currency_price) => {
-					// calculate corrected_fee in the non-native currency
-					let converted_corrected_fee = T::OraclePriceProvider::get_amount_by_prices(
-						&BNC,
-						corrected_fee,
-						bnc_price,
-						&fee_currency,
-						fee_currency_price,
-					)
-					.ok_or(TransactionValidityError::Invalid(
-						InvalidTransaction::Payment,
-					))?;
-					let refund = paid_fee.saturating_sub(converted_corrected_fee);
-					let converted_tip = T::OraclePriceProvider::get_amount_by_prices(
-						&BNC,
-						tip,
-						bnc_price,
-						&fee_currency,
-						fee_currency_price,
-					)
-					.ok_or(TransactionValidityError::Invalid(
-						InvalidTransaction::Payment,
-					))?;
-					(
-						fee_currency,
-						refund,
-						converted_corrected_fee.saturating_sub(converted_tip),
-						converted_tip,
-					)
-				}
-			};
-			// refund to the account that paid the fees
-			T::MultiCurrency::deposit(currency, who, refund)
+			Self::refund_and_deposit_fee(who, corrected_fee, tip, paid)
 				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+		}
+		Ok(())
+	}
+}
 
-			// deposit the fee
-			T::MultiCurrency::deposit(currency, &T::TreasuryAccount::get(), fee + tip)
-				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+impl<T: Config> Pallet<T> {
+	/// Refunds the part of the `paid` fee above `corrected_fee` of BNC to `who` and deposits the
+	/// rest to the treasury, swapping it into BNC if it was priced by the DEX.
+	pub fn refund_and_deposit_fee(
+		who: &T::AccountId,
+		corrected_fee: Balance,
+		tip: Balance,
+		paid: PaymentInfo<T::AccountId>,
+	) -> DispatchResult {
+		// Calculate how much refund we should return
+		let (currency, refund, fee, tip) = match paid {
+			PaymentInfo::Native(paid_fee) => (
+				BNC,
+				paid_fee.saturating_sub(corrected_fee),
+				corrected_fee.saturating_sub(tip),
+				tip,
+			),
+			PaymentInfo::Sponsored(paid_fee, ref sponsor) => {
+				// refund to the budget of the sponsor
+				Self::refund_sponsor(sponsor, who, paid_fee.saturating_sub(corrected_fee))?;
+				(BNC, Zero::zero(), corrected_fee.saturating_sub(tip), tip)
+			}
+			PaymentInfo::NonNative(paid_fee, fee_currency, bnc_price, fee_currency_price) => {
+				// calculate corrected_fee in the non-native currency, if the conversion fails the
+				// whole paid fee goes to the treasury
+				let converted_corrected_fee = T::OraclePriceProvider::get_amount_by_prices(
+					&BNC,
+					corrected_fee,
+					bnc_price,
+					&fee_currency,
+					fee_currency_price,
+				)
+				.unwrap_or(paid_fee)
+				.min(paid_fee);
+				let refund = paid_fee.saturating_sub(converted_corrected_fee);
+				let converted_tip = T::OraclePriceProvider::get_amount_by_prices(
+					&BNC,
+					tip,
+					bnc_price,
+					&fee_currency,
+					fee_currency_price,
+				)
+				.unwrap_or(Zero::zero())
+				.min(converted_corrected_fee);
+				(
+					fee_currency,
+					refund,
+					converted_corrected_fee.saturating_sub(converted_tip),
+					converted_tip,
+				)
+			}
+			PaymentInfo::Swapped(paid_fee, fee_currency, paid_bnc, _) => {
+				let converted_corrected_fee =
+					Self::swapped_fee_amount(paid_fee, paid_bnc, corrected_fee);
+				let refund = paid_fee.saturating_sub(converted_corrected_fee);
+				let converted_tip = Self::swapped_fee_amount(paid_fee, paid_bnc, tip);
+				(
+					fee_currency,
+					refund,
+					converted_corrected_fee.saturating_sub(converted_tip),
+					converted_tip,
+				)
+			}
+		};
+		Self::deposit_refund_and_fee(who, currency, refund, fee + tip)?;
+
+		// swap the fee and the tip into BNC, if it fails they are kept in their currency
+		if let PaymentInfo::Swapped(paid_fee, _, paid_bnc, route) = paid {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				<T as Config>::WeightInfo::swap_fee_to_native(),
+				DispatchClass::Mandatory,
+			);
+			let amount = fee.saturating_add(tip);
+			// the BNC the swapped amount was priced at when the fee was withdrawn
+			let bnc_amount =
+				multiply_by_rational_with_rounding(amount, paid_bnc, paid_fee, Rounding::Down)
+					.unwrap_or(corrected_fee);
+			let max_premium = DexFeeConversionPremium::<T>::get().unwrap_or_default();
+			let min_bnc_amount = bnc_amount.saturating_sub(max_premium.mul_ceil(bnc_amount));
+			if let Err(error) = with_storage_layer(|| {
+				Self::swap_fee_to_native(route, currency, amount, min_bnc_amount)
+			}) {
+				Self::deposit_event(Event::FeeSwapFailed {
+					currency_id: currency,
+					amount,
+					error,
+				});
+			}
 		}
 		Ok(())
 	}
+
+	/// Deposits the `refund` of a fee back to `who` and the `fee` to the treasury.
+	pub fn deposit_refund_and_fee(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		refund: Balance,
+		fee: Balance,
+	) -> DispatchResult {
+		// refund to the account that paid the fees. If this fails, the account might have dropped
+		// below the existential balance, in that case the refund goes to the treasury.
+		let unrefunded = match T::MultiCurrency::deposit(currency_id, who, refund) {
+			Ok(_) => Zero::zero(),
+			Err(_) => refund,
+		};
+
+		// deposit the fee
+		T::MultiCurrency::deposit(
+			currency_id,
+			&T::TreasuryAccount::get(),
+			fee.saturating_add(unrefunded),
+		)
+	}
+
+	/// Withdraws a fee of `fee_amount` BNC from `who` in `currency_id`, priced the same way as
+	/// the fees of extrinsics.
+	pub fn withdraw_fee_in_currency(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		fee_amount: Balance,
+	) -> Result<PaymentInfo<T::AccountId>, Error<T>> {
+		let payment = Self::get_fee_payments(currency_id, fee_amount)?
+			.into_iter()
+			.find(|payment| {
+				let (fee_currency, fee_amount) = payment.fee_currency_and_amount();
+				T::MultiCurrency::ensure_can_withdraw(fee_currency, who, fee_amount).is_ok()
+			})
+			.ok_or(Error::<T>::NotEnoughBalance)?;
+		let (fee_currency, fee_amount) = payment.fee_currency_and_amount();
+		T::MultiCurrency::withdraw(fee_currency, who, fee_amount)
+			.map_err(|_| Error::<T>::NotEnoughBalance)?;
+		Ok(payment)
+	}
 }
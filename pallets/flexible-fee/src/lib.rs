@@ -18,12 +18,15 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use crate::impls::on_charge_transaction::PaymentInfo;
 pub use crate::pallet::*;
 use bifrost_asset_registry::{AssetMetadata, CurrencyIdMapping};
 use bifrost_primitives::{
 	traits::XcmDestWeightAndFeeHandler, AssetHubChainId, Balance, BalanceCmp, CurrencyId,
-	DerivativeIndex, OraclePriceProvider, Price, TryConvertFrom, XcmOperationType, BNC, VBNC, WETH,
+	DerivativeIndex, FeeOption, OraclePriceProvider, TryConvertFrom, XcmOperationType, BNC, VBNC,
+	WETH,
 };
+use bifrost_stable_pool::traits::StablePoolHandler;
 use bifrost_xcm_interface::calls::{PolkadotXcmCall, RelaychainCall};
 use core::convert::Into;
 use cumulus_primitives_core::ParaId;
@@ -47,8 +50,9 @@ use polkadot_parachain_primitives::primitives::Sibling;
 use sp_arithmetic::traits::UniqueSaturatedInto;
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
-	traits::{AccountIdConversion, One},
-	BoundedVec, ModuleError, TransactionOutcome,
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AccountIdConversion, Zero},
+	BoundedVec, ModuleError, PerThing, Percent, Permill, Rounding, TransactionOutcome,
 };
 use sp_std::{boxed::Box, cmp::Ordering, vec, vec::Vec};
 pub use weights::WeightInfo;
@@ -76,6 +80,61 @@ pub enum TargetChain {
 	RelayChain,
 }
 
+/// The venue where fees paid in other currencies are swapped into BNC.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FeeSwapRoute {
+	Zenlink,
+	StablePool,
+}
+
+/// The extra fee charged by a rule.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ExtraFee {
+	/// A fixed amount of a currency
+	Flat(CurrencyId, Balance),
+	/// A proportion of the amount of the call, in the currency of the call
+	Proportional(Permill),
+}
+
+/// The arguments of a call extra fee rules match on.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Default)]
+pub struct ExtraFeeCallArgs {
+	/// The currency the call operates on
+	pub currency_id: Option<CurrencyId>,
+	/// The chain the call sends the assets to
+	pub destination: Option<Location>,
+	/// The amount of `currency_id` the call operates on
+	pub amount: Option<Balance>,
+}
+
+/// The arguments a rule requires of a call, on top of its raw call name. `None` matches any.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub struct ExtraFeeFilter {
+	pub currency_id: Option<CurrencyId>,
+	pub destination: Option<Location>,
+}
+
+impl ExtraFeeFilter {
+	pub fn matches(&self, args: &ExtraFeeCallArgs) -> bool {
+		self.currency_id
+			.map_or(true, |currency_id| args.currency_id == Some(currency_id))
+			&& self.destination.as_ref().map_or(true, |destination| {
+				args.destination.as_ref() == Some(destination)
+			})
+	}
+}
+
+/// Reads the arguments extra fee rules match on from the calls of the runtime.
+pub trait InspectExtraFeeCall<Call> {
+	fn call_args(call: &Call) -> ExtraFeeCallArgs;
+}
+
+impl<Call> InspectExtraFeeCall<Call> for () {
+	fn call_args(_call: &Call) -> ExtraFeeCallArgs {
+		Default::default()
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -95,6 +154,12 @@ pub mod pallet {
 		type XcmRouter: SendXcm;
 		/// Zenlink interface
 		type DexOperator: ExportZenlink<Self::AccountId, AssetId>;
+		/// Stable pool interface
+		type StablePoolHandler: StablePoolHandler<
+			Balance = Balance,
+			AccountId = Self::AccountId,
+			CurrencyId = CurrencyId,
+		>;
 		/// The oracle price feeder
 		type OraclePriceProvider: OraclePriceProvider;
 		/// The only origin that can set universal fee currency order list
@@ -128,6 +193,20 @@ pub mod pallet {
 		/// The `AllowVBNCAsFee` constant determines whether VBNC is allowed as a fee currency.
 		#[pallet::constant]
 		type AllowVBNCAsFee: Get<bool>;
+		/// The number of blocks in a day, the period of the sponsorship caps of users.
+		#[pallet::constant]
+		type BlocksPerDay: Get<BlockNumberFor<Self>>;
+		/// The BNC held from a sponsor for each of its sponsorship rules.
+		#[pallet::constant]
+		type SponsorshipRuleDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of sponsorship rules of a sponsor.
+		#[pallet::constant]
+		type MaxSponsorshipRules: Get<u32>;
+		/// The maximum number of sponsorship rules of the calls with the same call index.
+		#[pallet::constant]
+		type MaxSponsorsPerCall: Get<u32>;
+		/// Reads the currency, destination and amount of calls for the extra fee rules.
+		type InspectExtraFeeCall: InspectExtraFeeCall<<Self as frame_system::Config>::RuntimeCall>;
 	}
 
 	#[pallet::hooks]
@@ -178,6 +257,46 @@ pub mod pallet {
 			/// currency_id, fee_amount, receiver
 			fee_info: Option<(CurrencyId, BalanceOf<T>, T::AccountId)>,
 		},
+		/// Set the maximum premium over the oracle price of swapping fees into BNC
+		SetDexFeeConversion { max_premium: Option<Percent> },
+		/// Sponsor funded its budget
+		SponsorBudgetFunded {
+			sponsor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Sponsor withdrew from its budget
+		SponsorBudgetWithdrawn {
+			sponsor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Set sponsorship rule by call
+		SetSponsorshipRule {
+			sponsor: T::AccountId,
+			/// The raw call name whose fees are sponsored.
+			raw_call_name: RawCallName,
+			/// The maximum fee sponsored per user and day
+			daily_cap: Option<BalanceOf<T>>,
+		},
+		/// Transaction fee paid by a sponsor
+		FeeSponsored {
+			sponsor: T::AccountId,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Set extra fee rule by call and arguments
+		SetExtraFeeRule {
+			/// The raw call name prefix of the calls, a pallet index matches the whole pallet.
+			raw_call_name: RawCallName,
+			filter: ExtraFeeFilter,
+			/// extra fee, receiver
+			rule: Option<(ExtraFee, T::AccountId)>,
+		},
+		/// Swapping a fee collected by the treasury into BNC failed, it is kept in its currency
+		FeeSwapFailed {
+			currency_id: CurrencyId,
+			amount: BalanceOf<T>,
+			error: DispatchError,
+		},
 	}
 
 	/// The current storage version, we set to 2 our new version.
@@ -204,6 +323,68 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Extra fee rules by pallet index, or pallet and call index, and the arguments of the calls
+	#[pallet::storage]
+	pub type ExtraFeeRules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		RawCallName,
+		Blake2_128Concat,
+		ExtraFeeFilter,
+		(ExtraFee, T::AccountId),
+		OptionQuery,
+	>;
+
+	/// If set, fees paid in other currencies are swapped into BNC through the DEX, at a price at
+	/// most this premium over the oracle price
+	#[pallet::storage]
+	pub type DexFeeConversionPremium<T: Config> = StorageValue<_, Percent, OptionQuery>;
+
+	/// The BNC held by the pallet account to pay the fees sponsored by an account
+	#[pallet::storage]
+	pub type SponsorBudgets<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The maximum fee a sponsor pays per user and day and the deposit held for the rule, by call
+	#[pallet::storage]
+	pub type SponsorshipRules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		RawCallName,
+		(BalanceOf<T>, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// The number of sponsorship rules of a sponsor
+	#[pallet::storage]
+	pub type SponsorshipRuleCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The sponsors and raw call names of the sponsorship rules, by the pallet and call index of
+	/// the raw call names
+	#[pallet::storage]
+	pub type SponsorsByCall<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		RawCallName,
+		BoundedVec<(T::AccountId, RawCallName), T::MaxSponsorsPerCall>,
+		ValueQuery,
+	>;
+
+	/// The day and the fees a sponsor paid for a user on that day
+	#[pallet::storage]
+	pub type SponsoredFees<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(BlockNumberFor<T>, BalanceOf<T>),
+		ValueQuery,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
@@ -234,6 +415,14 @@ pub mod pallet {
 		EvmPermitRunnerError,
 		/// Percentage calculation failed due to overflow.
 		PercentageCalculationFailed,
+		/// The budget of the sponsor is not enough.
+		NotEnoughSponsorBudget,
+		/// The sponsor has reached the maximum number of sponsorship rules.
+		TooManySponsorshipRules,
+		/// The call has reached the maximum number of sponsors.
+		TooManySponsors,
+		/// The raw call name of an extra fee rule is not a pallet index or a pallet and call index.
+		InvalidRawCallName,
 	}
 
 	#[pallet::call]
@@ -368,6 +557,132 @@ pub mod pallet {
 
 			Ok(result)
 		}
+
+		/// Set the DEX conversion of fees paid in other currencies
+		/// Parameters:
+		/// - `max_premium`: The maximum premium over the oracle price of swapping the fees into
+		///   BNC. If `None`, fees are priced by the oracle and kept in their currency.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_dex_fee_conversion())]
+		pub fn set_dex_fee_conversion(
+			origin: OriginFor<T>,
+			max_premium: Option<Percent>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			DexFeeConversionPremium::<T>::set(max_premium);
+			Self::deposit_event(Event::<T>::SetDexFeeConversion { max_premium });
+			Ok(())
+		}
+
+		/// Fund the budget from which the sponsored fees are paid
+		/// Parameters:
+		/// - `amount`: The amount of BNC added to the budget.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::fund_sponsor_budget())]
+		pub fn fund_sponsor_budget(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			T::MultiCurrency::transfer(BNC, &sponsor, &Self::sponsor_account(), amount)?;
+			SponsorBudgets::<T>::mutate(&sponsor, |budget| *budget = budget.saturating_add(amount));
+			Self::deposit_event(Event::<T>::SponsorBudgetFunded { sponsor, amount });
+			Ok(())
+		}
+
+		/// Withdraw from the budget from which the sponsored fees are paid
+		/// Parameters:
+		/// - `amount`: The amount of BNC withdrawn from the budget.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_sponsor_budget())]
+		pub fn withdraw_sponsor_budget(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			SponsorBudgets::<T>::try_mutate(&sponsor, |budget| -> DispatchResult {
+				*budget = budget
+					.checked_sub(amount)
+					.ok_or(Error::<T>::NotEnoughSponsorBudget)?;
+				Ok(())
+			})?;
+			T::MultiCurrency::transfer(BNC, &Self::sponsor_account(), &sponsor, amount)?;
+			Self::deposit_event(Event::<T>::SponsorBudgetWithdrawn { sponsor, amount });
+			Ok(())
+		}
+
+		/// Set sponsorship rule by call
+		/// Parameters:
+		/// - `raw_call_name`: The raw call name whose fees are sponsored.
+		/// - `daily_cap`: The maximum fee sponsored per user and day. If `None`, the rule will be
+		///   removed.
+		///
+		/// A new rule holds `SponsorshipRuleDeposit` BNC from the sponsor until it is removed.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsorship_rule())]
+		pub fn set_sponsorship_rule(
+			origin: OriginFor<T>,
+			raw_call_name: RawCallName,
+			daily_cap: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			match (
+				daily_cap,
+				SponsorshipRules::<T>::get(&sponsor, &raw_call_name),
+			) {
+				(Some(daily_cap), Some((_, deposit))) => {
+					SponsorshipRules::<T>::insert(&sponsor, &raw_call_name, (daily_cap, deposit))
+				}
+				(Some(daily_cap), None) => {
+					Self::add_sponsorship_rule(&sponsor, &raw_call_name, daily_cap)?
+				}
+				(None, Some((_, deposit))) => {
+					Self::remove_sponsorship_rule(&sponsor, &raw_call_name, deposit)?
+				}
+				(None, None) => (),
+			};
+			Self::deposit_event(Event::<T>::SetSponsorshipRule {
+				sponsor,
+				raw_call_name,
+				daily_cap,
+			});
+			Ok(())
+		}
+
+		/// Set extra fee rule by call and arguments
+		/// Parameters:
+		/// - `raw_call_name`: The pallet index, matching all the calls of the pallet, or the pallet
+		///   and call index of the calls.
+		/// - `filter`: The currency and destination the calls must have.
+		/// - `rule`: The extra fee, flat or proportional to the amount of the call, and its
+		///   receiver. If `None`, the rule will be removed.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_extra_fee_rule())]
+		pub fn set_extra_fee_rule(
+			origin: OriginFor<T>,
+			raw_call_name: RawCallName,
+			filter: ExtraFeeFilter,
+			rule: Option<(ExtraFee, T::AccountId)>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			match rule.clone() {
+				Some(rule) => {
+					ensure!(
+						(1..=2).contains(&raw_call_name.len()),
+						Error::<T>::InvalidRawCallName
+					);
+					ExtraFeeRules::<T>::insert(&raw_call_name, &filter, rule)
+				}
+				None => ExtraFeeRules::<T>::remove(&raw_call_name, &filter),
+			};
+			Self::deposit_event(Event::<T>::SetExtraFeeRule {
+				raw_call_name,
+				filter,
+				rule,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -551,6 +866,129 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_sub_account_truncating(index)
 	}
 
+	/// The account holding the budgets of the sponsors
+	fn sponsor_account() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The key of a raw call name in `SponsorsByCall`, its pallet and call index.
+	fn sponsors_by_call_key(raw_call_name: &[u8]) -> RawCallName {
+		let len = raw_call_name.len().min(2);
+		BoundedVec::truncate_from(raw_call_name[..len].to_vec())
+	}
+
+	/// Holds the deposit of a new sponsorship rule of `sponsor` and indexes it by call.
+	fn add_sponsorship_rule(
+		sponsor: &T::AccountId,
+		raw_call_name: &RawCallName,
+		daily_cap: BalanceOf<T>,
+	) -> DispatchResult {
+		SponsorshipRuleCount::<T>::try_mutate(sponsor, |count| -> DispatchResult {
+			ensure!(
+				*count < T::MaxSponsorshipRules::get(),
+				Error::<T>::TooManySponsorshipRules
+			);
+			*count = count.saturating_add(1);
+			Ok(())
+		})?;
+		SponsorsByCall::<T>::try_mutate(Self::sponsors_by_call_key(raw_call_name), |sponsors| {
+			sponsors
+				.try_push((sponsor.clone(), raw_call_name.clone()))
+				.map_err(|_| Error::<T>::TooManySponsors)
+		})?;
+
+		let deposit = T::SponsorshipRuleDeposit::get();
+		T::MultiCurrency::transfer(BNC, sponsor, &Self::sponsor_account(), deposit)?;
+		SponsorshipRules::<T>::insert(sponsor, raw_call_name, (daily_cap, deposit));
+		Ok(())
+	}
+
+	/// Removes a sponsorship rule of `sponsor` and gives back its `deposit`.
+	fn remove_sponsorship_rule(
+		sponsor: &T::AccountId,
+		raw_call_name: &RawCallName,
+		deposit: BalanceOf<T>,
+	) -> DispatchResult {
+		SponsorshipRuleCount::<T>::mutate(sponsor, |count| *count = count.saturating_sub(1));
+		SponsorsByCall::<T>::mutate(Self::sponsors_by_call_key(raw_call_name), |sponsors| {
+			sponsors.retain(|(account, name)| account != sponsor || name != raw_call_name)
+		});
+
+		T::MultiCurrency::transfer(BNC, &Self::sponsor_account(), sponsor, deposit)?;
+		SponsorshipRules::<T>::remove(sponsor, raw_call_name);
+		Ok(())
+	}
+
+	/// Returns the first sponsor with a rule matching `call`, enough budget for `fee` and whose
+	/// daily cap for `who` is not reached.
+	fn get_fee_sponsor(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::RuntimeCall,
+		fee: Balance,
+	) -> Option<T::AccountId> {
+		let call = call.encode();
+		let day = Self::current_day();
+		// the rules matching `call` are indexed by its prefixes up to the call index
+		(0..=call.len().min(2)).find_map(|len| {
+			SponsorsByCall::<T>::get(Self::sponsors_by_call_key(&call[..len]))
+				.into_iter()
+				.find_map(|(sponsor, raw_call_name)| {
+					if !call.starts_with(&raw_call_name) || SponsorBudgets::<T>::get(&sponsor) < fee
+					{
+						return None;
+					}
+					let (daily_cap, _) = SponsorshipRules::<T>::get(&sponsor, &raw_call_name)?;
+					let (used_day, used) = SponsoredFees::<T>::get(&sponsor, who);
+					let used = if used_day == day { used } else { Zero::zero() };
+					(used.saturating_add(fee) <= daily_cap).then_some(sponsor)
+				})
+		})
+	}
+
+	/// Pays `fee` out of the budget of `sponsor`, counting it in the daily cap of `who`.
+	fn charge_sponsor(sponsor: &T::AccountId, who: &T::AccountId, fee: Balance) -> DispatchResult {
+		SponsorBudgets::<T>::try_mutate(sponsor, |budget| -> DispatchResult {
+			*budget = budget
+				.checked_sub(fee)
+				.ok_or(Error::<T>::NotEnoughSponsorBudget)?;
+			Ok(())
+		})?;
+		let day = Self::current_day();
+		SponsoredFees::<T>::mutate(sponsor, who, |(used_day, used)| {
+			if *used_day != day {
+				*used_day = day;
+				*used = Zero::zero();
+			}
+			*used = used.saturating_add(fee);
+		});
+		T::MultiCurrency::withdraw(BNC, &Self::sponsor_account(), fee)?;
+
+		Self::deposit_event(Event::<T>::FeeSponsored {
+			sponsor: sponsor.clone(),
+			who: who.clone(),
+			amount: fee,
+		});
+		Ok(())
+	}
+
+	/// Gives back the `refund` of a sponsored fee to the budget of `sponsor`.
+	fn refund_sponsor(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		refund: Balance,
+	) -> DispatchResult {
+		T::MultiCurrency::deposit(BNC, &Self::sponsor_account(), refund)?;
+		SponsorBudgets::<T>::mutate(sponsor, |budget| *budget = budget.saturating_add(refund));
+		SponsoredFees::<T>::mutate(sponsor, who, |(_, used)| {
+			*used = used.saturating_sub(refund)
+		});
+		Ok(())
+	}
+
+	fn current_day() -> BlockNumberFor<T> {
+		frame_system::Pallet::<T>::block_number() / T::BlocksPerDay::get()
+	}
+
 	/// Get user fee charge assets order
 	fn get_fee_currency_list(account_id: &T::AccountId) -> Vec<CurrencyId> {
 		// Get universal fee currency order list
@@ -580,33 +1018,189 @@ impl<T: Config> Pallet<T> {
 	fn get_fee_currency_and_fee_amount(
 		who: &T::AccountId,
 		fee_amount: Balance,
-	) -> Result<(CurrencyId, Balance, Price, Price), Error<T>> {
+	) -> Result<PaymentInfo<T::AccountId>, Error<T>> {
 		let fee_currency_list = Self::get_fee_currency_list(who);
 		// charge the fee by the order of the above order list.
 		// first to check whether the user has the asset. If no, pass it. If yes, try to make
 		// transaction in the DEX in exchange for BNC
 		for currency_id in fee_currency_list {
-			// If it is mainnet currency
-			if currency_id == BNC {
-				if T::MultiCurrency::ensure_can_withdraw(currency_id, who, fee_amount).is_ok() {
-					return Ok((currency_id, fee_amount, Price::one(), Price::one()));
-				}
-			} else {
-				let (fee_amount, price_in, price_out) =
-					T::OraclePriceProvider::get_oracle_amount_by_currency_and_amount_in(
-						&BNC,
-						fee_amount,
-						&currency_id,
-					)
-					.ok_or(Error::<T>::ConversionError)?;
-				if T::MultiCurrency::ensure_can_withdraw(currency_id, who, fee_amount).is_ok() {
-					return Ok((currency_id, fee_amount, price_in, price_out));
+			for payment in Self::get_fee_payments(currency_id, fee_amount)? {
+				let (fee_currency, fee_amount) = payment.fee_currency_and_amount();
+				if T::MultiCurrency::ensure_can_withdraw(fee_currency, who, fee_amount).is_ok() {
+					return Ok(payment);
 				}
 			}
 		}
 		Err(Error::<T>::NotEnoughBalance)
 	}
 
+	/// Returns the ways to pay `fee_amount` of BNC in `currency_id`, by order of preference.
+	fn get_fee_payments(
+		currency_id: CurrencyId,
+		fee_amount: Balance,
+	) -> Result<Vec<PaymentInfo<T::AccountId>>, Error<T>> {
+		// If it is mainnet currency
+		if currency_id == BNC {
+			return Ok(vec![PaymentInfo::Native(fee_amount)]);
+		}
+
+		let (oracle_amount, price_in, price_out) =
+			T::OraclePriceProvider::get_oracle_amount_by_currency_and_amount_in(
+				&BNC,
+				fee_amount,
+				&currency_id,
+			)
+			.ok_or(Error::<T>::ConversionError)?;
+		let mut payments = Vec::new();
+		if let Some((route, amount_in)) =
+			Self::get_dex_fee_amount(currency_id, fee_amount, oracle_amount)
+		{
+			payments.push(PaymentInfo::Swapped(
+				amount_in,
+				currency_id,
+				fee_amount,
+				route,
+			));
+		}
+		// Without a liquid route, the fee is priced by the oracle
+		payments.push(PaymentInfo::NonNative(
+			oracle_amount,
+			currency_id,
+			price_in,
+			price_out,
+		));
+		Ok(payments)
+	}
+
+	/// Returns the extra fees of `call` set in `ExtraFeeByCall` and `ExtraFeeRules`.
+	fn get_extra_fees(
+		call: &<T as frame_system::Config>::RuntimeCall,
+	) -> Vec<(CurrencyId, Balance, T::AccountId)> {
+		let encoded_call = call.encode();
+		let mut extra_fees: Vec<_> = ExtraFeeByCall::<T>::iter()
+			.filter(|(raw_call_name, _)| encoded_call.starts_with(raw_call_name))
+			.map(|(_, extra_fee)| extra_fee)
+			.collect();
+
+		let args = T::InspectExtraFeeCall::call_args(call);
+		// The rules are keyed by the pallet index or the pallet and call index of the calls
+		let rules = (1..=encoded_call.len().min(2)).flat_map(|len| {
+			ExtraFeeRules::<T>::iter_prefix(RawCallName::truncate_from(
+				encoded_call[..len].to_vec(),
+			))
+		});
+		extra_fees.extend(rules.filter_map(|(filter, (extra_fee, receiver))| {
+			if !filter.matches(&args) {
+				return None;
+			}
+			match extra_fee {
+				ExtraFee::Flat(currency_id, amount) => Some((currency_id, amount, receiver)),
+				// Calls without an amount are not charged proportional fees
+				ExtraFee::Proportional(ratio) => {
+					let amount = ratio.mul_ceil(args.amount?);
+					(!amount.is_zero()).then_some((args.currency_id?, amount, receiver))
+				}
+			}
+		}));
+		extra_fees
+	}
+
+	/// Returns the cheapest route and the amount of `currency_id` swapped into `bnc_amount`, if
+	/// the DEX conversion is enabled and the price is within the premium over `oracle_amount`.
+	fn get_dex_fee_amount(
+		currency_id: CurrencyId,
+		bnc_amount: Balance,
+		oracle_amount: Balance,
+	) -> Option<(FeeSwapRoute, Balance)> {
+		let max_premium = DexFeeConversionPremium::<T>::get()?;
+		let max_amount_in = oracle_amount.saturating_add(max_premium.mul_floor(oracle_amount));
+		[FeeSwapRoute::Zenlink, FeeSwapRoute::StablePool]
+			.into_iter()
+			.filter_map(|route| {
+				Self::get_fee_swap_input(route, currency_id, bnc_amount)
+					.ok()
+					.map(|amount_in| (route, amount_in))
+			})
+			.filter(|(_, amount_in)| !amount_in.is_zero() && *amount_in <= max_amount_in)
+			.min_by_key(|(_, amount_in)| *amount_in)
+	}
+
+	fn get_fee_swap_input(
+		route: FeeSwapRoute,
+		currency_id: CurrencyId,
+		bnc_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		match route {
+			FeeSwapRoute::Zenlink => {
+				let path = vec![
+					Self::get_currency_asset_id(currency_id)?,
+					Self::get_currency_asset_id(BNC)?,
+				];
+				T::DexOperator::get_amount_in_by_path(bnc_amount, &path)?
+					.first()
+					.copied()
+					.ok_or(Error::<T>::ConversionError.into())
+			}
+			FeeSwapRoute::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					T::StablePoolHandler::get_pool_id(&currency_id, &BNC)
+						.ok_or(Error::<T>::ConversionError)?;
+				T::StablePoolHandler::get_swap_input(
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					bnc_amount,
+				)
+			}
+		}
+	}
+
+	/// Swaps the fee collected by the treasury into at least `min_bnc_amount` BNC.
+	#[transactional]
+	fn swap_fee_to_native(
+		route: FeeSwapRoute,
+		currency_id: CurrencyId,
+		amount: Balance,
+		min_bnc_amount: Balance,
+	) -> DispatchResult {
+		let treasury = T::TreasuryAccount::get();
+		match route {
+			FeeSwapRoute::Zenlink => {
+				let path = vec![
+					Self::get_currency_asset_id(currency_id)?,
+					Self::get_currency_asset_id(BNC)?,
+				];
+				T::DexOperator::inner_swap_exact_assets_for_assets(
+					&treasury,
+					amount,
+					min_bnc_amount,
+					&path,
+					&treasury,
+				)
+			}
+			FeeSwapRoute::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					T::StablePoolHandler::get_pool_id(&currency_id, &BNC)
+						.ok_or(Error::<T>::ConversionError)?;
+				T::StablePoolHandler::swap(
+					&treasury,
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					amount,
+					min_bnc_amount,
+				)
+			}
+		}
+	}
+
+	/// Converts `bnc_amount` into the fee currency at the rate of a swapped payment.
+	fn swapped_fee_amount(paid_fee: Balance, paid_bnc: Balance, bnc_amount: Balance) -> Balance {
+		multiply_by_rational_with_rounding(paid_fee, bnc_amount, paid_bnc, Rounding::Up)
+			.unwrap_or(paid_fee)
+			.min(paid_fee)
+	}
+
 	fn charge_extra_fee(
 		who: &T::AccountId,
 		extra_fee_currency: CurrencyId,
@@ -671,9 +1265,83 @@ impl<T: Config> Pallet<T> {
 		fee: Balance,
 		_utx: &<T as frame_system::Config>::RuntimeCall,
 	) -> Result<(CurrencyId, Balance), Error<T>> {
-		let (fee_currency, fee_amount, _, _) = Self::get_fee_currency_and_fee_amount(who, fee)
+		let payment = Self::get_fee_currency_and_fee_amount(who, fee)
 			.map_err(|_| Error::<T>::NotEnoughBalance)?;
-		Ok((fee_currency, fee_amount))
+		Ok(payment.fee_currency_and_amount())
+	}
+
+	/// This function is for runtime-api to call
+	///
+	/// Returns how the fee and the extra fees of `call` can be paid in each currency of the fee
+	/// currency list of `who`, in the order they are tried. Currencies the fee cannot be priced in
+	/// are left out, unless it is paid by a sponsor.
+	pub fn get_fee_options(
+		who: &T::AccountId,
+		fee: Balance,
+		call: &<T as frame_system::Config>::RuntimeCall,
+	) -> Vec<FeeOption<T::AccountId, Balance>> {
+		let sponsor = Self::get_fee_sponsor(who, call, fee);
+		let extra_fees = Self::get_extra_fees(call);
+		Self::get_fee_currency_list(who)
+			.into_iter()
+			.filter_map(|currency_id| {
+				let mut all_priced = true;
+				let extra_fees: Vec<(Balance, T::AccountId)> = extra_fees
+					.iter()
+					.filter_map(
+						|(extra_fee_currency, extra_fee_amount, extra_fee_receiver)| {
+							let amount = if *extra_fee_currency == currency_id {
+								Some(*extra_fee_amount)
+							} else {
+								Self::get_swap_amount_in(
+									currency_id,
+									*extra_fee_currency,
+									*extra_fee_amount,
+								)
+								.ok()
+							};
+							all_priced &= amount.is_some();
+							amount.map(|amount| (amount, extra_fee_receiver.clone()))
+						},
+					)
+					.collect();
+				let extra_fee_amount = extra_fees
+					.iter()
+					.fold(Zero::zero(), |total: Balance, (amount, _)| {
+						total.saturating_add(*amount)
+					});
+				let can_pay = |fee_amount: Balance| {
+					T::MultiCurrency::ensure_can_withdraw(
+						currency_id,
+						who,
+						fee_amount.saturating_add(extra_fee_amount),
+					)
+					.is_ok()
+				};
+
+				let fee_amounts: Vec<Balance> = match sponsor {
+					Some(_) => vec![Zero::zero()],
+					None => Self::get_fee_payments(currency_id, fee)
+						.ok()?
+						.iter()
+						.map(|payment| payment.fee_currency_and_amount().1)
+						.collect(),
+				};
+				let (fee_amount, affordable) =
+					match fee_amounts.iter().find(|fee_amount| can_pay(**fee_amount)) {
+						Some(fee_amount) => (*fee_amount, all_priced),
+						None => (*fee_amounts.first()?, false),
+					};
+
+				Some(FeeOption {
+					currency_id,
+					fee_amount,
+					extra_fees,
+					affordable,
+					sponsor: sponsor.clone(),
+				})
+			})
+			.collect()
 	}
 
 	fn get_currency_asset_id(currency_id: CurrencyId) -> Result<AssetId, Error<T>> {
@@ -688,6 +1356,18 @@ impl<T: Config> Pallet<T> {
 		from_currency: CurrencyId,
 		to_currency: CurrencyId,
 		amount_out: Balance,
+	) -> Result<Balance, Error<T>> {
+		let amount_in = Self::get_swap_amount_in(from_currency, to_currency, amount_out)?;
+		T::MultiCurrency::ensure_can_withdraw(from_currency, who, amount_in)
+			.map_err(|_| Error::<T>::NotEnoughBalance)?;
+		Ok(amount_in)
+	}
+
+	/// Returns the amount of `from_currency` to swap into `amount_out` on Zenlink.
+	fn get_swap_amount_in(
+		from_currency: CurrencyId,
+		to_currency: CurrencyId,
+		amount_out: Balance,
 	) -> Result<Balance, Error<T>> {
 		// If it is other assets, go to exchange fee amount.
 		let from_asset_id =
@@ -697,15 +1377,11 @@ impl<T: Config> Pallet<T> {
 			Self::get_currency_asset_id(to_currency).map_err(|_| Error::<T>::ConversionError)?;
 
 		let path = vec![from_asset_id, to_asset_id];
-		match T::DexOperator::get_amount_in_by_path(amount_out, &path) {
-			Ok(amounts) => {
-				let amount_in = amounts[0];
-				T::MultiCurrency::ensure_can_withdraw(from_currency, who, amount_in)
-					.map_err(|_| Error::<T>::NotEnoughBalance)?;
-				Ok(amount_in)
-			}
-			Err(_) => Err(Error::<T>::NotEnoughBalance)?,
-		}
+		let amount_in = T::DexOperator::get_amount_in_by_path(amount_out, &path)
+			.ok()
+			.and_then(|amounts| amounts.first().copied())
+			.ok_or(Error::<T>::NotEnoughBalance)?;
+		Ok(amount_in)
 	}
 }
 
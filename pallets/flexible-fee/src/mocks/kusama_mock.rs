@@ -23,7 +23,7 @@ use crate::{self as flexible_fee, mocks::mock_price::MockOraclePriceProvider};
 use bifrost_asset_registry::AssetIdMaps;
 use bifrost_currencies::BasicCurrencyAdapter;
 use bifrost_primitives::{
-	Balance, CurrencyId, EvmPermit, FlexibleFeePalletId, TokenSymbol, ZenlinkPalletId,
+	Balance, CurrencyId, EvmPermit, FlexibleFeePalletId, TokenSymbol, ZenlinkPalletId, BNC,
 };
 use cumulus_primitives_core::ParaId as Pid;
 use frame_support::{
@@ -176,10 +176,15 @@ parameter_types! {
 	pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
 	pub const MaxFeeCurrencyOrderListLen: u32 = 50;
 	pub AllowVBNCAsFee: bool = false;
+	pub const BlocksPerDay: u64 = 7200;
+	pub const SponsorshipRuleDeposit: Balance = 1_000_000_000_000;
+	pub const MaxSponsorshipRules: u32 = 2;
+	pub const MaxSponsorsPerCall: u32 = 2;
 }
 
 impl crate::Config for Test {
 	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = ();
 	type RuntimeEvent = RuntimeEvent;
 	type MultiCurrency = Currencies;
 	type TreasuryAccount = TreasuryAccount;
@@ -198,6 +203,27 @@ impl crate::Config for Test {
 	type EvmPermit = PermitDispatchHandler;
 	type AssetIdMaps = AssetIdMaps<Test>;
 	type AllowVBNCAsFee = AllowVBNCAsFee;
+	type BlocksPerDay = BlocksPerDay;
+	type SponsorshipRuleDeposit = SponsorshipRuleDeposit;
+	type MaxSponsorshipRules = MaxSponsorshipRules;
+	type MaxSponsorsPerCall = MaxSponsorsPerCall;
+	type InspectExtraFeeCall = ExtraFeeCallInspector;
+}
+
+pub struct ExtraFeeCallInspector;
+impl InspectExtraFeeCall<RuntimeCall> for ExtraFeeCallInspector {
+	fn call_args(call: &RuntimeCall) -> ExtraFeeCallArgs {
+		match call {
+			RuntimeCall::Balances(BalancesCall::transfer_allow_death { value, .. }) => {
+				ExtraFeeCallArgs {
+					currency_id: Some(BNC),
+					destination: None,
+					amount: Some(*value),
+				}
+			}
+			_ => Default::default(),
+		}
+	}
 }
 
 pub struct XcmDestWeightAndFee;
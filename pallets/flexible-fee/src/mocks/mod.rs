@@ -19,4 +19,4 @@
 pub mod kusama_mock;
 pub mod polkadot_mock;
 
-mod mock_price;
+pub mod mock_price;
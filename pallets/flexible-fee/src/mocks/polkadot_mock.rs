@@ -169,10 +169,15 @@ parameter_types! {
 	pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
 	pub const MaxFeeCurrencyOrderListLen: u32 = 50;
 	pub AllowVBNCAsFee: bool = true;
+	pub const BlocksPerDay: u64 = 7200;
+	pub const SponsorshipRuleDeposit: Balance = 1_000_000_000_000;
+	pub const MaxSponsorshipRules: u32 = 2;
+	pub const MaxSponsorsPerCall: u32 = 2;
 }
 
 impl crate::Config for Test {
 	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = ();
 	type RuntimeEvent = RuntimeEvent;
 	type MultiCurrency = Currencies;
 	type TreasuryAccount = TreasuryAccount;
@@ -191,6 +196,11 @@ impl crate::Config for Test {
 	type EvmPermit = PermitDispatchHandler;
 	type AssetIdMaps = AssetIdMaps<Test>;
 	type AllowVBNCAsFee = AllowVBNCAsFee;
+	type BlocksPerDay = BlocksPerDay;
+	type SponsorshipRuleDeposit = SponsorshipRuleDeposit;
+	type MaxSponsorshipRules = MaxSponsorshipRules;
+	type MaxSponsorsPerCall = MaxSponsorsPerCall;
+	type InspectExtraFeeCall = ();
 }
 
 pub struct XcmDestWeightAndFee;
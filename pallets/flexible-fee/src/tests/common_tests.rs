@@ -20,18 +20,22 @@
 
 #![cfg(test)]
 use crate::{
-	impls::on_charge_transaction::PaymentInfo, mocks::kusama_mock::*, BlockNumberFor, BoundedVec,
-	Config, DispatchError::BadOrigin, Error, UserDefaultFeeCurrency,
+	impls::on_charge_transaction::PaymentInfo,
+	mocks::{kusama_mock::*, mock_price::MockOraclePriceProvider},
+	BlockNumberFor, BoundedVec, Config, DexFeeConversionPremium,
+	DispatchError::BadOrigin,
+	Error, ExtraFee, ExtraFeeFilter, ExtraFeeRules, FeeSwapRoute, RawCallName, SponsorBudgets,
+	SponsorsByCall, SponsorshipRuleCount, SponsorshipRules, UserDefaultFeeCurrency, WeightInfo,
 };
 use bifrost_asset_registry::AssetMetadata;
 use bifrost_asset_registry::CurrencyMetadatas;
 use bifrost_primitives::{
-	AccountFeeCurrency, BalanceCmp, CurrencyId, TryConvertFrom, BNC, DOT, KSM, MANTA, VBNC, VDOT,
-	WETH,
+	AccountFeeCurrency, BalanceCmp, CurrencyId, FeeOption, TryConvertFrom, BNC, DOT, KSM, MANTA,
+	VBNC, VDOT, WETH,
 };
 use frame_support::{
 	assert_noop, assert_ok, assert_storage_noop,
-	dispatch::{DispatchInfo, PostDispatchInfo},
+	dispatch::{DispatchClass, DispatchInfo, PostDispatchInfo},
 	pallet_prelude::ValidateUnsigned,
 	traits::fungibles::Mutate,
 	weights::Weight,
@@ -41,8 +45,9 @@ use pallet_traits::evm::InspectEvmAccounts;
 use pallet_transaction_payment::OnChargeTransaction;
 use sp_arithmetic::FixedU128;
 use sp_core::{H256, U256};
-use sp_runtime::{transaction_validity::TransactionSource, AccountId32};
+use sp_runtime::{transaction_validity::TransactionSource, AccountId32, Percent, Permill};
 use std::cmp::Ordering::{Greater, Less};
+use xcm::v4::{Junction::Parachain, Location};
 use zenlink_protocol::AssetId;
 
 // some common variables
@@ -495,6 +500,202 @@ fn withdraw_extra_fee() {
 	})
 }
 
+#[test]
+fn set_extra_fee_rule_should_work() {
+	new_test_ext().execute_with(|| {
+		let raw_call_name: RawCallName = BoundedVec::try_from(vec![10]).unwrap();
+		let filter = ExtraFeeFilter {
+			currency_id: Some(BNC),
+			destination: None,
+		};
+		let rule = (ExtraFee::Proportional(Permill::from_percent(10)), DICK);
+
+		assert_noop!(
+			FlexibleFee::set_extra_fee_rule(
+				RuntimeOrigin::signed(ALICE),
+				raw_call_name.clone(),
+				filter.clone(),
+				Some(rule.clone())
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			FlexibleFee::set_extra_fee_rule(
+				RuntimeOrigin::root(),
+				BoundedVec::try_from(vec![10, 0, 0]).unwrap(),
+				filter.clone(),
+				Some(rule.clone())
+			),
+			Error::<Test>::InvalidRawCallName
+		);
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			raw_call_name.clone(),
+			filter.clone(),
+			Some(rule.clone())
+		));
+		assert_eq!(
+			ExtraFeeRules::<Test>::get(&raw_call_name, &filter),
+			Some(rule)
+		);
+
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			raw_call_name.clone(),
+			filter.clone(),
+			None
+		));
+		assert_eq!(ExtraFeeRules::<Test>::get(&raw_call_name, &filter), None);
+	});
+}
+
+#[test]
+fn withdraw_fee_with_extra_fee_rules() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let fee = 100 * 10u128.pow(12);
+		// 10% of the amount of any balances call in BNC
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			BoundedVec::try_from(vec![10]).unwrap(),
+			ExtraFeeFilter {
+				currency_id: Some(BNC),
+				destination: None,
+			},
+			Some((ExtraFee::Proportional(Permill::from_percent(10)), DICK))
+		));
+		// Rules on other currencies or destinations do not match
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			BoundedVec::try_from(vec![10, 0]).unwrap(),
+			ExtraFeeFilter {
+				currency_id: Some(DOT),
+				destination: None,
+			},
+			Some((ExtraFee::Flat(BNC, 10u128.pow(12)), DICK))
+		));
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			BoundedVec::try_from(vec![10, 0]).unwrap(),
+			ExtraFeeFilter {
+				currency_id: None,
+				destination: Some(Location::new(1, [Parachain(2004)])),
+			},
+			Some((ExtraFee::Flat(BNC, 10u128.pow(12)), DICK))
+		));
+
+		let dick_balance = Currencies::free_balance(BNC, &DICK);
+		assert_eq!(
+			FlexibleFee::withdraw_fee(&ALICE, &BALANCE_TRANSFER_CALL, &info(), fee, 0).unwrap(),
+			Some(PaymentInfo::Native(fee))
+		);
+		// ceil(10% of 69)
+		assert_eq!(Currencies::free_balance(BNC, &DICK), dick_balance + 7);
+		assert_eq!(
+			Currencies::free_balance(BNC, &ALICE),
+			1000 * 10u128.pow(12) - fee - 7
+		);
+	});
+}
+
+#[test]
+fn get_fee_options_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let fee = 100 * 10u128.pow(12);
+		assert_ok!(FlexibleFee::set_default_fee_currency_list(
+			RuntimeOrigin::root(),
+			BoundedVec::try_from(vec![BNC, DOT, MANTA]).unwrap()
+		));
+
+		assert_eq!(
+			FlexibleFee::get_fee_options(&ALICE, fee, &BALANCE_TRANSFER_CALL),
+			vec![
+				FeeOption {
+					currency_id: BNC,
+					fee_amount: fee,
+					extra_fees: vec![],
+					affordable: true,
+					sponsor: None
+				},
+				FeeOption {
+					currency_id: DOT,
+					fee_amount: 4 * 10u128.pow(10),
+					extra_fees: vec![],
+					affordable: true,
+					sponsor: None
+				},
+				FeeOption {
+					currency_id: MANTA,
+					fee_amount: 25 * 10u128.pow(18),
+					extra_fees: vec![],
+					affordable: false,
+					sponsor: None
+				},
+			]
+		);
+
+		// The extra fee can not be priced in MANTA
+		assert_ok!(FlexibleFee::set_extra_fee(
+			RuntimeOrigin::root(),
+			BoundedVec::try_from(vec![10, 0]).unwrap(),
+			Some((DOT, 1_000_000_000, DICK))
+		));
+		Currencies::set_balance(DOT, &ALICE, 4 * 10u128.pow(10));
+		assert_eq!(
+			FlexibleFee::get_fee_options(&ALICE, fee, &BALANCE_TRANSFER_CALL),
+			vec![
+				FeeOption {
+					currency_id: BNC,
+					fee_amount: fee,
+					extra_fees: vec![(100_401_304_013, DICK)],
+					affordable: true,
+					sponsor: None
+				},
+				FeeOption {
+					currency_id: DOT,
+					fee_amount: 4 * 10u128.pow(10),
+					extra_fees: vec![(1_000_000_000, DICK)],
+					affordable: false,
+					sponsor: None
+				},
+				FeeOption {
+					currency_id: MANTA,
+					fee_amount: 25 * 10u128.pow(18),
+					extra_fees: vec![],
+					affordable: false,
+					sponsor: None
+				},
+			]
+		);
+		assert_eq!(
+			FlexibleFee::cal_fee_token_and_amount(&ALICE, fee, &BALANCE_TRANSFER_CALL),
+			Ok((BNC, fee))
+		);
+
+		// Only the extra fee is paid when the fee is sponsored
+		assert_ok!(FlexibleFee::fund_sponsor_budget(
+			RuntimeOrigin::signed(DICK),
+			200 * 10u128.pow(12)
+		));
+		assert_ok!(FlexibleFee::set_sponsorship_rule(
+			RuntimeOrigin::signed(DICK),
+			BoundedVec::try_from(vec![10, 0]).unwrap(),
+			Some(fee)
+		));
+		assert_eq!(
+			FlexibleFee::get_fee_options(&ALICE, fee, &BALANCE_TRANSFER_CALL)[1],
+			FeeOption {
+				currency_id: DOT,
+				fee_amount: 0,
+				extra_fees: vec![(1_000_000_000, DICK)],
+				affordable: true,
+				sponsor: Some(DICK)
+			}
+		);
+	})
+}
+
 #[test]
 fn correct_and_deposit_fee_should_work() {
 	new_test_ext().execute_with(|| {
@@ -581,6 +782,342 @@ fn correct_and_deposit_fee_with_tip() {
 	});
 }
 
+#[test]
+fn set_dex_fee_conversion_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FlexibleFee::set_dex_fee_conversion(
+				RuntimeOrigin::signed(ALICE),
+				Some(Percent::from_percent(5))
+			),
+			BadOrigin
+		);
+		assert_ok!(FlexibleFee::set_dex_fee_conversion(
+			RuntimeOrigin::root(),
+			Some(Percent::from_percent(5))
+		));
+		assert_eq!(
+			DexFeeConversionPremium::<Test>::get(),
+			Some(Percent::from_percent(5))
+		);
+		assert_ok!(FlexibleFee::set_dex_fee_conversion(
+			RuntimeOrigin::root(),
+			None
+		));
+		assert_eq!(DexFeeConversionPremium::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn withdraw_fee_with_dex_conversion() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		// The oracle prices DOT as BNC, as the DEX pool does
+		MockOraclePriceProvider::set_price(DOT, FixedU128::from_inner(200_000_000_000_000_000));
+		assert_ok!(FlexibleFee::set_user_default_fee_currency(
+			RuntimeOrigin::signed(ALICE),
+			Some(DOT)
+		));
+		assert_ok!(FlexibleFee::set_dex_fee_conversion(
+			RuntimeOrigin::root(),
+			Some(Percent::from_percent(5))
+		));
+
+		let fee = 10u128.pow(12);
+		let amount_in = FlexibleFee::get_fee_swap_input(FeeSwapRoute::Zenlink, DOT, fee).unwrap();
+		let already_withdrawn =
+			FlexibleFee::withdraw_fee(&ALICE, &BALANCE_TRANSFER_CALL, &info(), fee, 0).unwrap();
+		assert_eq!(
+			already_withdrawn,
+			Some(PaymentInfo::Swapped(
+				amount_in,
+				DOT,
+				fee,
+				FeeSwapRoute::Zenlink
+			))
+		);
+		assert_eq!(
+			Currencies::free_balance(DOT, &ALICE),
+			1000 * 10u128.pow(10) - amount_in
+		);
+
+		// The fee collected by the treasury is swapped into BNC
+		assert_ok!(FlexibleFee::correct_and_deposit_fee(
+			&ALICE,
+			&info(),
+			&post_info(),
+			fee,
+			0,
+			already_withdrawn
+		));
+		assert_eq!(Currencies::free_balance(DOT, &TREASURY_ACCOUNT), 0);
+		assert!(Currencies::free_balance(BNC, &TREASURY_ACCOUNT) >= fee);
+	});
+}
+
+#[test]
+fn failed_fee_swap_keeps_the_fee_in_its_currency() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		System::set_block_number(1);
+
+		// The mock has no stable pool to swap DOT into BNC
+		let (paid_fee, paid_bnc) = (4 * 10u128.pow(10), 10u128.pow(12));
+		let payment = PaymentInfo::Swapped(paid_fee, DOT, paid_bnc, FeeSwapRoute::StablePool);
+		assert_ok!(FlexibleFee::refund_and_deposit_fee(
+			&ALICE,
+			paid_bnc,
+			paid_bnc / 10,
+			payment
+		));
+		assert_eq!(Currencies::free_balance(DOT, &TREASURY_ACCOUNT), paid_fee);
+		System::assert_last_event(RuntimeEvent::FlexibleFee(crate::Event::FeeSwapFailed {
+			currency_id: DOT,
+			amount: paid_fee,
+			error: Error::<Test>::ConversionError.into(),
+		}));
+		assert_eq!(
+			*System::block_weight().get(DispatchClass::Mandatory),
+			<() as WeightInfo>::swap_fee_to_native()
+		);
+	});
+}
+
+#[test]
+fn withdraw_fee_in_currency_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let fee = 100 * 10u128.pow(12);
+
+		let payment = FlexibleFee::withdraw_fee_in_currency(&ALICE, DOT, fee).unwrap();
+		assert_eq!(
+			payment,
+			PaymentInfo::NonNative(
+				4 * 10u128.pow(10),
+				DOT,
+				FixedU128::from_inner(200_000_000_000_000_000),
+				FixedU128::from(5)
+			)
+		);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 996 * 10u128.pow(10));
+
+		// Half of the fee is refunded in DOT
+		assert_ok!(FlexibleFee::refund_and_deposit_fee(
+			&ALICE,
+			fee / 2,
+			0,
+			payment
+		));
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 998 * 10u128.pow(10));
+		assert_eq!(
+			Currencies::free_balance(DOT, &TREASURY_ACCOUNT),
+			2 * 10u128.pow(10)
+		);
+
+		Currencies::set_balance(DOT, &ALICE, 0u128);
+		assert_noop!(
+			FlexibleFee::withdraw_fee_in_currency(&ALICE, DOT, fee),
+			Error::<Test>::NotEnoughBalance
+		);
+	});
+}
+
+#[test]
+fn refund_and_deposit_fee_without_price_goes_to_treasury() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let paid = 10 * 10u128.pow(12);
+
+		// VBNC has no price to convert the corrected fee into, nothing is refunded
+		let payment = PaymentInfo::NonNative(paid, VBNC, FixedU128::from(1), FixedU128::from(1));
+		assert_ok!(FlexibleFee::refund_and_deposit_fee(
+			&ALICE,
+			10u128.pow(12),
+			0,
+			payment
+		));
+		assert_eq!(Currencies::free_balance(VBNC, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(VBNC, &TREASURY_ACCOUNT), paid);
+	});
+}
+
+#[test]
+fn withdraw_fee_falls_back_to_oracle_above_premium() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		MockOraclePriceProvider::set_price(DOT, FixedU128::from_inner(200_000_000_000_000_000));
+		assert_ok!(FlexibleFee::set_user_default_fee_currency(
+			RuntimeOrigin::signed(ALICE),
+			Some(DOT)
+		));
+		// The slippage of the DEX pool is above 1%
+		assert_ok!(FlexibleFee::set_dex_fee_conversion(
+			RuntimeOrigin::root(),
+			Some(Percent::from_percent(1))
+		));
+
+		let fee = 10u128.pow(12);
+		assert_eq!(
+			FlexibleFee::withdraw_fee(&ALICE, &BALANCE_TRANSFER_CALL, &info(), fee, 0).unwrap(),
+			Some(PaymentInfo::NonNative(
+				10u128.pow(10),
+				DOT,
+				FixedU128::from_inner(200_000_000_000_000_000),
+				FixedU128::from_inner(200_000_000_000_000_000)
+			))
+		);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 999 * 10u128.pow(10));
+	});
+}
+
+#[test]
+fn sponsor_budget_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		assert_ok!(FlexibleFee::fund_sponsor_budget(
+			RuntimeOrigin::signed(DICK),
+			10 * 10u128.pow(12)
+		));
+		assert_eq!(SponsorBudgets::<Test>::get(DICK), 10 * 10u128.pow(12));
+		assert_eq!(Currencies::free_balance(BNC, &DICK), 890 * 10u128.pow(12));
+
+		assert_noop!(
+			FlexibleFee::withdraw_sponsor_budget(RuntimeOrigin::signed(DICK), 11 * 10u128.pow(12)),
+			Error::<Test>::NotEnoughSponsorBudget
+		);
+		assert_ok!(FlexibleFee::withdraw_sponsor_budget(
+			RuntimeOrigin::signed(DICK),
+			4 * 10u128.pow(12)
+		));
+		assert_eq!(SponsorBudgets::<Test>::get(DICK), 6 * 10u128.pow(12));
+		assert_eq!(Currencies::free_balance(BNC, &DICK), 894 * 10u128.pow(12));
+
+		let raw_call_name = BoundedVec::try_from(vec![10, 0]).unwrap();
+		assert_ok!(FlexibleFee::set_sponsorship_rule(
+			RuntimeOrigin::signed(DICK),
+			raw_call_name.clone(),
+			Some(10u128.pow(12))
+		));
+		assert_eq!(
+			SponsorshipRules::<Test>::get(DICK, &raw_call_name),
+			Some((10u128.pow(12), SponsorshipRuleDeposit::get()))
+		);
+		assert_eq!(
+			Currencies::free_balance(BNC, &DICK),
+			894 * 10u128.pow(12) - SponsorshipRuleDeposit::get()
+		);
+		assert_ok!(FlexibleFee::set_sponsorship_rule(
+			RuntimeOrigin::signed(DICK),
+			raw_call_name.clone(),
+			None
+		));
+		assert_eq!(SponsorshipRules::<Test>::get(DICK, &raw_call_name), None);
+		assert_eq!(Currencies::free_balance(BNC, &DICK), 894 * 10u128.pow(12));
+	});
+}
+
+#[test]
+fn sponsorship_rules_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let rule = |who, raw_call_name: Vec<u8>| {
+			FlexibleFee::set_sponsorship_rule(
+				RuntimeOrigin::signed(who),
+				BoundedVec::try_from(raw_call_name).unwrap(),
+				Some(10u128.pow(12)),
+			)
+		};
+
+		// The rules are indexed by pallet and call index
+		assert_ok!(rule(DICK, vec![10]));
+		assert_ok!(rule(DICK, vec![10, 0, 1]));
+		assert_eq!(SponsorshipRuleCount::<Test>::get(DICK), 2);
+		assert_eq!(
+			SponsorsByCall::<Test>::get(BoundedVec::try_from(vec![10]).unwrap()).len(),
+			1
+		);
+		assert_eq!(
+			SponsorsByCall::<Test>::get(BoundedVec::try_from(vec![10, 0]).unwrap()).len(),
+			1
+		);
+
+		// Updating a rule holds no other deposit
+		let free_balance = Currencies::free_balance(BNC, &DICK);
+		assert_ok!(rule(DICK, vec![10]));
+		assert_eq!(Currencies::free_balance(BNC, &DICK), free_balance);
+
+		assert_noop!(
+			rule(DICK, vec![10, 1]),
+			Error::<Test>::TooManySponsorshipRules
+		);
+
+		assert_ok!(rule(ALICE, vec![10, 0]));
+		assert_ok!(Currencies::deposit(BNC, &BOB, 10 * 10u128.pow(12)));
+		assert_noop!(rule(BOB, vec![10, 0, 2]), Error::<Test>::TooManySponsors);
+
+		// Removing a rule frees its slots
+		assert_ok!(FlexibleFee::set_sponsorship_rule(
+			RuntimeOrigin::signed(DICK),
+			BoundedVec::try_from(vec![10, 0, 1]).unwrap(),
+			None
+		));
+		assert_eq!(SponsorshipRuleCount::<Test>::get(DICK), 1);
+		assert_ok!(rule(BOB, vec![10, 0, 2]));
+	});
+}
+
+#[test]
+fn withdraw_fee_from_sponsor() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let fee = 2 * 10u128.pow(12);
+		assert_ok!(FlexibleFee::fund_sponsor_budget(
+			RuntimeOrigin::signed(DICK),
+			10 * 10u128.pow(12)
+		));
+		assert_ok!(FlexibleFee::set_sponsorship_rule(
+			RuntimeOrigin::signed(DICK),
+			BoundedVec::try_from(vec![10, 0]).unwrap(),
+			Some(3 * 10u128.pow(12))
+		));
+
+		let already_withdrawn =
+			FlexibleFee::withdraw_fee(&ALICE, &BALANCE_TRANSFER_CALL, &info(), fee, 0).unwrap();
+		assert_eq!(already_withdrawn, Some(PaymentInfo::Sponsored(fee, DICK)));
+		assert_eq!(Currencies::free_balance(BNC, &ALICE), 1000 * 10u128.pow(12));
+		assert_eq!(SponsorBudgets::<Test>::get(DICK), 8 * 10u128.pow(12));
+
+		// The daily cap of ALICE is reached
+		assert_eq!(
+			FlexibleFee::withdraw_fee(&ALICE, &BALANCE_TRANSFER_CALL, &info(), fee, 0).unwrap(),
+			Some(PaymentInfo::Native(fee))
+		);
+		assert_eq!(Currencies::free_balance(BNC, &ALICE), 998 * 10u128.pow(12));
+
+		// The refund goes back to the budget of the sponsor
+		assert_ok!(FlexibleFee::correct_and_deposit_fee(
+			&ALICE,
+			&info(),
+			&post_info(),
+			10u128.pow(12),
+			0,
+			already_withdrawn
+		));
+		assert_eq!(SponsorBudgets::<Test>::get(DICK), 9 * 10u128.pow(12));
+		assert_eq!(
+			Currencies::free_balance(BNC, &TREASURY_ACCOUNT),
+			10u128.pow(12)
+		);
+
+		// The cap is reset the next day
+		System::set_block_number(7200);
+		assert_eq!(
+			FlexibleFee::withdraw_fee(&ALICE, &BALANCE_TRANSFER_CALL, &info(), fee, 0).unwrap(),
+			Some(PaymentInfo::Sponsored(fee, DICK))
+		);
+	});
+}
+
 #[test]
 fn get_currency_asset_id_should_work() {
 	new_test_ext().execute_with(|| {
@@ -55,6 +55,12 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn set_user_default_fee_currency() -> Weight;
 	fn set_default_fee_currency_list() -> Weight;
+	fn set_dex_fee_conversion() -> Weight;
+	fn fund_sponsor_budget() -> Weight;
+	fn withdraw_sponsor_budget() -> Weight;
+	fn set_sponsorship_rule() -> Weight;
+	fn set_extra_fee_rule() -> Weight;
+	fn swap_fee_to_native() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -79,4 +85,53 @@ impl WeightInfo for () {
 		Weight::from_parts(3_404_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `FlexibleFee::DexFeeConversionPremium` (r:0 w:1)
+	/// Proof: `FlexibleFee::DexFeeConversionPremium` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	fn set_dex_fee_conversion() -> Weight {
+		Weight::from_parts(3_500_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `FlexibleFee::SponsorBudgets` (r:1 w:1)
+	/// Proof: `FlexibleFee::SponsorBudgets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn fund_sponsor_budget() -> Weight {
+		Weight::from_parts(53_804_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `FlexibleFee::SponsorBudgets` (r:1 w:1)
+	/// Proof: `FlexibleFee::SponsorBudgets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_sponsor_budget() -> Weight {
+		Weight::from_parts(54_970_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `FlexibleFee::SponsorshipRules` (r:1 w:1)
+	/// Proof: `FlexibleFee::SponsorshipRules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FlexibleFee::SponsorshipRuleCount` (r:1 w:1)
+	/// Proof: `FlexibleFee::SponsorshipRuleCount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FlexibleFee::SponsorsByCall` (r:1 w:1)
+	/// Proof: `FlexibleFee::SponsorsByCall` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn set_sponsorship_rule() -> Weight {
+		Weight::from_parts(60_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `FlexibleFee::ExtraFeeRules` (r:0 w:1)
+	/// Proof: `FlexibleFee::ExtraFeeRules` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_extra_fee_rule() -> Weight {
+		Weight::from_parts(4_581_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Swaps the fee of a transaction collected by the treasury into BNC through the DEX.
+	fn swap_fee_to_native() -> Weight {
+		Weight::from_parts(80_000_000, 12000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
 }
@@ -19,15 +19,22 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bifrost_primitives::{CurrencyId, Liquidity, Rate, Ratio, Shortfall};
+pub use pallet_traits::{AccountPosition, MarketPosition};
 use parity_scale_codec::Codec;
 use sp_runtime::{DispatchError, FixedU128};
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(3)]
 	pub trait LendMarketApi<AccountId, Balance> where
 		AccountId: Codec,
 		Balance: Codec {
 		fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
 		fn get_market_status(asset_id: CurrencyId) -> Result<(Rate, Rate, Rate, Ratio, Balance, Balance, FixedU128), DispatchError>;
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
+		#[api_version(2)]
+		fn get_bad_debt(account: AccountId) -> Result<Vec<(CurrencyId, Balance)>, DispatchError>;
+		#[api_version(3)]
+		fn get_account_position(account: AccountId) -> Result<AccountPosition<CurrencyId, Balance>, DispatchError>;
 	}
 }
@@ -26,8 +26,9 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::error::{ErrorCode, ErrorObject},
 };
+use lend_market_rpc_runtime_api::{AccountPosition, MarketPosition};
 use parity_scale_codec::Codec;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{traits::Block as BlockT, FixedU128};
@@ -55,6 +56,18 @@ where
 		account: AccountId,
 		at: Option<BlockHash>,
 	) -> RpcResult<(Liquidity, Shortfall, Liquidity, Shortfall)>;
+	#[method(name = "lend_market_getBadDebt")]
+	fn get_bad_debt(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>>;
+	#[method(name = "lend_market_getAccountPosition")]
+	fn get_account_position(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountPosition<CurrencyId, NumberOrHex>>;
 }
 
 /// A struct that implements the [`LendMarketApi`].
@@ -77,6 +90,9 @@ pub enum Error {
 	RuntimeError,
 	AccountLiquidityError,
 	MarketStatusError,
+	BadDebtError,
+	AccountPositionError,
+	UnsupportedApiVersion,
 }
 
 impl From<Error> for i32 {
@@ -85,6 +101,9 @@ impl From<Error> for i32 {
 			Error::RuntimeError => 1,
 			Error::AccountLiquidityError => 2,
 			Error::MarketStatusError => 3,
+			Error::BadDebtError => 4,
+			Error::AccountPositionError => 5,
+			Error::UnsupportedApiVersion => 6,
 		}
 	}
 }
@@ -162,6 +181,68 @@ where
 			.map_err(runtime_error_into_rpc_error)?
 			.map_err(account_liquidity_error_into_rpc_error)
 	}
+
+	fn get_bad_debt(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let api_version = api
+			.api_version::<dyn LendMarketRuntimeApi<Block, AccountId, Balance>>(at)
+			.map_err(runtime_error_into_rpc_error)?;
+		ensure_api_version(api_version, 2)?;
+		api.get_bad_debt(at, account)
+			.map_err(runtime_error_into_rpc_error)?
+			.map_err(bad_debt_error_into_rpc_error)?
+			.into_iter()
+			.map(|(asset_id, amount)| Ok((asset_id, try_into_rpc_balance(amount)?)))
+			.collect()
+	}
+
+	fn get_account_position(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<AccountPosition<CurrencyId, NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let api_version = api
+			.api_version::<dyn LendMarketRuntimeApi<Block, AccountId, Balance>>(at)
+			.map_err(runtime_error_into_rpc_error)?;
+		ensure_api_version(api_version, 3)?;
+		let position = api
+			.get_account_position(at, account)
+			.map_err(runtime_error_into_rpc_error)?
+			.map_err(account_position_error_into_rpc_error)?;
+		let markets = position
+			.markets
+			.into_iter()
+			.map(|market| {
+				Ok(MarketPosition {
+					asset_id: market.asset_id,
+					supplied: try_into_rpc_balance(market.supplied)?,
+					borrowed: try_into_rpc_balance(market.borrowed)?,
+					is_collateral: market.is_collateral,
+					earned: try_into_rpc_balance(market.earned)?,
+					max_borrowable: try_into_rpc_balance(market.max_borrowable)?,
+					max_withdrawable: try_into_rpc_balance(market.max_withdrawable)?,
+				})
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+		Ok(AccountPosition {
+			health_factor: position.health_factor,
+			reward_accrued: try_into_rpc_balance(position.reward_accrued)?,
+			markets,
+		})
+	}
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -191,6 +272,39 @@ fn market_status_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObject<
 	)
 }
 
+/// Converts a bad debt error into an RPC error.
+fn bad_debt_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObject<'static> {
+	ErrorObject::owned(
+		Error::BadDebtError.into(),
+		"Not able to get bad debt",
+		Some(format!("{:?}", err)),
+	)
+}
+
+/// Converts an account position error into an RPC error.
+fn account_position_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObject<'static> {
+	ErrorObject::owned(
+		Error::AccountPositionError.into(),
+		"Not able to get account position",
+		Some(format!("{:?}", err)),
+	)
+}
+
+/// Ensures the runtime implements the version of `LendMarketApi` a method was added in.
+fn ensure_api_version(api_version: Option<u32>, required: u32) -> RpcResult<()> {
+	match api_version {
+		Some(version) if version >= required => Ok(()),
+		_ => Err(ErrorObject::owned(
+			Error::UnsupportedApiVersion.into(),
+			"Runtime API method not supported",
+			Some(format!(
+				"requires LendMarketApi version {}, found {:?}",
+				required, api_version
+			)),
+		)),
+	}
+}
+
 fn try_into_rpc_balance<T: std::fmt::Display + Copy + TryInto<NumberOrHex>>(
 	value: T,
 ) -> RpcResult<NumberOrHex> {
@@ -9,16 +9,18 @@ use frame_support::{
 	traits::tokens::{Fortitude, Precision},
 };
 use frame_system::{self, RawOrigin as SystemOrigin};
-use rate_model::{InterestRateModel, JumpModel};
+use rate_model::{AdaptiveModel, InterestRateModel};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
 
-const RATE_MODEL_MOCK: InterestRateModel = InterestRateModel::Jump(JumpModel {
-	base_rate: Rate::from_inner(Rate::DIV / 100 * 2),
-	jump_rate: Rate::from_inner(Rate::DIV / 100 * 10),
-	full_rate: Rate::from_inner(Rate::DIV / 100 * 32),
-	jump_utilization: Ratio::from_percent(80),
+const ADAPTIVE_RATE_MODEL_MOCK: InterestRateModel = InterestRateModel::Adaptive(AdaptiveModel {
+	target_utilization: Ratio::from_percent(90),
+	initial_rate_at_target: Rate::from_inner(Rate::DIV / 100 * 4),
+	min_rate_at_target: Rate::from_inner(Rate::DIV / 1000),
+	max_rate_at_target: Rate::from_inner(Rate::DIV * 2),
+	adjustment_speed: Rate::from_inner(Rate::DIV * 50),
+	curve_steepness: Rate::from_inner(Rate::DIV * 4),
 });
 
 fn market_mock<T: Config>() -> Market<BalanceOf<T>> {
@@ -28,12 +30,8 @@ fn market_mock<T: Config>() -> Market<BalanceOf<T>> {
 		liquidation_threshold: Ratio::from_percent(55),
 		liquidate_incentive: Rate::from_inner(Rate::DIV / 100 * 110),
 		state: MarketState::Active,
-		rate_model: InterestRateModel::Jump(JumpModel {
-			base_rate: Rate::from_inner(Rate::DIV / 100 * 2),
-			jump_rate: Rate::from_inner(Rate::DIV / 100 * 10),
-			full_rate: Rate::from_inner(Rate::DIV / 100 * 32),
-			jump_utilization: Ratio::from_percent(80),
-		}),
+		// The adaptive model is the most expensive one to accrue interest with
+		rate_model: ADAPTIVE_RATE_MODEL_MOCK,
 		reserve_factor: Ratio::from_percent(15),
 		liquidate_incentive_reserved_factor: Ratio::from_percent(3),
 		supply_cap: 1_000_000_000_000_000_000_000u128,
@@ -100,7 +98,10 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 
 #[benchmarks(
 	where
-		T: Config + pallet_prices::Config + pallet_balances::Config<Balance = Balance>
+		T: Config
+			+ pallet_prices::Config
+			+ pallet_balances::Config<Balance = Balance>
+			+ pallet_timestamp::Config
 )]
 mod benchmarks {
 	use super::*;
@@ -131,17 +132,21 @@ mod benchmarks {
 
 	#[benchmark]
 	fn update_rate_model() -> Result<(), BenchmarkError> {
+		// Worst case: a borrowed adaptive market which has not accrued interest for a day
+		let market = pending_market_mock::<T>(VSKSM);
 		assert_ok!(LendMarket::<T>::add_market(
 			SystemOrigin::Root.into(),
 			KSM,
-			pending_market_mock::<T>(VSKSM)
+			market.clone()
 		));
+		TotalBorrows::<T>::insert(KSM, BalanceOf::<T>::from(INITIAL_AMOUNT));
+		AdaptiveRateAtTarget::<T>::insert(KSM, Rate::from_inner(Rate::DIV / 100 * 5));
+		LastAccruedInterestTime::<T>::insert(KSM, 1);
+		pallet_timestamp::Pallet::<T>::set_timestamp((24 * 60 * 60 * 1000u32).into());
 
 		#[extrinsic_call]
-		_(SystemOrigin::Root, KSM, RATE_MODEL_MOCK);
+		_(SystemOrigin::Root, KSM, ADAPTIVE_RATE_MODEL_MOCK);
 
-		let mut market = pending_market_mock::<T>(VSKSM);
-		market.rate_model = RATE_MODEL_MOCK;
 		assert_last_event::<T>(Event::<T>::UpdatedMarket(KSM, market).into());
 		Ok(())
 	}
@@ -700,6 +705,57 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn write_off_bad_debt() -> Result<(), BenchmarkError> {
+		let alice: T::AccountId = account("Sample", 100, SEED);
+		let bob: T::AccountId = account("Sample", 101, SEED);
+		let backstop: T::AccountId = account("Sample", 102, SEED);
+		transfer_initial_balance::<T>(alice.clone());
+		transfer_initial_balance::<T>(bob.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 200_000_000;
+		let reserves_amount: u32 = 10_000_000;
+		let backstop_amount: u32 = 10_000_000;
+
+		assert_ok!(LendMarket::<T>::add_market(
+			SystemOrigin::Root.into(),
+			VKSM,
+			pending_market_mock::<T>(VSKSM)
+		));
+		assert_ok!(LendMarket::<T>::activate_market(
+			SystemOrigin::Root.into(),
+			VKSM
+		));
+		assert_ok!(LendMarket::<T>::add_market(
+			SystemOrigin::Root.into(),
+			KSM,
+			pending_market_mock::<T>(LKSM)
+		));
+		assert_ok!(LendMarket::<T>::activate_market(
+			SystemOrigin::Root.into(),
+			KSM
+		));
+		assert_ok!(LendMarket::<T>::mint(
+			SystemOrigin::Signed(bob.clone()).into(),
+			KSM,
+			deposit_amount.into()
+		));
+		set_account_borrows::<T>(alice.clone(), KSM, borrowed_amount.into());
+		// worst case: the debt drains the reserves and the backstop, the rest is socialized
+		TotalReserves::<T>::insert(KSM, BalanceOf::<T>::from(reserves_amount));
+		<T as pallet::Config>::Assets::mint_into(KSM, &backstop, backstop_amount.into())?;
+		assert_ok!(LendMarket::<T>::set_backstop_account(
+			SystemOrigin::Root.into(),
+			Some(backstop)
+		));
+
+		#[extrinsic_call]
+		_(SystemOrigin::Signed(bob), alice.clone(), KSM);
+
+		assert!(TotalReserves::<T>::get(KSM).is_zero());
+		Ok(())
+	}
+
 	#[benchmark]
 	fn update_liquidation_free_collateral() -> Result<(), BenchmarkError> {
 		#[extrinsic_call]
@@ -709,6 +765,31 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_isolation_mode() -> Result<(), BenchmarkError> {
+		assert_ok!(LendMarket::<T>::add_market(
+			SystemOrigin::Root.into(),
+			KSM,
+			pending_market_mock::<T>(LKSM)
+		));
+		let isolation_mode = IsolationMode {
+			debt_ceiling: BalanceOf::<T>::from(INITIAL_AMOUNT),
+			borrowable_assets: vec![KSM].try_into().unwrap(),
+		};
+
+		#[extrinsic_call]
+		_(SystemOrigin::Root, KSM, Some(isolation_mode.clone()));
+
+		assert_last_event::<T>(
+			Event::<T>::IsolationModeUpdated {
+				asset_id: KSM,
+				isolation_mode: Some(isolation_mode),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext_benchmark(),
@@ -45,6 +45,16 @@ impl<T: Config> Pallet<T> {
 			borrow_index_new,
 		) = Self::get_market_status(asset_id)?;
 
+		if let InterestRateModel::Adaptive(ref adaptive) = Self::market(asset_id)?.rate_model {
+			let (_, rate_at_target) = Self::calc_adaptive_borrow_rate(
+				asset_id,
+				adaptive,
+				util,
+				now - last_accrued_interest_time,
+			)?;
+			AdaptiveRateAtTarget::<T>::insert(asset_id, rate_at_target);
+		}
+
 		Self::update_last_accrued_interest_time(asset_id, now)?;
 		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
 		TotalReserves::<T>::insert(asset_id, total_reserves_new);
@@ -75,23 +85,28 @@ impl<T: Config> Pallet<T> {
 	> {
 		let market = Self::market(asset_id)?;
 		let total_supply = TotalSupply::<T>::get(asset_id);
-		let total_cash = Self::get_total_cash(asset_id);
+		let total_cash = Self::get_accounted_cash(asset_id);
 		let mut total_borrows = TotalBorrows::<T>::get(asset_id);
 		let mut total_reserves = TotalReserves::<T>::get(asset_id);
 		let mut borrow_index = BorrowIndex::<T>::get(asset_id);
 
 		let util = Self::calc_utilization_ratio(total_cash, total_borrows, total_reserves)?;
-		let borrow_rate = market
-			.rate_model
-			.get_borrow_rate(util)
-			.ok_or(ArithmeticError::Overflow)?;
+		let now = T::UnixTime::now().as_secs();
+		let last_accrued_interest_time = LastAccruedInterestTime::<T>::get(asset_id);
+		let delta_time = now.saturating_sub(last_accrued_interest_time);
+		let borrow_rate = match market.rate_model {
+			InterestRateModel::Adaptive(ref adaptive) => {
+				Self::calc_adaptive_borrow_rate(asset_id, adaptive, util, delta_time)?.0
+			}
+			_ => market
+				.rate_model
+				.get_borrow_rate(util)
+				.ok_or(ArithmeticError::Overflow)?,
+		};
 		let supply_rate =
 			InterestRateModel::get_supply_rate(borrow_rate, util, market.reserve_factor);
 
-		let now = T::UnixTime::now().as_secs();
-		let last_accrued_interest_time = LastAccruedInterestTime::<T>::get(asset_id);
 		if now > last_accrued_interest_time {
-			let delta_time = now - last_accrued_interest_time;
 			let interest_accumulated =
 				Self::accrued_interest(borrow_rate, total_borrows, delta_time)
 					.ok_or(ArithmeticError::Overflow)?;
@@ -123,12 +138,27 @@ impl<T: Config> Pallet<T> {
 		))
 	}
 
+	/// Calculate the average borrow rate of an adaptive market over the last `delta_time`
+	/// seconds, and its rate at target at the end of the period.
+	pub(crate) fn calc_adaptive_borrow_rate(
+		asset_id: AssetIdOf<T>,
+		adaptive: &AdaptiveModel,
+		util: Ratio,
+		delta_time: Timestamp,
+	) -> Result<(Rate, Rate), DispatchError> {
+		let rate_at_target =
+			AdaptiveRateAtTarget::<T>::get(asset_id).unwrap_or(adaptive.initial_rate_at_target);
+		adaptive
+			.get_borrow_rate(util, rate_at_target, delta_time)
+			.ok_or(ArithmeticError::Overflow.into())
+	}
+
 	/// Update the exchange rate according to the totalCash, totalBorrows and totalSupply.
 	/// This function does not accrue interest before calculating the exchange rate.
 	/// exchangeRate = (totalCash + totalBorrows - totalReserves) / totalSupply
 	pub fn exchange_rate_stored(asset_id: AssetIdOf<T>) -> Result<Rate, DispatchError> {
 		let total_supply = TotalSupply::<T>::get(asset_id);
-		let total_cash = Self::get_total_cash(asset_id);
+		let total_cash = Self::get_accounted_cash(asset_id);
 		let total_borrows = TotalBorrows::<T>::get(asset_id);
 		let total_reserves = TotalReserves::<T>::get(asset_id);
 
@@ -187,6 +187,7 @@ impl<T: Config> Pallet<T> {
 				if d.voucher_balance.is_zero() {
 					// remove deposits storage if zero balance
 					*deposits = None;
+					Self::release_isolated_collateral(source, underlying_id);
 				} else {
 					*deposits = Some(d);
 				}
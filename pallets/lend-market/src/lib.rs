@@ -31,8 +31,12 @@ use core::cmp::max;
 pub use crate::rate_model::*;
 use bifrost_primitives::{
 	Balance, CurrencyId, Liquidity, OraclePriceProvider, Price, Rate, Ratio, Shortfall, Timestamp,
+	TryConvertFrom,
 };
+use bifrost_stable_pool::traits::StablePoolHandler;
+use cumulus_primitives_core::ParaId;
 use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
 	pallet_prelude::*,
 	require_transactional,
 	traits::{
@@ -46,22 +50,27 @@ use frame_system::pallet_prelude::*;
 use num_traits::cast::ToPrimitive;
 pub use pallet::*;
 use pallet_traits::{
-	ConvertToBigUint, LendMarket as LendMarketTrait, LendMarketMarketDataProvider,
-	LendMarketPositionDataProvider, MarketInfo, MarketStatus,
+	AccountPosition, ConvertToBigUint, LendMarket as LendMarketTrait, LendMarketMarketDataProvider,
+	LendMarketPositionDataProvider, MarketInfo, MarketPosition, MarketStatus,
 };
 use sp_core::bounded::BoundedVec;
 use sp_runtime::{
 	traits::{
 		AccountIdConversion, BlockNumberProvider, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub,
-		One, SaturatedConversion, Saturating, StaticLookup, Zero,
+		Dispatchable, One, SaturatedConversion, Saturating, StaticLookup, Zero,
 	},
 	ArithmeticError, FixedPointNumber, FixedU128,
 };
-use sp_std::{result::Result, vec::Vec};
+use sp_std::{result::Result, vec, vec::Vec};
+use zenlink_protocol::{AssetId as ZenlinkAssetId, ExportZenlink};
 
 use log;
 use sp_io::hashing::blake2_256;
-pub use types::{BorrowSnapshot, Deposits, EarnedSnapshot, Market, MarketState, RewardMarketState};
+pub use types::{
+	BorrowSnapshot, Deposits, EModeCategory, EModeCategoryId, EarnedSnapshot, IsolationMode,
+	LiquidationAuction, LiquidationAuctionConfig, LiquidationSwapRoute, Market, MarketAction,
+	MarketState, RewardMarketState,
+};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -113,6 +122,9 @@ pub mod pallet {
 		/// add/reduce reserves. Root can always do this.
 		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The origin which can pause and unpause the actions of a market.
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -135,6 +147,38 @@ pub mod pallet {
 
 		/// The current block number provider.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// The overarching call type, dispatched by flash loans.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<Call<Self>>;
+
+		/// Fee of flash loans, added to the reserves of the market.
+		#[pallet::constant]
+		type FlashLoanFee: Get<Ratio>;
+
+		/// The maximum number of calls dispatched by a flash loan.
+		#[pallet::constant]
+		type MaxFlashLoanCalls: Get<u32>;
+
+		/// Zenlink DEX operator, swaps the seized collateral of liquidations.
+		type DexOperator: ExportZenlink<Self::AccountId, ZenlinkAssetId>;
+
+		/// Stable pool handler, swaps the seized collateral of liquidations.
+		type StablePoolHandler: StablePoolHandler<
+			Balance = Balance,
+			AccountId = Self::AccountId,
+			CurrencyId = CurrencyId,
+		>;
+
+		/// The id of this parachain, used to convert currencies into zenlink assets.
+		type ParachainId: Get<ParaId>;
+
+		/// Collateral worth no more than this value, in dollars with 18 decimals, is dust and
+		/// does not prevent the debt of its owner from being written off.
+		#[pallet::constant]
+		type DustCollateralValue: Get<BalanceOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -201,6 +245,52 @@ pub mod pallet {
 		MarketBondDoesNotExist,
 		/// Error converting Vec to BoundedVec.
 		ConversionError,
+		/// Efficiency mode category does not exist
+		EModeCategoryDoesNotExist,
+		/// Efficiency mode category is still assigned to some assets
+		EModeCategoryInUse,
+		/// Asset does not belong to the account's efficiency mode category
+		AssetNotInEModeCategory,
+		/// Isolated collateral cannot be used together with other collaterals
+		IsolatedCollateralMixed,
+		/// Isolation mode can only be set on a market without depositors
+		IsolationMarketHasDepositors,
+		/// Asset cannot be borrowed against an isolated collateral
+		NotBorrowableInIsolationMode,
+		/// Upper bound of the isolated collateral debt is exceeded
+		DebtCeilingExceeded,
+		/// Account has no outstanding debt in the market
+		NoBadDebt,
+		/// Account still has collaterals, its debt is not bad debt
+		CollateralNotExhausted,
+		/// The market must be liquidated through liquidation auctions
+		LiquidationAuctionRequired,
+		/// The market is not in auction mode
+		LiquidationAuctionDisabled,
+		/// Invalid liquidation auction parameters
+		InvalidLiquidationAuctionConfig,
+		/// A liquidation auction is already opened for the borrow
+		LiquidationAuctionAlreadyOpened,
+		/// Liquidation auction does not exist
+		LiquidationAuctionDoesNotExist,
+		/// The borrower is still liquidatable
+		BorrowerStillLiquidatable,
+		/// Flash loan and its fee are not repaid at the end of the calls
+		FlashLoanNotRepaid,
+		/// The action is paused in this market
+		MarketActionPaused,
+		/// No pool or pair can swap the collateral into the liquidation asset
+		SwapRouteNotFound,
+		/// The currency cannot be converted into a zenlink asset
+		ZenlinkAssetConversionFailed,
+		/// The collateral left to the liquidator is less than the minimum profit
+		InsufficientLiquidationProfit,
+		/// An account cannot delegate borrowing to itself
+		InvalidDelegatee,
+		/// The delegated borrow exceeds the allowance of the delegator
+		BorrowAllowanceExceeded,
+		/// Only the delegator or the delegatee can repay a delegated borrow
+		NotDelegationParty,
 	}
 
 	#[pallet::event]
@@ -271,6 +361,126 @@ pub mod pallet {
 			asset_id: AssetIdOf<T>,
 			market_bond: Vec<AssetIdOf<T>>,
 		},
+		/// Efficiency mode category has been set or removed
+		EModeCategoryUpdated {
+			category_id: EModeCategoryId,
+			category: Option<EModeCategory>,
+		},
+		/// The efficiency mode category of an asset has been set or removed
+		AssetEModeCategoryUpdated {
+			asset_id: AssetIdOf<T>,
+			category_id: Option<EModeCategoryId>,
+		},
+		/// The isolation mode of a collateral asset has been set or removed
+		IsolationModeUpdated {
+			asset_id: AssetIdOf<T>,
+			isolation_mode: Option<IsolationMode<BalanceOf<T>, T::MaxLengthLimit>>,
+		},
+		/// An account entered or left an efficiency mode category
+		AccountEModeCategoryUpdated {
+			account: T::AccountId,
+			category_id: Option<EModeCategoryId>,
+		},
+		/// The bad debt backstop account has been set or removed
+		BackstopAccountUpdated { account: Option<T::AccountId> },
+		/// Bad debt has been written off against the market reserves
+		BadDebtCoveredByReserves {
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			total_reserves: BalanceOf<T>,
+		},
+		/// Bad debt has been covered by the backstop account
+		BadDebtCoveredByBackstop {
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			backstop: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Bad debt has been socialized among the suppliers through the exchange rate
+		BadDebtSocialized {
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			exchange_rate: Rate,
+		},
+		/// The dust collateral of a borrower with bad debt has been added to the market reserves
+		DustCollateralSeized {
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// The liquidation auction parameters of a market have been set or removed
+		LiquidationAuctionConfigUpdated {
+			asset_id: AssetIdOf<T>,
+			config: Option<LiquidationAuctionConfig>,
+		},
+		/// A liquidation auction has been opened
+		LiquidationAuctionOpened {
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			collateral_asset_id: AssetIdOf<T>,
+			start_time: Timestamp,
+		},
+		/// A bidder repaid debt in a liquidation auction
+		LiquidationAuctionBid {
+			bidder: T::AccountId,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			repay_amount: BalanceOf<T>,
+			liquidate_incentive: Rate,
+		},
+		/// A liquidation auction has been closed
+		LiquidationAuctionClosed {
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+		},
+		/// A flash loan has been repaid
+		FlashLoaned {
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			fee: BalanceOf<T>,
+		},
+		/// An action of a market has been paused or unpaused
+		MarketActionPauseUpdated {
+			asset_id: AssetIdOf<T>,
+			action: MarketAction,
+			paused: bool,
+		},
+		/// A borrow has been liquidated and the seized collateral swapped to repay it
+		/// [liquidator, borrower, liquidation_asset_id, collateral_asset_id, repay_amount,
+		/// profit]
+		LiquidatedAndSwapped {
+			liquidator: T::AccountId,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			collateral_asset_id: AssetIdOf<T>,
+			repay_amount: BalanceOf<T>,
+			profit: BalanceOf<T>,
+		},
+		/// A delegator has updated the borrow allowance of a delegatee
+		BorrowAllowanceApproved {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			limit: BalanceOf<T>,
+		},
+		/// A delegatee has borrowed against the liquidity of a delegator
+		DelegatedBorrowed {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// A delegated borrow has been repaid by the delegator or the delegatee
+		DelegatedBorrowRepaid {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			payer: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	/// The timestamp of the last calculation of accrued interest
@@ -366,6 +576,12 @@ pub mod pallet {
 	pub type UtilizationRatio<T: Config> =
 		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Ratio, ValueQuery>;
 
+	/// Rate at target utilization of markets using the adaptive interest rate model
+	/// CurrencyId -> Rate
+	#[pallet::storage]
+	pub type AdaptiveRateAtTarget<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Rate, OptionQuery>;
+
 	/// Mapping of asset id to its market
 	#[pallet::storage]
 	pub type Markets<T: Config> =
@@ -442,6 +658,123 @@ pub mod pallet {
 	pub type MarketBond<T: Config> =
 		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BoundedVec<AssetIdOf<T>, T::MaxLengthLimit>>;
 
+	/// Efficiency mode categories
+	/// CategoryId -> EModeCategory
+	#[pallet::storage]
+	pub type EModeCategories<T: Config> =
+		StorageMap<_, Twox64Concat, EModeCategoryId, EModeCategory, OptionQuery>;
+
+	/// Mapping of asset id to the efficiency mode category it belongs to
+	/// CurrencyId -> CategoryId
+	#[pallet::storage]
+	pub type AssetEModeCategory<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, EModeCategoryId, OptionQuery>;
+
+	/// Mapping of account addresses to the efficiency mode category they opted into
+	/// Owner -> CategoryId
+	#[pallet::storage]
+	pub type AccountEModeCategory<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, EModeCategoryId, OptionQuery>;
+
+	/// Mapping of isolated collateral assets to their isolation mode
+	/// CurrencyId -> IsolationMode
+	#[pallet::storage]
+	pub type IsolationModes<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		IsolationMode<BalanceOf<T>, T::MaxLengthLimit>,
+		OptionQuery,
+	>;
+
+	/// Total debt backed by each isolated collateral asset, in units of the borrowed asset
+	/// CollateralCurrencyId -> BorrowedCurrencyId -> Balance
+	#[pallet::storage]
+	pub type IsolationModeTotalDebt<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Mapping of accounts to the isolated collateral asset they use
+	/// Owner -> CurrencyId
+	#[pallet::storage]
+	pub type AccountIsolatedCollateral<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, AssetIdOf<T>, OptionQuery>;
+
+	/// The account drawn on to cover bad debt once the market reserves are exhausted
+	#[pallet::storage]
+	pub type BackstopAccount<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// Mapping of markets in auction mode to their liquidation auction parameters
+	/// CurrencyId -> LiquidationAuctionConfig
+	#[pallet::storage]
+	pub type LiquidationAuctionConfigs<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, LiquidationAuctionConfig, OptionQuery>;
+
+	/// Opened liquidation auctions
+	/// LiquidationCurrencyId -> Borrower -> LiquidationAuction
+	#[pallet::storage]
+	pub type LiquidationAuctions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		LiquidationAuction,
+		OptionQuery,
+	>;
+
+	/// Amount of outstanding flash loans, still accounted in the exchange rate of the market
+	/// CurrencyId -> Balance
+	#[pallet::storage]
+	pub type FlashLoans<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Actions paused in a market
+	/// CurrencyId -> MarketAction -> bool
+	#[pallet::storage]
+	pub type PausedMarketActions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Twox64Concat,
+		MarketAction,
+		bool,
+		ValueQuery,
+	>;
+
+	/// Borrow limits approved by delegators for delegatees
+	/// CurrencyId -> (Delegator, Delegatee) -> Balance
+	#[pallet::storage]
+	pub type BorrowAllowances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Debts drawn by delegatees, owed by delegators and part of their `AccountBorrows`
+	/// CurrencyId -> Delegator -> Delegatee -> BorrowSnapshot
+	#[pallet::storage]
+	pub type DelegatedBorrows<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, AssetIdOf<T>>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		BorrowSnapshot<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
@@ -551,6 +884,9 @@ pub mod pallet {
 		/// Updates the rate model of a stored market. Returns `Err` if the market
 		/// currency does not exist or the rate model is invalid.
 		///
+		/// Interest is accrued with the previous model first. The rate at target of an
+		/// adaptive model is carried over to a new adaptive model, within its bounds.
+		///
 		/// - `asset_id`: Market related currency
 		/// - `rate_model`: The new rate model to be updated
 		#[pallet::call_index(2)]
@@ -563,10 +899,14 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 			ensure!(rate_model.check_model(), Error::<T>::InvalidRateModelParam);
+			Self::accrue_interest(asset_id)?;
 			let market = Self::mutate_market(asset_id, |stored_market| {
 				stored_market.rate_model = rate_model;
 				stored_market.clone()
 			})?;
+			if !matches!(rate_model, InterestRateModel::Adaptive(_)) {
+				AdaptiveRateAtTarget::<T>::remove(asset_id);
+			}
 			Self::deposit_event(Event::<T>::UpdatedMarket(asset_id, market));
 
 			Ok(().into())
@@ -926,7 +1266,7 @@ pub mod pallet {
 			asset_id: AssetIdOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::ensure_active_market(asset_id)?;
+			Self::ensure_market_action_allowed(asset_id, MarketAction::Repay)?;
 			Self::accrue_interest(asset_id)?;
 			let account_borrows = Self::current_borrow_balance(&who, asset_id)?;
 			Self::do_repay_borrow(&who, asset_id, account_borrows)?;
@@ -947,7 +1287,7 @@ pub mod pallet {
 			enable: bool,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::ensure_active_market(asset_id)?;
+			Self::ensure_market_action_allowed(asset_id, MarketAction::CollateralToggle)?;
 			ensure!(
 				AccountDeposits::<T>::contains_key(asset_id, &who),
 				Error::<T>::NoDeposit
@@ -969,7 +1309,8 @@ pub mod pallet {
 		/// - `repay_amount`: the amount to be repaid borrow.
 		/// - `collateral_asset_id`: The collateral to seize from the borrower.
 		#[pallet::call_index(17)]
-		#[pallet::weight(T::WeightInfo::liquidate_borrow())]
+		#[pallet::weight(T::WeightInfo::liquidate_borrow()
+			.saturating_add(T::WeightInfo::write_off_bad_debt()))]
 		#[transactional]
 		pub fn liquidate_borrow(
 			origin: OriginFor<T>,
@@ -1013,27 +1354,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			T::ReserveOrigin::ensure_origin(origin)?;
 			let payer = T::Lookup::lookup(payer)?;
-			Self::ensure_active_market(asset_id)?;
-
-			T::Assets::transfer(
-				asset_id,
-				&payer,
-				&Self::account_id(),
-				add_amount,
-				Preservation::Expendable,
-			)?;
-			let total_reserves = TotalReserves::<T>::get(asset_id);
-			let total_reserves_new = total_reserves
-				.checked_add(add_amount)
-				.ok_or(ArithmeticError::Overflow)?;
-			TotalReserves::<T>::insert(asset_id, total_reserves_new);
-
-			Self::deposit_event(Event::<T>::ReservesAdded(
-				payer,
-				asset_id,
-				add_amount,
-				total_reserves_new,
-			));
+			Self::do_add_reserves(&payer, asset_id, add_amount)?;
 
 			Ok(().into())
 		}
@@ -1166,69 +1487,877 @@ pub mod pallet {
 			});
 			Ok(().into())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	pub fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account_truncating()
-	}
+		/// Sets or removes an efficiency mode category.
+		///
+		/// A category cannot be removed while assets are still assigned to it.
+		///
+		/// - `category_id`: the category to be updated
+		/// - `category`: the collateral factor and liquidation threshold of the category, `None`
+		///   removes it
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::update_market())]
+		#[transactional]
+		pub fn set_e_mode_category(
+			origin: OriginFor<T>,
+			category_id: EModeCategoryId,
+			category: Option<EModeCategory>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			match category {
+				Some(category) => {
+					ensure!(
+						category.collateral_factor < Ratio::one(),
+						Error::<T>::InvalidFactor
+					);
+					ensure!(
+						category.liquidation_threshold < Ratio::one()
+							&& category.liquidation_threshold >= category.collateral_factor,
+						Error::<T>::InvalidFactor
+					);
+					EModeCategories::<T>::insert(category_id, category);
+				}
+				None => {
+					ensure!(
+						!AssetEModeCategory::<T>::iter_values().any(|id| id == category_id),
+						Error::<T>::EModeCategoryInUse
+					);
+					EModeCategories::<T>::remove(category_id);
+				}
+			}
 
-	fn get_lf_borrowed_value(account: &T::AccountId) -> Result<FixedU128, DispatchError> {
-		let lf_borrowed_amount =
-			Self::current_borrow_balance(account, T::LiquidationFreeAssetId::get())?;
-		Self::get_asset_value(T::LiquidationFreeAssetId::get(), lf_borrowed_amount)
-	}
+			Self::deposit_event(Event::<T>::EModeCategoryUpdated {
+				category_id,
+				category,
+			});
+			Ok(().into())
+		}
 
-	fn get_lf_base_position(account: &T::AccountId) -> Result<FixedU128, DispatchError> {
-		let mut total_asset_value: FixedU128 = FixedU128::zero();
-		for (asset_id, _market) in Self::active_markets()
-			.filter(|(asset_id, _)| LiquidationFreeCollaterals::<T>::get().contains(asset_id))
-		{
-			total_asset_value = total_asset_value
-				.checked_add(&Self::collateral_asset_value(account, asset_id)?)
-				.ok_or(ArithmeticError::Overflow)?;
+		/// Assigns an asset to an efficiency mode category, or removes it from its category.
+		///
+		/// - `asset_id`: Market related currency
+		/// - `category_id`: the category the asset belongs to, `None` removes the asset from its
+		///   category
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::update_market())]
+		#[transactional]
+		pub fn set_asset_e_mode_category(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			category_id: Option<EModeCategoryId>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			match category_id {
+				Some(id) => {
+					ensure!(
+						EModeCategories::<T>::contains_key(id),
+						Error::<T>::EModeCategoryDoesNotExist
+					);
+					AssetEModeCategory::<T>::insert(asset_id, id);
+				}
+				None => AssetEModeCategory::<T>::remove(asset_id),
+			}
+
+			Self::deposit_event(Event::<T>::AssetEModeCategoryUpdated {
+				asset_id,
+				category_id,
+			});
+			Ok(().into())
 		}
-		Ok(total_asset_value)
-	}
 
-	fn get_lf_liquidation_base_position(
-		account: &T::AccountId,
-	) -> Result<FixedU128, DispatchError> {
-		let mut total_asset_value: FixedU128 = FixedU128::zero();
-		for (asset_id, _market) in Self::active_markets()
-			.filter(|(asset_id, _)| LiquidationFreeCollaterals::<T>::get().contains(asset_id))
-		{
-			total_asset_value = total_asset_value
-				.checked_add(&Self::liquidation_threshold_asset_value(account, asset_id)?)
-				.ok_or(ArithmeticError::Overflow)?;
+		/// Sets or removes the isolation mode of a collateral asset.
+		///
+		/// An asset can only be isolated while its market has no depositors. Removing the
+		/// isolation clears its total debt, and its accounts are no longer treated as isolated.
+		///
+		/// - `asset_id`: Market related currency
+		/// - `isolation_mode`: the debt ceiling and borrowable assets of the collateral, `None`
+		///   removes the isolation
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_isolation_mode())]
+		#[transactional]
+		pub fn set_isolation_mode(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			isolation_mode: Option<IsolationMode<BalanceOf<T>, T::MaxLengthLimit>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			let is_isolated = IsolationModes::<T>::contains_key(asset_id);
+			match isolation_mode {
+				Some(ref mode) => {
+					ensure!(
+						is_isolated || AccountDeposits::<T>::iter_prefix(asset_id).next().is_none(),
+						Error::<T>::IsolationMarketHasDepositors
+					);
+					IsolationModes::<T>::insert(asset_id, mode)
+				}
+				None => {
+					if is_isolated {
+						let _ = IsolationModeTotalDebt::<T>::clear_prefix(asset_id, u32::MAX, None);
+					}
+					IsolationModes::<T>::remove(asset_id)
+				}
+			}
+
+			Self::deposit_event(Event::<T>::IsolationModeUpdated {
+				asset_id,
+				isolation_mode,
+			});
+			Ok(().into())
 		}
-		Ok(total_asset_value)
-	}
 
-	pub fn get_account_liquidity(
-		account: &T::AccountId,
-	) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
-		let total_borrow_value = Self::total_borrowed_value(account)?;
-		let total_collateral_value = Self::total_collateral_value(account)?;
-		let lf_borrowed_value = Self::get_lf_borrowed_value(account)?;
-		let lf_base_position = Self::get_lf_base_position(account)?;
+		/// Sender enters or leaves an efficiency mode category.
+		///
+		/// All outstanding borrows must belong to the new category, and the account must
+		/// not have any shortfall afterwards.
+		///
+		/// - `category_id`: the category to enter, `None` leaves the current category
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::collateral_asset())]
+		#[transactional]
+		pub fn set_e_mode(
+			origin: OriginFor<T>,
+			category_id: Option<EModeCategoryId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			match category_id {
+				Some(id) => {
+					ensure!(
+						EModeCategories::<T>::contains_key(id),
+						Error::<T>::EModeCategoryDoesNotExist
+					);
+					for (asset_id, _) in Self::active_markets() {
+						if Self::current_borrow_balance(&who, asset_id)?.is_zero() {
+							continue;
+						}
+						ensure!(
+							AssetEModeCategory::<T>::get(asset_id) == Some(id),
+							Error::<T>::AssetNotInEModeCategory
+						);
+					}
+					AccountEModeCategory::<T>::insert(&who, id);
+				}
+				None => AccountEModeCategory::<T>::remove(&who),
+			}
+			let (_, shortfall, _, _) = Self::get_account_liquidity(&who)?;
+			ensure!(shortfall.is_zero(), Error::<T>::InsufficientLiquidity);
 
-		log::trace!(
-			target: "lend-market::get_account_liquidity",
-			"account: {:?}, total_borrow_value: {:?}, total_collateral_value: {:?}, lf_borrowed_value: {:?}, lf_base_position: {:?}",
-			account,
-			total_borrow_value.into_inner(),
-			total_collateral_value.into_inner(),
-			lf_borrowed_value.into_inner(),
-			lf_base_position.into_inner(),
-		);
-		match (
-			total_collateral_value > total_borrow_value,
-			lf_base_position > lf_borrowed_value,
-		) {
-			(true, true) => Ok((
-				total_collateral_value - total_borrow_value,
+			Self::deposit_event(Event::<T>::AccountEModeCategoryUpdated {
+				account: who,
+				category_id,
+			});
+			Ok(().into())
+		}
+
+		/// Sets or removes the account drawn on to cover bad debt.
+		///
+		/// - `account`: the backstop account, `None` removes it
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::update_market())]
+		#[transactional]
+		pub fn set_backstop_account(
+			origin: OriginFor<T>,
+			account: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			BackstopAccount::<T>::set(account.clone());
+
+			Self::deposit_event(Event::<T>::BackstopAccountUpdated { account });
+			Ok(().into())
+		}
+
+		/// Writes off the debt of a borrower in shortfall who has no collateral left.
+		///
+		/// The collateral worth no more than dust is added to the market reserves. The debt is
+		/// covered by the market reserves first, then by the backstop account, and
+		/// the rest is socialized among the suppliers through the exchange rate.
+		///
+		/// - `borrower`: the borrower whose debt is written off
+		/// - `asset_id`: the asset of the debt
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::write_off_bad_debt())]
+		#[transactional]
+		pub fn write_off_bad_debt(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_active_market(asset_id)?;
+			ensure!(
+				!Self::has_collateral(&borrower)?,
+				Error::<T>::CollateralNotExhausted
+			);
+			ensure!(
+				Self::has_shortfall(&borrower)?,
+				Error::<T>::InsufficientShortfall
+			);
+			Self::accrue_interest(asset_id)?;
+			Self::seize_dust_collateral(&borrower)?;
+			Self::do_write_off_bad_debt(&borrower, asset_id)?;
+
+			Ok(().into())
+		}
+
+		/// Puts a market into auction mode, or back to fixed incentive liquidations.
+		///
+		/// - `asset_id`: Market related currency
+		/// - `config`: the liquidation auction parameters, `None` disables the auction mode
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::set_liquidation_auction_config())]
+		#[transactional]
+		pub fn set_liquidation_auction_config(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			config: Option<LiquidationAuctionConfig>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			match config {
+				Some(config) => {
+					ensure!(
+						config.start_incentive >= Rate::one()
+							&& config.end_incentive >= config.start_incentive
+							&& !config.duration.is_zero(),
+						Error::<T>::InvalidLiquidationAuctionConfig
+					);
+					LiquidationAuctionConfigs::<T>::insert(asset_id, config);
+				}
+				None => LiquidationAuctionConfigs::<T>::remove(asset_id),
+			}
+
+			Self::deposit_event(Event::<T>::LiquidationAuctionConfigUpdated { asset_id, config });
+			Ok(().into())
+		}
+
+		/// Opens a descending price auction over the collateral of a liquidatable borrower.
+		///
+		/// - `borrower`: the borrower to be liquidated.
+		/// - `liquidation_asset_id`: the assert to be liquidated.
+		/// - `collateral_asset_id`: The collateral to seize from the borrower.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::open_liquidation_auction())]
+		#[transactional]
+		pub fn open_liquidation_auction(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			collateral_asset_id: AssetIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let market =
+				Self::ensure_market_action_allowed(liquidation_asset_id, MarketAction::Liquidate)?;
+			Self::ensure_active_market(collateral_asset_id)?;
+			let config = LiquidationAuctionConfigs::<T>::get(liquidation_asset_id)
+				.ok_or(Error::<T>::LiquidationAuctionDisabled)?;
+			ensure!(
+				!LiquidationAuctions::<T>::contains_key(liquidation_asset_id, &borrower),
+				Error::<T>::LiquidationAuctionAlreadyOpened
+			);
+			ensure!(
+				!LiquidationFreeCollaterals::<T>::get().contains(&collateral_asset_id),
+				Error::<T>::CollateralReserved
+			);
+			ensure!(
+				AccountDeposits::<T>::get(collateral_asset_id, &borrower).is_collateral,
+				Error::<T>::DepositsAreNotCollateral
+			);
+			Self::accrue_interest(liquidation_asset_id)?;
+			Self::accrue_interest(collateral_asset_id)?;
+			Self::liquidate_borrow_allowed(&borrower, liquidation_asset_id, Zero::zero(), &market)?;
+
+			let start_time = T::UnixTime::now().as_secs();
+			LiquidationAuctions::<T>::insert(
+				liquidation_asset_id,
+				&borrower,
+				LiquidationAuction {
+					collateral_asset_id,
+					start_time,
+					config,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::LiquidationAuctionOpened {
+				borrower,
+				liquidation_asset_id,
+				collateral_asset_id,
+				start_time,
+			});
+			Ok(().into())
+		}
+
+		/// Repays debt of a borrower in a liquidation auction at the current auction price.
+		///
+		/// The auction is closed once the borrower is no longer liquidatable. A stale auction,
+		/// whose borrower is no longer liquidatable or whose market config has changed, is closed
+		/// without settling the bid.
+		///
+		/// - `borrower`: the borrower to be liquidated.
+		/// - `liquidation_asset_id`: the assert to be liquidated.
+		/// - `repay_amount`: the amount to be repaid borrow.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::bid_liquidation_auction())]
+		#[transactional]
+		pub fn bid_liquidation_auction(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			#[pallet::compact] repay_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let auction = LiquidationAuctions::<T>::get(liquidation_asset_id, &borrower)
+				.ok_or(Error::<T>::LiquidationAuctionDoesNotExist)?;
+			Self::accrue_interest(liquidation_asset_id)?;
+			Self::accrue_interest(auction.collateral_asset_id)?;
+			if Self::is_liquidation_auction_stale(&borrower, liquidation_asset_id, &auction)? {
+				LiquidationAuctions::<T>::remove(liquidation_asset_id, &borrower);
+				Self::deposit_event(Event::<T>::LiquidationAuctionClosed {
+					borrower,
+					liquidation_asset_id,
+				});
+				return Ok(().into());
+			}
+
+			let elapsed = T::UnixTime::now()
+				.as_secs()
+				.saturating_sub(auction.start_time);
+			let liquidate_incentive = auction.config.incentive_at(elapsed);
+			Self::do_liquidate_borrow_with_incentive(
+				who.clone(),
+				borrower.clone(),
+				liquidation_asset_id,
+				repay_amount,
+				auction.collateral_asset_id,
+				liquidate_incentive,
+			)?;
+			Self::deposit_event(Event::<T>::LiquidationAuctionBid {
+				bidder: who,
+				borrower: borrower.clone(),
+				liquidation_asset_id,
+				repay_amount,
+				liquidate_incentive,
+			});
+
+			if !Self::is_liquidatable(&borrower, liquidation_asset_id)? {
+				LiquidationAuctions::<T>::remove(liquidation_asset_id, &borrower);
+				Self::deposit_event(Event::<T>::LiquidationAuctionClosed {
+					borrower,
+					liquidation_asset_id,
+				});
+			}
+			Ok(().into())
+		}
+
+		/// Closes the liquidation auction of a borrower who is no longer liquidatable, or whose
+		/// market config has been changed or removed since the auction was opened.
+		///
+		/// - `borrower`: the borrower of the auction.
+		/// - `liquidation_asset_id`: the assert of the auction.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::close_liquidation_auction())]
+		#[transactional]
+		pub fn close_liquidation_auction(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let auction = LiquidationAuctions::<T>::get(liquidation_asset_id, &borrower)
+				.ok_or(Error::<T>::LiquidationAuctionDoesNotExist)?;
+			Self::accrue_interest(liquidation_asset_id)?;
+			ensure!(
+				Self::is_liquidation_auction_stale(&borrower, liquidation_asset_id, &auction)?,
+				Error::<T>::BorrowerStillLiquidatable
+			);
+
+			LiquidationAuctions::<T>::remove(liquidation_asset_id, &borrower);
+			Self::deposit_event(Event::<T>::LiquidationAuctionClosed {
+				borrower,
+				liquidation_asset_id,
+			});
+			Ok(().into())
+		}
+
+		/// Sender borrows cash of a market and dispatches `calls` with its own origin.
+		///
+		/// The borrowed amount plus `FlashLoanFee` must be repayable from the sender's account
+		/// once all calls are dispatched, otherwise the whole extrinsic reverts. The fee is
+		/// added to the reserves of the market.
+		///
+		/// - `asset_id`: the asset to be borrowed.
+		/// - `amount`: the amount to be borrowed.
+		/// - `calls`: the calls to be dispatched with the borrowed assets.
+		#[pallet::call_index(33)]
+		#[pallet::weight({
+			let dispatch_weight = calls
+				.iter()
+				.map(|call| call.get_dispatch_info().weight)
+				.fold(Weight::zero(), |total, weight| total.saturating_add(weight));
+			T::WeightInfo::borrow()
+				.saturating_add(T::WeightInfo::repay_borrow())
+				.saturating_add(dispatch_weight)
+		})]
+		#[transactional]
+		pub fn flash_loan(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			calls: BoundedVec<<T as Config>::RuntimeCall, T::MaxFlashLoanCalls>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin.clone())?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			Self::ensure_market_action_allowed(asset_id, MarketAction::Borrow)?;
+			Self::accrue_interest(asset_id)?;
+			Self::ensure_enough_cash(asset_id, amount)?;
+
+			Self::take_flash_loan(&who, asset_id, amount)?;
+
+			for call in calls {
+				call.dispatch(origin.clone()).map_err(|e| e.error)?;
+			}
+
+			let fee = Self::repay_flash_loan(&who, asset_id, amount)?;
+
+			Self::deposit_event(Event::<T>::FlashLoaned {
+				borrower: who,
+				asset_id,
+				amount,
+				fee,
+			});
+			Ok(().into())
+		}
+
+		/// Pauses or unpauses an action in a market, the other actions are left untouched.
+		///
+		/// The origin must conform to `ControlOrigin`.
+		///
+		/// - `asset_id`: market related currency
+		/// - `action`: the action to be paused or unpaused
+		/// - `paused`: whether the action is paused
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::set_market_action_paused())]
+		#[transactional]
+		pub fn set_market_action_paused(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			action: MarketAction,
+			paused: bool,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+
+			if paused {
+				PausedMarketActions::<T>::insert(asset_id, action, true);
+			} else {
+				PausedMarketActions::<T>::remove(asset_id, action);
+			}
+			Self::deposit_event(Event::<T>::MarketActionPauseUpdated {
+				asset_id,
+				action,
+				paused,
+			});
+			Ok(().into())
+		}
+
+		/// The sender liquidates a borrow without holding the liquidation asset.
+		///
+		/// `repay_amount` is borrowed from the liquidation market like a flash loan, the seized
+		/// collateral is redeemed and swapped through `route` to repay the loan and its fee,
+		/// and the liquidator keeps the collateral left.
+		///
+		/// - `borrower`: the borrower to be liquidated.
+		/// - `liquidation_asset_id`: the assert to be liquidated.
+		/// - `repay_amount`: the amount to be repaid borrow.
+		/// - `collateral_asset_id`: The collateral to seize from the borrower.
+		/// - `route`: the venue where the collateral is swapped.
+		/// - `min_profit`: the minimum amount of collateral left to the liquidator.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::liquidate_borrow()
+			.saturating_add(T::WeightInfo::write_off_bad_debt())
+			.saturating_add(T::WeightInfo::redeem())
+			.saturating_add(T::WeightInfo::borrow())
+			.saturating_add(T::WeightInfo::repay_borrow()))]
+		#[transactional]
+		pub fn liquidate_borrow_and_swap(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			#[pallet::compact] repay_amount: BalanceOf<T>,
+			collateral_asset_id: AssetIdOf<T>,
+			route: LiquidationSwapRoute,
+			#[pallet::compact] min_profit: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			// if liquidate by incentive, check the collateral asset
+			let liquidation_free_collaterals = LiquidationFreeCollaterals::<T>::get();
+			ensure!(
+				!liquidation_free_collaterals.contains(&collateral_asset_id),
+				Error::<T>::CollateralReserved
+			);
+			ensure!(!repay_amount.is_zero(), Error::<T>::InvalidAmount);
+			Self::ensure_market_action_allowed(liquidation_asset_id, MarketAction::Liquidate)?;
+			Self::ensure_market_action_allowed(collateral_asset_id, MarketAction::Redeem)?;
+			Self::accrue_interest(liquidation_asset_id)?;
+			Self::accrue_interest(collateral_asset_id)?;
+			Self::ensure_enough_cash(liquidation_asset_id, repay_amount)?;
+
+			Self::take_flash_loan(&who, liquidation_asset_id, repay_amount)?;
+
+			let vouchers_before =
+				AccountDeposits::<T>::get(collateral_asset_id, &who).voucher_balance;
+			Self::do_liquidate_borrow(
+				who.clone(),
+				borrower.clone(),
+				liquidation_asset_id,
+				repay_amount,
+				collateral_asset_id,
+			)?;
+			let seized_vouchers = AccountDeposits::<T>::get(collateral_asset_id, &who)
+				.voucher_balance
+				.checked_sub(vouchers_before)
+				.ok_or(ArithmeticError::Underflow)?;
+
+			let exchange_rate = Self::exchange_rate_stored(collateral_asset_id)?;
+			Self::update_earned_stored(&who, collateral_asset_id, exchange_rate)?;
+			let collateral_amount =
+				Self::do_redeem_voucher(&who, collateral_asset_id, seized_vouchers)?;
+
+			let fee = T::FlashLoanFee::get().mul_ceil(repay_amount);
+			let owed_amount = repay_amount
+				.checked_add(fee)
+				.ok_or(ArithmeticError::Overflow)?;
+			let swapped_amount = if collateral_asset_id == liquidation_asset_id {
+				owed_amount
+			} else {
+				Self::swap_exact_amount_out(
+					&who,
+					route,
+					collateral_asset_id,
+					liquidation_asset_id,
+					owed_amount,
+					collateral_amount,
+				)?
+			};
+			Self::repay_flash_loan(&who, liquidation_asset_id, repay_amount)?;
+
+			let profit = collateral_amount
+				.checked_sub(swapped_amount)
+				.ok_or(Error::<T>::InsufficientLiquidationProfit)?;
+			ensure!(
+				profit >= min_profit,
+				Error::<T>::InsufficientLiquidationProfit
+			);
+
+			Self::deposit_event(Event::<T>::LiquidatedAndSwapped {
+				liquidator: who,
+				borrower,
+				liquidation_asset_id,
+				collateral_asset_id,
+				repay_amount,
+				profit,
+			});
+			Ok(().into())
+		}
+
+		/// Sender allows a delegatee to borrow against their liquidity, up to `limit` of
+		/// outstanding debt. A zero `limit` revokes the allowance.
+		///
+		/// - `delegatee`: the account allowed to borrow.
+		/// - `asset_id`: the asset to be borrowed.
+		/// - `limit`: the maximum debt the delegatee can draw.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::approve_borrow_allowance())]
+		#[transactional]
+		pub fn approve_borrow_allowance(
+			origin: OriginFor<T>,
+			delegatee: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] limit: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who != delegatee, Error::<T>::InvalidDelegatee);
+			Self::market(asset_id)?;
+
+			if limit.is_zero() {
+				BorrowAllowances::<T>::remove(asset_id, (&who, &delegatee));
+			} else {
+				BorrowAllowances::<T>::insert(asset_id, (&who, &delegatee), limit);
+			}
+			Self::deposit_event(Event::<T>::BorrowAllowanceApproved {
+				delegator: who,
+				delegatee,
+				asset_id,
+				limit,
+			});
+			Ok(().into())
+		}
+
+		/// Sender borrows assets against the liquidity of a delegator, the debt is owed by
+		/// the delegator and tracked for the sender.
+		///
+		/// - `delegator`: the account whose liquidity is borrowed against.
+		/// - `asset_id`: the asset to be borrowed.
+		/// - `borrow_amount`: the amount to be borrowed.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::borrow_on_behalf())]
+		#[transactional]
+		pub fn borrow_on_behalf(
+			origin: OriginFor<T>,
+			delegator: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] borrow_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who != delegator, Error::<T>::InvalidDelegatee);
+			Self::accrue_interest(asset_id)?;
+
+			let delegated_borrows =
+				Self::current_delegated_borrow_balance(&delegator, &who, asset_id)?;
+			let delegated_borrows_new = delegated_borrows
+				.checked_add(borrow_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				delegated_borrows_new <= BorrowAllowances::<T>::get(asset_id, (&delegator, &who)),
+				Error::<T>::BorrowAllowanceExceeded
+			);
+
+			Self::do_borrow_to(&delegator, &who, asset_id, borrow_amount)?;
+			DelegatedBorrows::<T>::insert(
+				(asset_id, &delegator, &who),
+				BorrowSnapshot {
+					principal: delegated_borrows_new,
+					borrow_index: BorrowIndex::<T>::get(asset_id),
+				},
+			);
+			Self::deposit_event(Event::<T>::DelegatedBorrowed {
+				delegator,
+				delegatee: who,
+				asset_id,
+				amount: borrow_amount,
+			});
+			Ok(().into())
+		}
+
+		/// Sender repays a delegated borrow, the sender must be its delegator or delegatee.
+		///
+		/// - `delegator`: the account owing the debt.
+		/// - `delegatee`: the account which has drawn the debt.
+		/// - `asset_id`: the asset to be repaid.
+		/// - `repay_amount`: the amount to be repaid.
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::repay_delegated_borrow())]
+		#[transactional]
+		pub fn repay_delegated_borrow(
+			origin: OriginFor<T>,
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] repay_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				who == delegator || who == delegatee,
+				Error::<T>::NotDelegationParty
+			);
+			Self::ensure_market_action_allowed(asset_id, MarketAction::Repay)?;
+			Self::accrue_interest(asset_id)?;
+
+			let delegated_borrows =
+				Self::current_delegated_borrow_balance(&delegator, &delegatee, asset_id)?;
+			let delegated_borrows_new = delegated_borrows
+				.checked_sub(repay_amount)
+				.ok_or(Error::<T>::TooMuchRepay)?;
+
+			let account_borrows = Self::current_borrow_balance(&delegator, asset_id)?;
+			Self::do_repay_borrow_with_amount(
+				&who,
+				&delegator,
+				asset_id,
+				account_borrows,
+				repay_amount,
+			)?;
+			if delegated_borrows_new.is_zero() {
+				DelegatedBorrows::<T>::remove((asset_id, &delegator, &delegatee));
+			} else {
+				DelegatedBorrows::<T>::insert(
+					(asset_id, &delegator, &delegatee),
+					BorrowSnapshot {
+						principal: delegated_borrows_new,
+						borrow_index: BorrowIndex::<T>::get(asset_id),
+					},
+				);
+			}
+			Self::deposit_event(Event::<T>::DelegatedBorrowRepaid {
+				delegator,
+				delegatee,
+				payer: who,
+				asset_id,
+				amount: repay_amount,
+			});
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Lends `amount` of the market cash to `who` until `repay_flash_loan` is called.
+	fn take_flash_loan(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		// The loan is still accounted in the exchange rate and utilization ratio, so that the
+		// calls cannot take advantage of the drained market, see `get_accounted_cash`
+		FlashLoans::<T>::mutate(asset_id, |loans| *loans = loans.saturating_add(amount));
+		T::Assets::transfer(
+			asset_id,
+			&Self::account_id(),
+			who,
+			amount,
+			Preservation::Expendable,
+		)?;
+		Ok(())
+	}
+
+	/// Takes back a flash loan and its fee from `who`, the fee is added to the reserves.
+	fn repay_flash_loan(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let fee = T::FlashLoanFee::get().mul_ceil(amount);
+		FlashLoans::<T>::mutate(asset_id, |loans| *loans = loans.saturating_sub(amount));
+		T::Assets::transfer(
+			asset_id,
+			who,
+			&Self::account_id(),
+			amount.checked_add(fee).ok_or(ArithmeticError::Overflow)?,
+			Preservation::Expendable,
+		)
+		.map_err(|_| Error::<T>::FlashLoanNotRepaid)?;
+		TotalReserves::<T>::try_mutate(asset_id, |total_reserves| -> DispatchResult {
+			*total_reserves = total_reserves
+				.checked_add(fee)
+				.ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+		Ok(fee)
+	}
+
+	/// Swaps at most `max_amount_in` of `asset_in` for `amount_out` of `asset_out` through
+	/// `route`, returns the amount of `asset_in` spent.
+	fn swap_exact_amount_out(
+		who: &T::AccountId,
+		route: LiquidationSwapRoute,
+		asset_in: AssetIdOf<T>,
+		asset_out: AssetIdOf<T>,
+		amount_out: BalanceOf<T>,
+		max_amount_in: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match route {
+			LiquidationSwapRoute::StablePool => {
+				let (pool_id, index_in, index_out) =
+					T::StablePoolHandler::get_pool_id(&asset_in, &asset_out)
+						.ok_or(Error::<T>::SwapRouteNotFound)?;
+				let amount_in =
+					T::StablePoolHandler::get_swap_input(pool_id, index_in, index_out, amount_out)?;
+				ensure!(
+					amount_in <= max_amount_in,
+					Error::<T>::InsufficientLiquidationProfit
+				);
+				T::StablePoolHandler::swap(
+					who, pool_id, index_in, index_out, amount_in, amount_out,
+				)?;
+				Ok(amount_in)
+			}
+			LiquidationSwapRoute::Zenlink => {
+				let path = vec![
+					Self::zenlink_asset_id(asset_in)?,
+					Self::zenlink_asset_id(asset_out)?,
+				];
+				let amount_in = T::DexOperator::get_amount_in_by_path(amount_out, &path)
+					.map_err(|_| Error::<T>::SwapRouteNotFound)?
+					.first()
+					.copied()
+					.ok_or(Error::<T>::SwapRouteNotFound)?;
+				ensure!(
+					amount_in <= max_amount_in,
+					Error::<T>::InsufficientLiquidationProfit
+				);
+				T::DexOperator::inner_swap_assets_for_exact_assets(
+					who, amount_out, amount_in, &path, who,
+				)?;
+				Ok(amount_in)
+			}
+		}
+	}
+
+	pub fn zenlink_asset_id(asset_id: AssetIdOf<T>) -> Result<ZenlinkAssetId, DispatchError> {
+		ZenlinkAssetId::try_convert_from(asset_id, T::ParachainId::get().into())
+			.map_err(|_| Error::<T>::ZenlinkAssetConversionFailed.into())
+	}
+
+	fn get_lf_borrowed_value(account: &T::AccountId) -> Result<FixedU128, DispatchError> {
+		let lf_borrowed_amount =
+			Self::current_borrow_balance(account, T::LiquidationFreeAssetId::get())?;
+		Self::get_asset_value(T::LiquidationFreeAssetId::get(), lf_borrowed_amount)
+	}
+
+	fn get_lf_base_position(account: &T::AccountId) -> Result<FixedU128, DispatchError> {
+		let mut total_asset_value: FixedU128 = FixedU128::zero();
+		for (asset_id, _market) in Self::active_markets()
+			.filter(|(asset_id, _)| LiquidationFreeCollaterals::<T>::get().contains(asset_id))
+		{
+			total_asset_value = total_asset_value
+				.checked_add(&Self::collateral_asset_value(account, asset_id)?)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+		Ok(total_asset_value)
+	}
+
+	fn get_lf_liquidation_base_position(
+		account: &T::AccountId,
+	) -> Result<FixedU128, DispatchError> {
+		let mut total_asset_value: FixedU128 = FixedU128::zero();
+		for (asset_id, _market) in Self::active_markets()
+			.filter(|(asset_id, _)| LiquidationFreeCollaterals::<T>::get().contains(asset_id))
+		{
+			total_asset_value = total_asset_value
+				.checked_add(&Self::liquidation_threshold_asset_value(account, asset_id)?)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+		Ok(total_asset_value)
+	}
+
+	pub fn get_account_liquidity(
+		account: &T::AccountId,
+	) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
+		let total_borrow_value = Self::total_borrowed_value(account)?;
+		let total_collateral_value = Self::total_collateral_value(account)?;
+		let lf_borrowed_value = Self::get_lf_borrowed_value(account)?;
+		let lf_base_position = Self::get_lf_base_position(account)?;
+
+		log::trace!(
+			target: "lend-market::get_account_liquidity",
+			"account: {:?}, total_borrow_value: {:?}, total_collateral_value: {:?}, lf_borrowed_value: {:?}, lf_base_position: {:?}",
+			account,
+			total_borrow_value.into_inner(),
+			total_collateral_value.into_inner(),
+			lf_borrowed_value.into_inner(),
+			lf_base_position.into_inner(),
+		);
+		match (
+			total_collateral_value > total_borrow_value,
+			lf_base_position > lf_borrowed_value,
+		) {
+			(true, true) => Ok((
+				total_collateral_value - total_borrow_value,
 				FixedU128::zero(),
 				lf_base_position - lf_borrowed_value,
 				FixedU128::zero(),
@@ -1337,7 +2466,8 @@ impl<T: Config> Pallet<T> {
 		let underlying_amount =
 			Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
 		let market = Self::market(asset_id)?;
-		let effects_amount = market.collateral_factor.mul_ceil(underlying_amount);
+		let (collateral_factor, _) = Self::collateral_factors(supplier, asset_id, &market);
+		let effects_amount = collateral_factor.mul_ceil(underlying_amount);
 
 		Ok(BalanceOf::<T>::saturated_from(effects_amount))
 	}
@@ -1369,11 +2499,116 @@ impl<T: Config> Pallet<T> {
 		let underlying_amount =
 			Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
 		let market = Self::market(asset_id)?;
-		let effects_amount = market.liquidation_threshold.mul_ceil(underlying_amount);
+		let (_, liquidation_threshold) = Self::collateral_factors(borrower, asset_id, &market);
+		let effects_amount = liquidation_threshold.mul_ceil(underlying_amount);
 
 		Self::get_asset_value(asset_id, effects_amount)
 	}
 
+	/// Returns the collateral factor and liquidation threshold of `asset_id` for `account`.
+	///
+	/// The factors of the account's efficiency mode category apply when the asset belongs to
+	/// it, otherwise the market ones are used.
+	fn collateral_factors(
+		account: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		market: &Market<BalanceOf<T>>,
+	) -> (Ratio, Ratio) {
+		AccountEModeCategory::<T>::get(account)
+			.filter(|id| AssetEModeCategory::<T>::get(asset_id) == Some(*id))
+			.and_then(EModeCategories::<T>::get)
+			.map(|category| (category.collateral_factor, category.liquidation_threshold))
+			.unwrap_or((market.collateral_factor, market.liquidation_threshold))
+	}
+
+	/// Returns the isolated collateral used by `account`, if any.
+	///
+	/// Accounts are not released when the isolation of their collateral is removed, so the
+	/// collateral only counts while it is still isolated.
+	fn isolated_collateral(account: &T::AccountId) -> Option<AssetIdOf<T>> {
+		AccountIsolatedCollateral::<T>::get(account)
+			.filter(|asset_id| IsolationModes::<T>::contains_key(asset_id))
+	}
+
+	/// Stops counting `asset_id` as the isolated collateral of `account`, if it is.
+	fn release_isolated_collateral(account: &T::AccountId, asset_id: AssetIdOf<T>) {
+		AccountIsolatedCollateral::<T>::mutate_exists(account, |isolated_collateral| {
+			if *isolated_collateral == Some(asset_id) {
+				*isolated_collateral = None;
+			}
+		});
+	}
+
+	/// Checks the efficiency mode and isolation mode restrictions of a borrow
+	fn ensure_borrow_mode_allowed(
+		asset_id: AssetIdOf<T>,
+		borrower: &T::AccountId,
+		borrow_value: FixedU128,
+	) -> DispatchResult {
+		if let Some(category_id) = AccountEModeCategory::<T>::get(borrower) {
+			ensure!(
+				AssetEModeCategory::<T>::get(asset_id) == Some(category_id),
+				Error::<T>::AssetNotInEModeCategory
+			);
+		}
+
+		if let Some(collateral_asset_id) = Self::isolated_collateral(borrower) {
+			let isolation_mode = IsolationModes::<T>::get(collateral_asset_id)
+				.ok_or(Error::<T>::NotBorrowableInIsolationMode)?;
+			ensure!(
+				isolation_mode.borrowable_assets.contains(&asset_id),
+				Error::<T>::NotBorrowableInIsolationMode
+			);
+			let total_debt_value = Self::isolation_mode_debt_value(collateral_asset_id)?
+				.checked_add(&borrow_value)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				total_debt_value.into_inner() <= isolation_mode.debt_ceiling,
+				Error::<T>::DebtCeilingExceeded
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Adjusts the total debt backed by the isolated collateral of `account`, if any.
+	fn update_isolation_mode_debt(
+		account: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		increase: bool,
+	) -> DispatchResult {
+		let Some(collateral_asset_id) = Self::isolated_collateral(account) else {
+			return Ok(());
+		};
+		IsolationModeTotalDebt::<T>::mutate_exists(collateral_asset_id, asset_id, |total_debt| {
+			let debt = total_debt.unwrap_or_default();
+			let debt = if increase {
+				debt.saturating_add(amount)
+			} else {
+				// Interest is not tracked by the debt ceiling, so repayments can exceed it
+				debt.saturating_sub(amount)
+			};
+			*total_debt = Some(debt).filter(|debt| !debt.is_zero());
+		});
+
+		Ok(())
+	}
+
+	/// Returns the current value of the total debt backed by an isolated collateral asset.
+	fn isolation_mode_debt_value(
+		collateral_asset_id: AssetIdOf<T>,
+	) -> Result<FixedU128, DispatchError> {
+		let mut total_debt_value = FixedU128::zero();
+		for (asset_id, debt) in IsolationModeTotalDebt::<T>::iter_prefix(collateral_asset_id) {
+			total_debt_value = total_debt_value
+				.checked_add(&Self::get_asset_value(asset_id, debt)?)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+
+		Ok(total_debt_value)
+	}
+
 	fn total_collateral_value(supplier: &T::AccountId) -> Result<FixedU128, DispatchError> {
 		let mut total_asset_value: FixedU128 = FixedU128::zero();
 		for (asset_id, _market) in Self::active_markets() {
@@ -1427,7 +2662,8 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let market = Self::market(asset_id)?;
-		let effects_amount = market.collateral_factor.mul_ceil(redeem_amount);
+		let (collateral_factor, _) = Self::collateral_factors(redeemer, asset_id, &market);
+		let effects_amount = collateral_factor.mul_ceil(redeem_amount);
 		let redeem_effects_value = Self::get_asset_value(asset_id, effects_amount)?;
 		log::trace!(
 			target: "lend-market::redeem_allowed",
@@ -1467,6 +2703,7 @@ impl<T: Config> Pallet<T> {
 			if d.voucher_balance.is_zero() {
 				// remove deposits storage if zero balance
 				*deposits = None;
+				Self::release_isolated_collateral(who, asset_id);
 			} else {
 				*deposits = Some(d);
 			}
@@ -1500,6 +2737,7 @@ impl<T: Config> Pallet<T> {
 		Self::ensure_under_borrow_cap(asset_id, borrow_amount)?;
 		Self::ensure_enough_cash(asset_id, borrow_amount)?;
 		let borrow_value = Self::get_asset_value(asset_id, borrow_amount)?;
+		Self::ensure_borrow_mode_allowed(asset_id, borrower, borrow_value)?;
 		Self::ensure_liquidity(
 			borrower,
 			borrow_value,
@@ -1525,6 +2763,7 @@ impl<T: Config> Pallet<T> {
 
 	#[require_transactional]
 	fn do_repay_borrow_with_amount(
+		payer: &T::AccountId,
 		borrower: &T::AccountId,
 		asset_id: AssetIdOf<T>,
 		account_borrows: BalanceOf<T>,
@@ -1538,7 +2777,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Assets::transfer(
 			asset_id,
-			borrower,
+			payer,
 			&Self::account_id(),
 			repay_amount,
 			Preservation::Expendable,
@@ -1562,7 +2801,54 @@ impl<T: Config> Pallet<T> {
 			},
 		);
 		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+		Self::update_isolation_mode_debt(borrower, asset_id, repay_amount, false)?;
+
+		Ok(())
+	}
+
+	/// `borrower` borrows `amount` against their liquidity, the asset is sent to `recipient`.
+	fn do_borrow_to(
+		borrower: &AccountIdOf<T>,
+		recipient: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> Result<(), DispatchError> {
+		Self::ensure_market_action_allowed(asset_id, MarketAction::Borrow)?;
+
+		Self::accrue_interest(asset_id)?;
+		Self::borrow_allowed_for_market_bond(asset_id, borrower, amount)?;
+		Self::borrow_allowed(asset_id, borrower, amount)?;
+
+		// update borrow index after accrue interest.
+		Self::update_reward_borrow_index(asset_id)?;
+		Self::distribute_borrower_reward(asset_id, borrower)?;
 
+		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
+		let account_borrows_new = account_borrows
+			.checked_add(amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		let total_borrows = TotalBorrows::<T>::get(asset_id);
+		let total_borrows_new = total_borrows
+			.checked_add(amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		AccountBorrows::<T>::insert(
+			asset_id,
+			borrower,
+			BorrowSnapshot {
+				principal: account_borrows_new,
+				borrow_index: BorrowIndex::<T>::get(asset_id),
+			},
+		);
+		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+		Self::update_isolation_mode_debt(borrower, asset_id, amount, true)?;
+		T::Assets::transfer(
+			asset_id,
+			&Self::account_id(),
+			recipient,
+			amount,
+			Preservation::Expendable,
+		)?;
+		Self::deposit_event(Event::<T>::Borrowed(borrower.clone(), asset_id, amount));
 		Ok(())
 	}
 
@@ -1573,6 +2859,59 @@ impl<T: Config> Pallet<T> {
 		asset_id: AssetIdOf<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
 		let snapshot: BorrowSnapshot<BalanceOf<T>> = AccountBorrows::<T>::get(asset_id, who);
+		Self::borrow_balance_of_snapshot(asset_id, &snapshot)
+	}
+
+	// Calculates and returns the most recent amount of `currency_id` borrowed by `delegatee`
+	// against the liquidity of `delegator`.
+	pub fn current_delegated_borrow_balance(
+		delegator: &T::AccountId,
+		delegatee: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let snapshot = DelegatedBorrows::<T>::get((asset_id, delegator, delegatee));
+		let delegated_borrow_balance = Self::borrow_balance_of_snapshot(asset_id, &snapshot)?;
+		Ok(delegated_borrow_balance.min(Self::current_borrow_balance(delegator, asset_id)?))
+	}
+
+	// Scales the debts drawn by the delegatees of `delegator` by the share of its borrows left
+	// after a repay, liquidation or write-off, so the allowances they used are freed with it.
+	fn reduce_delegated_borrows(
+		delegator: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		account_borrows: BalanceOf<T>,
+		account_borrows_new: BalanceOf<T>,
+	) -> DispatchResult {
+		if account_borrows_new >= account_borrows {
+			return Ok(());
+		}
+		let remaining_ratio = Rate::checked_from_rational(account_borrows_new, account_borrows)
+			.ok_or(ArithmeticError::Overflow)?;
+		let borrow_index = BorrowIndex::<T>::get(asset_id);
+		for (delegatee, snapshot) in DelegatedBorrows::<T>::iter_prefix((asset_id, delegator)) {
+			let delegated_borrows = Self::borrow_balance_of_snapshot(asset_id, &snapshot)?;
+			let delegated_borrows_new = remaining_ratio
+				.saturating_mul_int(delegated_borrows)
+				.min(account_borrows_new);
+			if delegated_borrows_new.is_zero() {
+				DelegatedBorrows::<T>::remove((asset_id, delegator, &delegatee));
+			} else {
+				DelegatedBorrows::<T>::insert(
+					(asset_id, delegator, &delegatee),
+					BorrowSnapshot {
+						principal: delegated_borrows_new,
+						borrow_index,
+					},
+				);
+			}
+		}
+		Ok(())
+	}
+
+	fn borrow_balance_of_snapshot(
+		asset_id: AssetIdOf<T>,
+		snapshot: &BorrowSnapshot<BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
 		if snapshot.principal.is_zero() || snapshot.borrow_index.is_zero() {
 			return Ok(Zero::zero());
 		}
@@ -1676,15 +3015,45 @@ impl<T: Config> Pallet<T> {
 	/// account for borrower. Then the protocol will reduce borrower's debt
 	/// and liquidator will receive collateral_asset_id(as voucher amount) from
 	/// borrower.
+	///
+	/// Markets in auction mode can only be liquidated through liquidation auctions.
+	#[require_transactional]
+	pub fn do_liquidate_borrow(
+		liquidator: T::AccountId,
+		borrower: T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+		repay_amount: BalanceOf<T>,
+		collateral_asset_id: AssetIdOf<T>,
+	) -> DispatchResult {
+		let market =
+			Self::ensure_market_action_allowed(liquidation_asset_id, MarketAction::Liquidate)?;
+		ensure!(
+			!LiquidationAuctionConfigs::<T>::contains_key(liquidation_asset_id),
+			Error::<T>::LiquidationAuctionRequired
+		);
+
+		Self::do_liquidate_borrow_with_incentive(
+			liquidator,
+			borrower,
+			liquidation_asset_id,
+			repay_amount,
+			collateral_asset_id,
+			market.liquidate_incentive,
+		)
+	}
+
+	/// Liquidates a borrow, the liquidator receives `liquidate_incentive` times the repaid value
+	/// in collateral.
 	#[require_transactional]
-	pub fn do_liquidate_borrow(
+	fn do_liquidate_borrow_with_incentive(
 		liquidator: T::AccountId,
 		borrower: T::AccountId,
 		liquidation_asset_id: AssetIdOf<T>,
 		repay_amount: BalanceOf<T>,
 		collateral_asset_id: AssetIdOf<T>,
+		liquidate_incentive: Rate,
 	) -> DispatchResult {
-		Self::ensure_active_market(liquidation_asset_id)?;
+		Self::ensure_market_action_allowed(liquidation_asset_id, MarketAction::Liquidate)?;
 		Self::ensure_active_market(collateral_asset_id)?;
 
 		let market = Self::market(liquidation_asset_id)?;
@@ -1706,7 +3075,7 @@ impl<T: Config> Pallet<T> {
 		let collateral_value = Self::get_asset_value(collateral_asset_id, borrower_deposit_amount)?;
 		// liquidate_value contains the incentive of liquidator and the punishment of the borrower
 		let liquidate_value = Self::get_asset_value(liquidation_asset_id, repay_amount)?
-			.checked_mul(&market.liquidate_incentive)
+			.checked_mul(&liquidate_incentive)
 			.ok_or(ArithmeticError::Overflow)?;
 
 		if collateral_value < liquidate_value {
@@ -1738,8 +3107,384 @@ impl<T: Config> Pallet<T> {
 			repay_amount,
 			real_collateral_underlying_amount,
 			&market,
+			liquidate_incentive,
+		)?;
+
+		// Write off the remaining debt once the collaterals are seized. The debts in the other
+		// markets are written off through `write_off_bad_debt`.
+		if !Self::has_collateral(&borrower)?
+			&& Self::has_shortfall(&borrower)?
+			&& !Self::current_borrow_balance(&borrower, liquidation_asset_id)?.is_zero()
+		{
+			Self::seize_dust_collateral(&borrower)?;
+			Self::do_write_off_bad_debt(&borrower, liquidation_asset_id)?;
+		}
+
+		Ok(())
+	}
+
+	/// Returns whether the borrow of `borrower` in `liquidation_asset_id` can be liquidated
+	fn is_liquidatable(
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+	) -> Result<bool, DispatchError> {
+		if Self::current_borrow_balance(borrower, liquidation_asset_id)?.is_zero() {
+			return Ok(false);
+		}
+		let market = Self::market(liquidation_asset_id)?;
+		Ok(
+			Self::liquidate_borrow_allowed(borrower, liquidation_asset_id, Zero::zero(), &market)
+				.is_ok(),
+		)
+	}
+
+	/// Returns whether a liquidation auction can no longer be bid on, either because the
+	/// borrower is no longer liquidatable or because the auction config of the market has been
+	/// changed or removed since the auction was opened
+	fn is_liquidation_auction_stale(
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+		auction: &LiquidationAuction,
+	) -> Result<bool, DispatchError> {
+		if LiquidationAuctionConfigs::<T>::get(liquidation_asset_id) != Some(auction.config) {
+			return Ok(true);
+		}
+		Ok(!Self::is_liquidatable(borrower, liquidation_asset_id)?)
+	}
+
+	/// Returns whether `account` still has collateral worth more than dust
+	fn has_collateral(account: &T::AccountId) -> Result<bool, DispatchError> {
+		let mut collateral_value = FixedU128::zero();
+		for asset_id in Markets::<T>::iter_keys() {
+			let deposits = AccountDeposits::<T>::get(asset_id, account);
+			if !deposits.is_collateral || deposits.voucher_balance.is_zero() {
+				continue;
+			}
+			let amount = Self::exchange_rate_stored(asset_id)?
+				.checked_mul_int(deposits.voucher_balance)
+				.ok_or(ArithmeticError::Overflow)?;
+			collateral_value = collateral_value
+				.checked_add(&Self::get_asset_value(asset_id, amount)?)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+
+		Ok(collateral_value.into_inner() > T::DustCollateralValue::get())
+	}
+
+	/// Returns whether the borrows of `account` exceed its liquidation threshold value
+	fn has_shortfall(account: &T::AccountId) -> Result<bool, DispatchError> {
+		let (_, shortfall, _, _) = Self::get_account_liquidation_threshold_liquidity(account)?;
+		Ok(!shortfall.is_zero())
+	}
+
+	/// Adds the collaterals of `borrower`, worth no more than dust, to the market reserves.
+	///
+	/// The vouchers are burnt while the underlying stays in the pallet account, so the exchange
+	/// rate of the markets is unchanged.
+	#[require_transactional]
+	fn seize_dust_collateral(borrower: &T::AccountId) -> DispatchResult {
+		for asset_id in Markets::<T>::iter_keys() {
+			let deposits = AccountDeposits::<T>::get(asset_id, borrower);
+			if !deposits.is_collateral || deposits.voucher_balance.is_zero() {
+				continue;
+			}
+			Self::update_reward_supply_index(asset_id)?;
+			Self::distribute_supplier_reward(asset_id, borrower)?;
+
+			let amount = Self::calc_underlying_amount(
+				deposits.voucher_balance,
+				Self::exchange_rate_stored(asset_id)?,
+			)?;
+			AccountDeposits::<T>::remove(asset_id, borrower);
+			Self::release_isolated_collateral(borrower, asset_id);
+			TotalSupply::<T>::try_mutate(asset_id, |total_supply| -> DispatchResult {
+				*total_supply = total_supply
+					.checked_sub(deposits.voucher_balance)
+					.ok_or(ArithmeticError::Underflow)?;
+				Ok(())
+			})?;
+			TotalReserves::<T>::try_mutate(asset_id, |total_reserves| -> DispatchResult {
+				*total_reserves = total_reserves
+					.checked_add(amount)
+					.ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::<T>::DustCollateralSeized {
+				borrower: borrower.clone(),
+				asset_id,
+				amount,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Returns the outstanding debts of `account` if it is in shortfall with no collateral left.
+	pub fn get_bad_debt(
+		account: &T::AccountId,
+	) -> Result<Vec<(AssetIdOf<T>, BalanceOf<T>)>, DispatchError> {
+		if Self::has_collateral(account)? || !Self::has_shortfall(account)? {
+			return Ok(Vec::new());
+		}
+		let mut bad_debt = Vec::new();
+		for (asset_id, _) in Self::active_markets() {
+			let account_borrows = Self::current_borrow_balance(account, asset_id)?;
+			if !account_borrows.is_zero() {
+				bad_debt.push((asset_id, account_borrows));
+			}
+		}
+
+		Ok(bad_debt)
+	}
+
+	/// Returns the positions of `account` in the markets it supplied to or borrowed from.
+	///
+	/// Balances include the interest accrued up to now, while the health factor and the
+	/// borrowable and withdrawable amounts follow the checks of the corresponding extrinsics.
+	pub fn get_account_position(
+		account: &T::AccountId,
+	) -> Result<AccountPosition<AssetIdOf<T>, BalanceOf<T>>, DispatchError> {
+		let mut markets = Vec::new();
+		for (asset_id, market) in Markets::<T>::iter() {
+			let deposits = AccountDeposits::<T>::get(asset_id, account);
+			let snapshot = AccountBorrows::<T>::get(asset_id, account);
+			if deposits.voucher_balance.is_zero() && snapshot.principal.is_zero() {
+				continue;
+			}
+
+			let (_, _, exchange_rate, _, _, _, borrow_index) = Self::get_market_status(asset_id)?;
+			let supplied = Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
+			let borrowed = if snapshot.principal.is_zero() || snapshot.borrow_index.is_zero() {
+				Zero::zero()
+			} else {
+				borrow_index
+					.checked_div(&snapshot.borrow_index)
+					.and_then(|r| r.checked_mul_int(snapshot.principal))
+					.ok_or(ArithmeticError::Overflow)?
+			};
+			let account_earned = AccountEarned::<T>::get(asset_id, account);
+			let earned = exchange_rate
+				.saturating_sub(account_earned.exchange_rate_prior)
+				.checked_mul_int(deposits.voucher_balance)
+				.and_then(|r| r.checked_add(account_earned.total_earned_prior))
+				.ok_or(ArithmeticError::Overflow)?;
+			let (max_borrowable, max_withdrawable) = if market.state == MarketState::Active {
+				(
+					Self::max_borrowable(account, asset_id, &market)?,
+					Self::max_withdrawable(account, asset_id, &market, supplied)?,
+				)
+			} else {
+				(Zero::zero(), Zero::zero())
+			};
+
+			markets.push(MarketPosition {
+				asset_id,
+				supplied,
+				borrowed,
+				is_collateral: deposits.is_collateral,
+				earned,
+				max_borrowable,
+				max_withdrawable,
+			});
+		}
+
+		let total_borrow_value = Self::total_borrowed_value(account)?;
+		let health_factor = if total_borrow_value.is_zero() {
+			None
+		} else {
+			Some(
+				Self::total_liquidation_threshold_value(account)?
+					.checked_div(&total_borrow_value)
+					.ok_or(ArithmeticError::Overflow)?,
+			)
+		};
+
+		Ok(AccountPosition {
+			health_factor,
+			reward_accrued: RewardAccrued::<T>::get(account),
+			markets,
+		})
+	}
+
+	/// Returns the amount of `asset_id` that `account` is able to borrow.
+	fn max_borrowable(
+		account: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		market: &Market<BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		if !MarketBond::<T>::contains_key(asset_id)
+			|| Self::ensure_borrow_mode_allowed(asset_id, account, FixedU128::zero()).is_err()
+		{
+			return Ok(Zero::zero());
+		}
+
+		let (total_liquidity, _, lf_liquidity, _) = Self::get_account_liquidity(account)?;
+		let mut borrowable_value = if asset_id == T::LiquidationFreeAssetId::get() {
+			max(total_liquidity, lf_liquidity)
+		} else {
+			total_liquidity.saturating_sub(lf_liquidity)
+		};
+		let (bonded_collateral_value, bonded_borrow_value) =
+			Self::market_bond_values(asset_id, account)?;
+		borrowable_value =
+			borrowable_value.min(bonded_collateral_value.saturating_sub(bonded_borrow_value));
+		if let Some(collateral_asset_id) = Self::isolated_collateral(account) {
+			let debt_ceiling = IsolationModes::<T>::get(collateral_asset_id)
+				.map(|isolation_mode| isolation_mode.debt_ceiling)
+				.unwrap_or_default();
+			let debt_headroom = FixedU128::from_inner(debt_ceiling)
+				.saturating_sub(Self::isolation_mode_debt_value(collateral_asset_id)?);
+			borrowable_value = borrowable_value.min(debt_headroom);
+		}
+
+		let borrowable_amount = borrowable_value
+			.checked_div(&Self::get_price(asset_id)?)
+			.ok_or(ArithmeticError::Overflow)?
+			.into_inner();
+		let reducible_cash =
+			Self::get_total_cash(asset_id).saturating_sub(TotalReserves::<T>::get(asset_id));
+		let borrow_cap_headroom = market
+			.borrow_cap
+			.saturating_sub(TotalBorrows::<T>::get(asset_id));
+
+		Ok(borrowable_amount
+			.min(reducible_cash)
+			.min(borrow_cap_headroom))
+	}
+
+	/// Returns the underlying amount of `asset_id` that `account` is able to redeem.
+	fn max_withdrawable(
+		account: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		market: &Market<BalanceOf<T>>,
+		supplied: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let reducible_cash =
+			Self::get_total_cash(asset_id).saturating_sub(TotalReserves::<T>::get(asset_id));
+		let withdrawable_amount = supplied.min(reducible_cash);
+		let (collateral_factor, _) = Self::collateral_factors(account, asset_id, market);
+		if !AccountDeposits::<T>::get(asset_id, account).is_collateral
+			|| collateral_factor.is_zero()
+		{
+			return Ok(withdrawable_amount);
+		}
+
+		let (total_liquidity, _, lf_liquidity, _) = Self::get_account_liquidity(account)?;
+		let redeemable_value = if LiquidationFreeCollaterals::<T>::get().contains(&asset_id) {
+			max(total_liquidity, lf_liquidity)
+		} else {
+			total_liquidity.saturating_sub(lf_liquidity)
+		};
+		// Only the collateral factor of the redeemed amount counts against the liquidity
+		let redeemable_amount = redeemable_value
+			.checked_div(&Self::get_price(asset_id)?)
+			.and_then(|r| r.checked_div(&collateral_factor.into()))
+			.ok_or(ArithmeticError::Overflow)?
+			.into_inner();
+
+		Ok(withdrawable_amount.min(redeemable_amount))
+	}
+
+	/// Writes off the debt of `borrower` in `asset_id`.
+	///
+	/// The debt is covered by the market reserves first, then by the backstop account. The rest
+	/// is socialized through the exchange rate, as far as the exchange rate stays valid.
+	#[require_transactional]
+	fn do_write_off_bad_debt(borrower: &T::AccountId, asset_id: AssetIdOf<T>) -> DispatchResult {
+		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
+		ensure!(!account_borrows.is_zero(), Error::<T>::NoBadDebt);
+
+		Self::update_reward_borrow_index(asset_id)?;
+		Self::distribute_borrower_reward(asset_id, borrower)?;
+
+		let mut remaining = account_borrows;
+
+		// 1. write off against the reserves
+		let total_reserves = TotalReserves::<T>::get(asset_id);
+		let reserves_amount = remaining.min(total_reserves);
+		if !reserves_amount.is_zero() {
+			let total_reserves_new = total_reserves.saturating_sub(reserves_amount);
+			TotalReserves::<T>::insert(asset_id, total_reserves_new);
+			remaining = remaining.saturating_sub(reserves_amount);
+			Self::deposit_event(Event::<T>::BadDebtCoveredByReserves {
+				borrower: borrower.clone(),
+				asset_id,
+				amount: reserves_amount,
+				total_reserves: total_reserves_new,
+			});
+		}
+
+		// 2. draw on the backstop account
+		if let Some(backstop) = BackstopAccount::<T>::get().filter(|_| !remaining.is_zero()) {
+			let backstop_amount = remaining.min(T::Assets::reducible_balance(
+				asset_id,
+				&backstop,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			));
+			if !backstop_amount.is_zero() {
+				T::Assets::transfer(
+					asset_id,
+					&backstop,
+					&Self::account_id(),
+					backstop_amount,
+					Preservation::Expendable,
+				)?;
+				remaining = remaining.saturating_sub(backstop_amount);
+				Self::deposit_event(Event::<T>::BadDebtCoveredByBackstop {
+					borrower: borrower.clone(),
+					asset_id,
+					backstop,
+					amount: backstop_amount,
+				});
+			}
+		}
+
+		// 3. socialize the rest, without pushing the exchange rate under its lower bound
+		let covered_amount = account_borrows.saturating_sub(remaining);
+		let total_borrows = TotalBorrows::<T>::get(asset_id).saturating_sub(covered_amount);
+		let total_supply = TotalSupply::<T>::get(asset_id);
+		let socialized_amount = if total_supply.is_zero() {
+			remaining
+		} else {
+			let underlying_value = Self::get_accounted_cash(asset_id)
+				.saturating_add(total_borrows)
+				.saturating_sub(TotalReserves::<T>::get(asset_id));
+			let min_underlying_value =
+				Rate::from_inner(MIN_EXCHANGE_RATE).saturating_mul_int(total_supply);
+			remaining.min(underlying_value.saturating_sub(min_underlying_value))
+		};
+
+		// NOTE : total_borrows can be less than account_borrows, see `do_repay_borrow_with_amount`
+		TotalBorrows::<T>::insert(asset_id, total_borrows.saturating_sub(socialized_amount));
+		let account_borrows_new = remaining.saturating_sub(socialized_amount);
+		AccountBorrows::<T>::insert(
+			asset_id,
+			borrower,
+			BorrowSnapshot {
+				principal: account_borrows_new,
+				borrow_index: BorrowIndex::<T>::get(asset_id),
+			},
+		);
+		Self::reduce_delegated_borrows(borrower, asset_id, account_borrows, account_borrows_new)?;
+		Self::update_isolation_mode_debt(
+			borrower,
+			asset_id,
+			covered_amount.saturating_add(socialized_amount),
+			false,
 		)?;
 
+		if !socialized_amount.is_zero() {
+			let exchange_rate = Self::exchange_rate_stored(asset_id)?;
+			ExchangeRate::<T>::insert(asset_id, exchange_rate);
+			Self::deposit_event(Event::<T>::BadDebtSocialized {
+				borrower: borrower.clone(),
+				asset_id,
+				amount: socialized_amount,
+				exchange_rate,
+			});
+		}
+
 		Ok(())
 	}
 
@@ -1752,6 +3497,7 @@ impl<T: Config> Pallet<T> {
 		repay_amount: BalanceOf<T>,
 		collateral_underlying_amount: BalanceOf<T>,
 		market: &Market<BalanceOf<T>>,
+		liquidate_incentive: Rate,
 	) -> DispatchResult {
 		log::trace!(
 			target: "lend-market::liquidated_transfer",
@@ -1797,6 +3543,13 @@ impl<T: Config> Pallet<T> {
 			},
 		);
 		TotalBorrows::<T>::insert(liquidation_asset_id, total_borrows_new);
+		Self::update_isolation_mode_debt(borrower, liquidation_asset_id, repay_amount, false)?;
+		Self::reduce_delegated_borrows(
+			borrower,
+			liquidation_asset_id,
+			account_borrows,
+			account_borrows_new,
+		)?;
 
 		// update supply index before modify supply balance.
 		Self::update_reward_supply_index(collateral_asset_id)?;
@@ -1824,7 +3577,7 @@ impl<T: Config> Pallet<T> {
 		)?;
 		let incentive_reserved_amount = market.liquidate_incentive_reserved_factor.mul_floor(
 			FixedU128::from_inner(collateral_amount)
-				.checked_div(&market.liquidate_incentive)
+				.checked_div(&liquidate_incentive)
 				.map(|r| r.into_inner())
 				.ok_or(ArithmeticError::Underflow)?,
 		);
@@ -1873,6 +3626,20 @@ impl<T: Config> Pallet<T> {
 			.ok_or_else(|| Error::<T>::MarketNotActivated.into())
 	}
 
+	/// Ensures the market is active and `action` is not paused in it.
+	fn ensure_market_action_allowed(
+		asset_id: AssetIdOf<T>,
+		action: MarketAction,
+	) -> Result<Market<BalanceOf<T>>, DispatchError> {
+		let market = Self::ensure_active_market(asset_id)?;
+		ensure!(
+			!PausedMarketActions::<T>::get(asset_id, action),
+			Error::<T>::MarketActionPaused
+		);
+
+		Ok(market)
+	}
+
 	/// Ensure market is enough to supply `amount` asset.
 	fn ensure_under_supply_cap(asset_id: AssetIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
 		let market = Self::market(asset_id)?;
@@ -1962,6 +3729,26 @@ impl<T: Config> Pallet<T> {
 		account: &T::AccountId,
 		reduce_amount: FixedU128,
 	) -> DispatchResult {
+		let (total_collateral_value, total_borrow_value) =
+			Self::market_bond_values(borrow_asset_id, account)?;
+
+		let total_liquidity = total_collateral_value
+			.checked_sub(&total_borrow_value)
+			.ok_or(ArithmeticError::Underflow)?;
+
+		if total_liquidity >= reduce_amount {
+			return Ok(());
+		}
+
+		Err(Error::<T>::InsufficientLiquidity.into())
+	}
+
+	// Returns the value of the collaterals bonded to `borrow_asset_id` and the value borrowed
+	// of `borrow_asset_id` by `account`.
+	fn market_bond_values(
+		borrow_asset_id: AssetIdOf<T>,
+		account: &T::AccountId,
+	) -> Result<(FixedU128, FixedU128), DispatchError> {
 		let collateral_asset_ids = MarketBond::<T>::try_get(borrow_asset_id)
 			.map_err(|_err| Error::<T>::MarketBondDoesNotExist)?;
 
@@ -1975,15 +3762,7 @@ impl<T: Config> Pallet<T> {
 				.ok_or(ArithmeticError::Overflow)?;
 		}
 
-		let total_liquidity = total_collateral_value
-			.checked_sub(&total_borrow_value)
-			.ok_or(ArithmeticError::Underflow)?;
-
-		if total_liquidity >= reduce_amount {
-			return Ok(());
-		}
-
-		Err(Error::<T>::InsufficientLiquidity.into())
+		Ok((total_collateral_value, total_borrow_value))
 	}
 
 	pub fn calc_underlying_amount(
@@ -2014,6 +3793,12 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Returns the cash of the market including the outstanding flash loans, which are owed
+	/// back to the market by the end of the extrinsic.
+	fn get_accounted_cash(asset_id: AssetIdOf<T>) -> BalanceOf<T> {
+		Self::get_total_cash(asset_id).saturating_add(FlashLoans::<T>::get(asset_id))
+	}
+
 	// Returns the uniform format price.
 	// Formula: `price = oracle_price * 10.pow(18 - asset_decimal)`
 	// This particular price makes it easy to calculate the value ,
@@ -2110,11 +3895,41 @@ impl<T: Config> Pallet<T> {
 		Ok(T::AccountId::decode(&mut &entropy[..]).map_err(|_| Error::<T>::CodecError)?)
 	}
 
+	/// Adds `add_amount` of `asset_id` from `payer` to the reserves of its market.
+	pub fn do_add_reserves(
+		payer: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		add_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::ensure_active_market(asset_id)?;
+
+		T::Assets::transfer(
+			asset_id,
+			payer,
+			&Self::account_id(),
+			add_amount,
+			Preservation::Expendable,
+		)?;
+		let total_reserves = TotalReserves::<T>::get(asset_id);
+		let total_reserves_new = total_reserves
+			.checked_add(add_amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		TotalReserves::<T>::insert(asset_id, total_reserves_new);
+
+		Self::deposit_event(Event::<T>::ReservesAdded(
+			payer.clone(),
+			asset_id,
+			add_amount,
+			total_reserves_new,
+		));
+		Ok(())
+	}
+
 	pub fn do_redeem_all(
 		who: &AccountIdOf<T>,
 		asset_id: AssetIdOf<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
-		Self::ensure_active_market(asset_id)?;
+		Self::ensure_market_action_allowed(asset_id, MarketAction::Redeem)?;
 		Self::accrue_interest(asset_id)?;
 		let exchange_rate = Self::exchange_rate_stored(asset_id)?;
 		Self::update_earned_stored(&who, asset_id, exchange_rate)?;
@@ -2132,7 +3947,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		amount: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
 		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
-		Self::ensure_active_market(asset_id)?;
+		Self::ensure_market_action_allowed(asset_id, MarketAction::Mint)?;
 		Self::ensure_under_supply_cap(asset_id, amount)?;
 
 		Self::accrue_interest(asset_id)?;
@@ -2176,42 +3991,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		asset_id: AssetIdOf<T>,
 		amount: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
-		Self::ensure_active_market(asset_id)?;
-
-		Self::accrue_interest(asset_id)?;
-		Self::borrow_allowed_for_market_bond(asset_id, borrower, amount)?;
-		Self::borrow_allowed(asset_id, borrower, amount)?;
-
-		// update borrow index after accrue interest.
-		Self::update_reward_borrow_index(asset_id)?;
-		Self::distribute_borrower_reward(asset_id, borrower)?;
-
-		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
-		let account_borrows_new = account_borrows
-			.checked_add(amount)
-			.ok_or(ArithmeticError::Overflow)?;
-		let total_borrows = TotalBorrows::<T>::get(asset_id);
-		let total_borrows_new = total_borrows
-			.checked_add(amount)
-			.ok_or(ArithmeticError::Overflow)?;
-		AccountBorrows::<T>::insert(
-			asset_id,
-			borrower,
-			BorrowSnapshot {
-				principal: account_borrows_new,
-				borrow_index: BorrowIndex::<T>::get(asset_id),
-			},
-		);
-		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
-		T::Assets::transfer(
-			asset_id,
-			&Self::account_id(),
-			borrower,
-			amount,
-			Preservation::Expendable,
-		)?;
-		Self::deposit_event(Event::<T>::Borrowed(borrower.clone(), asset_id, amount));
-		Ok(())
+		Self::do_borrow_to(borrower, borrower, asset_id, amount)
 	}
 
 	fn do_collateral_asset(
@@ -2219,7 +3999,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		asset_id: AssetIdOf<T>,
 		enable: bool,
 	) -> Result<(), DispatchError> {
-		Self::ensure_active_market(asset_id)?;
+		Self::ensure_market_action_allowed(asset_id, MarketAction::CollateralToggle)?;
 		ensure!(
 			AccountDeposits::<T>::contains_key(asset_id, supplier),
 			Error::<T>::NoDeposit
@@ -2227,8 +4007,23 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		let mut deposits = AccountDeposits::<T>::get(asset_id, supplier);
 		// turn on the collateral button
 		if enable {
+			if IsolationModes::<T>::contains_key(asset_id) {
+				ensure!(
+					!Markets::<T>::iter_keys().any(|id| id != asset_id
+						&& AccountDeposits::<T>::get(id, supplier).is_collateral),
+					Error::<T>::IsolatedCollateralMixed
+				);
+			} else {
+				ensure!(
+					Self::isolated_collateral(supplier).is_none(),
+					Error::<T>::IsolatedCollateralMixed
+				);
+			}
 			deposits.is_collateral = true;
 			AccountDeposits::<T>::insert(asset_id, supplier, deposits);
+			if IsolationModes::<T>::contains_key(asset_id) {
+				AccountIsolatedCollateral::<T>::insert(supplier, asset_id);
+			}
 			Self::deposit_event(Event::<T>::CollateralAssetAdded(supplier.clone(), asset_id));
 			return Ok(());
 		}
@@ -2252,6 +4047,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		}
 		deposits.is_collateral = false;
 		AccountDeposits::<T>::insert(asset_id, supplier, deposits);
+		Self::release_isolated_collateral(supplier, asset_id);
 
 		Self::deposit_event(Event::<T>::CollateralAssetRemoved(
 			supplier.clone(),
@@ -2266,10 +4062,16 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		asset_id: AssetIdOf<T>,
 		amount: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
-		Self::ensure_active_market(asset_id)?;
+		Self::ensure_market_action_allowed(asset_id, MarketAction::Repay)?;
 		Self::accrue_interest(asset_id)?;
 		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
-		Self::do_repay_borrow_with_amount(borrower, asset_id, account_borrows, amount)?;
+		Self::do_repay_borrow_with_amount(borrower, borrower, asset_id, account_borrows, amount)?;
+		Self::reduce_delegated_borrows(
+			borrower,
+			asset_id,
+			account_borrows,
+			account_borrows.saturating_sub(amount),
+		)?;
 		Self::deposit_event(Event::<T>::RepaidBorrow(borrower.clone(), asset_id, amount));
 		Ok(())
 	}
@@ -2279,7 +4081,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		asset_id: AssetIdOf<T>,
 		amount: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
-		Self::ensure_active_market(asset_id)?;
+		Self::ensure_market_action_allowed(asset_id, MarketAction::Redeem)?;
 		Self::accrue_interest(asset_id)?;
 		let exchange_rate = Self::exchange_rate_stored(asset_id)?;
 		Self::update_earned_stored(supplier, asset_id, exchange_rate)?;
@@ -2333,6 +4135,9 @@ impl<T: Config> LendMarketMarketDataProvider<AssetIdOf<T>, BalanceOf<T>> for Pal
 		if let Ok(market) = Self::market(asset_id) {
 			let rate = match market.rate_model {
 				InterestRateModel::Jump(jump) => Some(jump.full_rate),
+				InterestRateModel::Adaptive(adaptive) => adaptive
+					.max_rate_at_target
+					.checked_mul(&adaptive.curve_steepness),
 				_ => None,
 			};
 			return rate;
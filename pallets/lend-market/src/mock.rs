@@ -29,14 +29,18 @@ use frame_support::{
 	traits::{AsEnsureOriginWithArg, Nothing, SortedMembers},
 };
 use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
-use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
-use sp_runtime::{traits::IdentityLookup, AccountId32};
-use sp_std::vec::Vec;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended, MultiCurrency};
+use sp_runtime::{
+	traits::{IdentityLookup, UniqueSaturatedInto},
+	AccountId32,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 use std::{
 	cell::RefCell,
 	collections::HashMap,
 	hash::{Hash, Hasher},
 };
+use zenlink_protocol::{AssetBalance, LocalAssetHandler, PairLpGenerate, ZenlinkMultiAssets};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -51,6 +55,7 @@ construct_runtime!(
 		TimestampPallet: pallet_timestamp,
 		Assets: pallet_assets,
 		Prices: pallet_prices,
+		ZenlinkProtocol: zenlink_protocol,
 	}
 );
 
@@ -315,10 +320,95 @@ impl pallet_prices::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const SelfParaId: u32 = 2001;
+}
+
+impl zenlink_protocol::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkPalletId;
+	type SelfParaId = SelfParaId;
+	type TargetChains = ();
+	type WeightInfo = ();
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PairLpGenerate<Self>;
+}
+
+type MultiAssets = ZenlinkMultiAssets<ZenlinkProtocol, Balances, LocalAssetAdaptor<Currencies>>;
+
+// Below is the implementation of tokens manipulation functions other than native token.
+pub struct LocalAssetAdaptor<Local>(PhantomData<Local>);
+
+impl<Local, AccountId> LocalAssetHandler<AccountId> for LocalAssetAdaptor<Local>
+where
+	Local: MultiCurrency<AccountId, CurrencyId = CurrencyId>,
+{
+	fn local_balance_of(asset_id: ZenlinkAssetId, who: &AccountId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::free_balance(currency_id, &who).saturated_into()
+	}
+
+	fn local_total_supply(asset_id: ZenlinkAssetId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::total_issuance(currency_id).saturated_into()
+	}
+
+	fn local_is_exists(asset_id: ZenlinkAssetId) -> bool {
+		let rs: Result<CurrencyId, _> = asset_id.try_into();
+		rs.is_ok()
+	}
+
+	fn local_transfer(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		target: &AccountId,
+		amount: AssetBalance,
+	) -> DispatchResult {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::transfer(
+			currency_id,
+			&origin,
+			&target,
+			amount.unique_saturated_into(),
+		)
+	}
+
+	fn local_deposit(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::deposit(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+
+	fn local_withdraw(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::withdraw(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+}
+
+pub struct ParachainId;
+impl Get<ParaId> for ParachainId {
+	fn get() -> ParaId {
+		ParaId::from(2001)
+	}
+}
+
 parameter_types! {
 	pub const RewardAssetId: CurrencyId = BNC;
 	pub const LiquidationFreeAssetId: CurrencyId = DOT;
 	pub const MaxLengthLimit: u32 = 500;
+	pub const DustCollateralValue: Balance = 1_000_000_000_000;
+	pub const FlashLoanFee: Ratio = Ratio::from_parts(900);
+	pub const MaxFlashLoanCalls: u32 = 10;
 }
 
 impl Config for Test {
@@ -327,6 +417,7 @@ impl Config for Test {
 	type PalletId = LendMarketPalletId;
 	type ReserveOrigin = EnsureRoot<AccountId>;
 	type UpdateOrigin = EnsureRoot<AccountId>;
+	type ControlOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
 	type UnixTime = TimestampPallet;
 	type Assets = Currencies;
@@ -334,6 +425,13 @@ impl Config for Test {
 	type LiquidationFreeAssetId = LiquidationFreeAssetId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanFee = FlashLoanFee;
+	type MaxFlashLoanCalls = MaxFlashLoanCalls;
+	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = ();
+	type ParachainId = ParachainId;
+	type DustCollateralValue = DustCollateralValue;
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
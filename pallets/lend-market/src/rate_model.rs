@@ -16,9 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use bifrost_primitives::{Rate, Ratio};
+use bifrost_primitives::{Rate, Ratio, Timestamp, SECONDS_PER_YEAR};
 use scale_info::TypeInfo;
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, Saturating};
+use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating};
 
 use crate::*;
 
@@ -28,6 +28,7 @@ use crate::*;
 pub enum InterestRateModel {
 	Jump(JumpModel),
 	Curve(CurveModel),
+	Adaptive(AdaptiveModel),
 }
 
 impl Default for InterestRateModel {
@@ -64,14 +65,21 @@ impl InterestRateModel {
 		match self {
 			Self::Jump(jump) => jump.check_model(),
 			Self::Curve(curve) => curve.check_model(),
+			Self::Adaptive(adaptive) => adaptive.check_model(),
 		}
 	}
 
 	/// Calculates the current borrow interest rate
+	///
+	/// Adaptive models are evaluated at their initial rate at target, use
+	/// [`AdaptiveModel::get_borrow_rate`] to take their current state into account.
 	pub fn get_borrow_rate(&self, utilization: Ratio) -> Option<Rate> {
 		match self {
 			Self::Jump(jump) => jump.get_borrow_rate(utilization),
 			Self::Curve(curve) => curve.get_borrow_rate(utilization),
+			Self::Adaptive(adaptive) => adaptive
+				.get_borrow_rate(utilization, adaptive.initial_rate_at_target, 0)
+				.map(|(borrow_rate, _)| borrow_rate),
 		}
 	}
 
@@ -192,6 +200,170 @@ impl CurveModel {
 	}
 }
 
+/// The adaptive interest rate model
+///
+/// The borrow rate follows a curve around the rate at target utilization, and the rate at target
+/// itself keeps moving towards the target utilization over time: it grows exponentially while the
+/// utilization is above the target, and decays while it is below, at a bounded speed.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct AdaptiveModel {
+	/// The utilization the rate at target is adjusted towards
+	pub target_utilization: Ratio,
+	/// The rate at target when the model is first used
+	pub initial_rate_at_target: Rate,
+	/// The lower bound of the rate at target
+	pub min_rate_at_target: Rate,
+	/// The upper bound of the rate at target
+	pub max_rate_at_target: Rate,
+	/// How fast the rate at target moves per year, at 100% or 0% utilization
+	pub adjustment_speed: Rate,
+	/// The borrow rate at 100% utilization is `curve_steepness` times the rate at target, and
+	/// the borrow rate at 0% utilization is the rate at target divided by `curve_steepness`
+	pub curve_steepness: Rate,
+}
+
+impl AdaptiveModel {
+	pub const MAX_RATE_AT_TARGET: Rate = Rate::from_inner(Rate::DIV * 2); // 200%
+	pub const MAX_ADJUSTMENT_SPEED: Rate = Rate::from_inner(Rate::DIV * 100); // 100 per year
+	pub const MAX_CURVE_STEEPNESS: Rate = Rate::from_inner(Rate::DIV * 10);
+	/// The rate at target moves by at most e^20 in a single accrual, far beyond any sane bounds
+	pub const MAX_LINEAR_ADAPTATION: Rate = Rate::from_inner(Rate::DIV * 20);
+
+	/// Check the adaptive model for sanity
+	pub fn check_model(&self) -> bool {
+		if self.target_utilization.is_zero() || self.target_utilization == Ratio::one() {
+			return false;
+		}
+		if self.min_rate_at_target > self.initial_rate_at_target
+			|| self.initial_rate_at_target > self.max_rate_at_target
+			|| self.max_rate_at_target > Self::MAX_RATE_AT_TARGET
+		{
+			return false;
+		}
+		if self.adjustment_speed > Self::MAX_ADJUSTMENT_SPEED {
+			return false;
+		}
+
+		self.curve_steepness >= Rate::one() && self.curve_steepness <= Self::MAX_CURVE_STEEPNESS
+	}
+
+	/// Calculates the borrow interest rate of adaptive model
+	///
+	/// Returns the average borrow rate over the `elapsed` seconds since `rate_at_target` was
+	/// stored, and the rate at target at the end of the period.
+	pub fn get_borrow_rate(
+		&self,
+		utilization: Ratio,
+		rate_at_target: Rate,
+		elapsed: Timestamp,
+	) -> Option<(Rate, Rate)> {
+		// The normalized distance to the target utilization, within [0, 1]
+		let is_above_target = utilization > self.target_utilization;
+		let err: Rate = if is_above_target {
+			Rate::checked_from_rational(
+				(utilization - self.target_utilization).deconstruct(),
+				(Ratio::one() - self.target_utilization).deconstruct(),
+			)?
+		} else {
+			Rate::checked_from_rational(
+				(self.target_utilization - utilization).deconstruct(),
+				self.target_utilization.deconstruct(),
+			)?
+		};
+
+		// linear_adaptation = adjustment_speed * err * elapsed / SECONDS_PER_YEAR
+		let linear_adaptation = self
+			.adjustment_speed
+			.checked_mul(&err)?
+			.checked_mul(&Rate::saturating_from_integer(elapsed))?
+			.checked_div(&Rate::saturating_from_integer(SECONDS_PER_YEAR))?
+			.min(Self::MAX_LINEAR_ADAPTATION);
+
+		let start_rate_at_target = self.clamp_rate_at_target(rate_at_target);
+		let end_rate_at_target =
+			self.adapt_rate_at_target(start_rate_at_target, linear_adaptation, is_above_target)?;
+		let mid_rate_at_target = self.adapt_rate_at_target(
+			start_rate_at_target,
+			linear_adaptation.checked_div(&Rate::saturating_from_integer(2))?,
+			is_above_target,
+		)?;
+		// Trapezoidal approximation of the average rate at target over the period
+		let avg_rate_at_target = start_rate_at_target
+			.checked_add(&end_rate_at_target)?
+			.checked_add(&mid_rate_at_target.checked_mul(&Rate::saturating_from_integer(2))?)?
+			.checked_div(&Rate::saturating_from_integer(4))?;
+
+		Some((
+			self.curve(avg_rate_at_target, err, is_above_target)?,
+			end_rate_at_target,
+		))
+	}
+
+	fn clamp_rate_at_target(&self, rate_at_target: Rate) -> Rate {
+		rate_at_target
+			.max(self.min_rate_at_target)
+			.min(self.max_rate_at_target)
+	}
+
+	// rate_at_target * e^(±linear_adaptation), within the bounds of the model
+	fn adapt_rate_at_target(
+		&self,
+		rate_at_target: Rate,
+		linear_adaptation: Rate,
+		is_above_target: bool,
+	) -> Option<Rate> {
+		let growth = exp(linear_adaptation)?;
+		let rate_at_target = if is_above_target {
+			rate_at_target.saturating_mul(growth)
+		} else {
+			rate_at_target.checked_div(&growth)?
+		};
+
+		Some(self.clamp_rate_at_target(rate_at_target))
+	}
+
+	// Above the target: ((curve_steepness - 1) * err + 1) * rate_at_target
+	// Below the target: (1 - (1 - 1 / curve_steepness) * err) * rate_at_target
+	fn curve(&self, rate_at_target: Rate, err: Rate, is_above_target: bool) -> Option<Rate> {
+		let coefficient = if is_above_target {
+			self.curve_steepness
+				.checked_sub(&Rate::one())?
+				.checked_mul(&err)?
+				.checked_add(&Rate::one())?
+		} else {
+			let slope =
+				Rate::one().checked_sub(&Rate::one().checked_div(&self.curve_steepness)?)?;
+			Rate::one().checked_sub(&slope.checked_mul(&err)?)?
+		};
+
+		rate_at_target.checked_mul(&coefficient)
+	}
+}
+
+/// Approximates e^x, with x no larger than [`AdaptiveModel::MAX_LINEAR_ADAPTATION`].
+///
+/// e^x = (e^(x / 2^8))^(2^8), where e^(x / 2^8) is computed by its Taylor series.
+fn exp(x: Rate) -> Option<Rate> {
+	const SQUARINGS: u32 = 8;
+	const TERMS: u128 = 6;
+
+	let y = x.checked_div(&Rate::saturating_from_integer(1u128 << SQUARINGS))?;
+	let mut term = Rate::one();
+	let mut result = Rate::one();
+	for n in 1..=TERMS {
+		term = term
+			.checked_mul(&y)?
+			.checked_div(&Rate::saturating_from_integer(n))?;
+		result = result.checked_add(&term)?;
+	}
+	for _ in 0..SQUARINGS {
+		result = result.checked_mul(&result)?;
+	}
+
+	Some(result)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -275,4 +447,122 @@ mod tests {
 			Rate::from_inner(154217728000000000)
 		);
 	}
+
+	// Test adaptive model
+	fn adaptive_model() -> AdaptiveModel {
+		AdaptiveModel {
+			target_utilization: Ratio::from_percent(90),
+			initial_rate_at_target: Rate::saturating_from_rational(4, 100),
+			min_rate_at_target: Rate::saturating_from_rational(1, 1000),
+			max_rate_at_target: Rate::saturating_from_integer(2),
+			adjustment_speed: Rate::saturating_from_integer(50),
+			curve_steepness: Rate::saturating_from_integer(4),
+		}
+	}
+
+	#[test]
+	fn check_adaptive_model_works() {
+		assert!(adaptive_model().check_model());
+		assert!(!AdaptiveModel {
+			target_utilization: Ratio::zero(),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			target_utilization: Ratio::one(),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			initial_rate_at_target: Rate::saturating_from_rational(1, 10_000),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			max_rate_at_target: Rate::saturating_from_integer(3),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			curve_steepness: Rate::saturating_from_rational(1, 2),
+			..adaptive_model()
+		}
+		.check_model());
+	}
+
+	#[test]
+	fn exp_approximation_works() {
+		assert_eq!(exp(Rate::zero()).unwrap(), Rate::one());
+		let e = exp(Rate::one()).unwrap();
+		assert!(e > Rate::saturating_from_rational(2718, 1000));
+		assert!(e < Rate::saturating_from_rational(2719, 1000));
+		let e20 = exp(AdaptiveModel::MAX_LINEAR_ADAPTATION).unwrap();
+		assert!(e20 > Rate::saturating_from_integer(485_000_000));
+		assert!(e20 < Rate::saturating_from_integer(486_000_000));
+	}
+
+	#[test]
+	fn adaptive_model_follows_the_curve() {
+		let model = adaptive_model();
+		let rate_at_target = model.initial_rate_at_target;
+
+		let (rate, end_rate_at_target) = model
+			.get_borrow_rate(Ratio::from_percent(90), rate_at_target, 0)
+			.unwrap();
+		assert_eq!(rate, rate_at_target);
+		assert_eq!(end_rate_at_target, rate_at_target);
+
+		let (rate, _) = model
+			.get_borrow_rate(Ratio::one(), rate_at_target, 0)
+			.unwrap();
+		assert_eq!(rate, rate_at_target * model.curve_steepness);
+
+		let (rate, _) = model
+			.get_borrow_rate(Ratio::zero(), rate_at_target, 0)
+			.unwrap();
+		assert_eq!(rate, rate_at_target / model.curve_steepness);
+	}
+
+	#[test]
+	fn adaptive_rate_at_target_adjusts_over_time() {
+		let model = adaptive_model();
+		let rate_at_target = model.initial_rate_at_target;
+		let day = 24 * 60 * 60;
+
+		// Nothing changes at the target utilization
+		let (_, end) = model
+			.get_borrow_rate(Ratio::from_percent(90), rate_at_target, day)
+			.unwrap();
+		assert_eq!(end, rate_at_target);
+
+		// Above the target the rate at target grows, and the average rate lies in between
+		let (rate, end) = model
+			.get_borrow_rate(Ratio::one(), rate_at_target, day)
+			.unwrap();
+		assert!(end > rate_at_target);
+		assert!(rate > rate_at_target * model.curve_steepness);
+		assert!(rate < end * model.curve_steepness);
+		// e^(50 / 365) ~= 1.1468
+		assert!(end > Rate::saturating_from_rational(4587, 100_000));
+		assert!(end < Rate::saturating_from_rational(4588, 100_000));
+
+		// Below the target it decays
+		let (rate, end) = model
+			.get_borrow_rate(Ratio::zero(), rate_at_target, day)
+			.unwrap();
+		assert!(end < rate_at_target);
+		assert!(rate < rate_at_target / model.curve_steepness);
+		assert!(rate > end / model.curve_steepness);
+
+		// And is always kept within the bounds
+		let year = 365 * day;
+		let (_, end) = model
+			.get_borrow_rate(Ratio::one(), rate_at_target, year)
+			.unwrap();
+		assert_eq!(end, model.max_rate_at_target);
+		let (_, end) = model
+			.get_borrow_rate(Ratio::zero(), rate_at_target, year)
+			.unwrap();
+		assert_eq!(end, model.min_rate_at_target);
+	}
 }
@@ -16,7 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod account_position;
+mod bad_debt;
+mod credit_delegation;
+mod e_mode;
 mod edge_cases;
+mod flash_loan;
 mod interest_rate;
 mod lend_tokens;
 mod liquidate_borrow;
@@ -0,0 +1,112 @@
+use crate::{mock::*, tests::LendMarket, AccountEarned};
+use bifrost_primitives::SECONDS_PER_YEAR;
+use frame_support::assert_ok;
+use pallet_traits::MarketPosition;
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+fn market_position(account: &AccountId, asset_id: CurrencyId) -> MarketPosition<CurrencyId, u128> {
+	LendMarket::get_account_position(account)
+		.unwrap()
+		.markets
+		.into_iter()
+		.find(|position| position.asset_id == asset_id)
+		.unwrap()
+}
+
+fn init_positions() {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		KSM,
+		vec![DOT]
+	));
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		DOT,
+		unit(200)
+	));
+	assert_ok!(LendMarket::collateral_asset(
+		RuntimeOrigin::signed(ALICE),
+		DOT,
+		true
+	));
+	assert_ok!(LendMarket::borrow(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		unit(50)
+	));
+}
+
+#[test]
+fn account_position_works() {
+	new_test_ext().execute_with(|| {
+		init_positions();
+
+		let position = LendMarket::get_account_position(&ALICE).unwrap();
+		assert_eq!(position.markets.len(), 2);
+		assert_eq!(position.reward_accrued, 0);
+		// 200 * 55% / 50
+		assert_eq!(
+			position.health_factor,
+			Some(FixedU128::saturating_from_rational(22, 10))
+		);
+
+		// The liquidity of 200 * 50% - 50 allows redeeming 100 DOT
+		assert_eq!(
+			market_position(&ALICE, DOT),
+			MarketPosition {
+				asset_id: DOT,
+				supplied: unit(200),
+				borrowed: 0,
+				is_collateral: true,
+				earned: 0,
+				max_borrowable: 0,
+				max_withdrawable: unit(100),
+			}
+		);
+		assert_eq!(
+			market_position(&ALICE, KSM),
+			MarketPosition {
+				asset_id: KSM,
+				supplied: 0,
+				borrowed: unit(50),
+				is_collateral: false,
+				earned: 0,
+				max_borrowable: unit(50),
+				max_withdrawable: 0,
+			}
+		);
+
+		// Without borrows, the whole supply can be redeemed
+		let position = LendMarket::get_account_position(&BOB).unwrap();
+		assert_eq!(position.health_factor, None);
+		assert_eq!(market_position(&BOB, KSM).max_withdrawable, unit(150));
+		assert!(LendMarket::get_account_position(&CHARLIE)
+			.unwrap()
+			.markets
+			.is_empty());
+	})
+}
+
+#[test]
+fn account_position_includes_interest_not_yet_accrued() {
+	new_test_ext().execute_with(|| {
+		init_positions();
+
+		TimestampPallet::set_timestamp(6000 + SECONDS_PER_YEAR * 1000);
+		let borrowed = market_position(&ALICE, KSM).borrowed;
+		let supplied = market_position(&BOB, KSM).supplied;
+		let earned = market_position(&BOB, KSM).earned;
+		assert!(borrowed > unit(50));
+		assert!(supplied > unit(200));
+		assert_eq!(earned, supplied - unit(200));
+
+		assert_ok!(LendMarket::accrue_interest(KSM));
+		assert_eq!(
+			LendMarket::current_borrow_balance(&ALICE, KSM),
+			Ok(borrowed)
+		);
+		assert_eq!(AccountEarned::<Test>::get(KSM, BOB).total_earned_prior, 0);
+		assert_eq!(market_position(&BOB, KSM).supplied, supplied);
+	})
+}
@@ -0,0 +1,207 @@
+use crate::{
+	mock::*, tests::LendMarket, AccountDeposits, BackstopAccount, Config, Error, ExchangeRate,
+	TotalBorrows, TotalReserves, TotalSupply,
+};
+use bifrost_primitives::Rate;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles::{Inspect, Mutate},
+		tokens::Preservation,
+	},
+};
+use sp_runtime::{traits::Zero, FixedPointNumber};
+
+fn init_bad_debt() {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		DOT_U,
+		vec![KSM]
+	));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(DAVE),
+		DOT_U,
+		unit(200)
+	));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		unit(100)
+	));
+	assert_ok!(LendMarket::collateral_asset(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		true
+	));
+	assert_ok!(LendMarket::borrow(
+		RuntimeOrigin::signed(ALICE),
+		DOT_U,
+		unit(40)
+	));
+}
+
+#[test]
+fn write_off_bad_debt_requires_no_collateral() {
+	new_test_ext().execute_with(|| {
+		init_bad_debt();
+		assert_eq!(LendMarket::get_bad_debt(&ALICE), Ok(vec![]));
+		assert_noop!(
+			LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), ALICE, DOT_U),
+			Error::<Test>::CollateralNotExhausted
+		);
+		assert_noop!(
+			LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), DAVE, KSM),
+			Error::<Test>::NoBadDebt
+		);
+	})
+}
+
+#[test]
+fn dust_collateral_requires_shortfall() {
+	new_test_ext().execute_with(|| {
+		init_bad_debt();
+		// A healthy account borrowing against collateral worth less than the dust value
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(BOB),
+			KSM,
+			unit(1) / 2
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(BOB),
+			KSM,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(BOB),
+			DOT_U,
+			unit(1) / 10
+		));
+		assert_eq!(LendMarket::get_bad_debt(&BOB), Ok(vec![]));
+		assert_noop!(
+			LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), BOB, DOT_U),
+			Error::<Test>::InsufficientShortfall
+		);
+	})
+}
+
+#[test]
+fn dust_collateral_is_seized_on_write_off() {
+	new_test_ext().execute_with(|| {
+		init_bad_debt();
+		// Collateral worth less than the dust value is left
+		AccountDeposits::<Test>::mutate(KSM, ALICE, |deposits| deposits.voucher_balance = 1_000);
+		assert_eq!(
+			LendMarket::get_bad_debt(&ALICE),
+			Ok(vec![(DOT_U, unit(40))])
+		);
+		let dust_amount = LendMarket::calc_underlying_amount(
+			1_000,
+			LendMarket::exchange_rate_stored(KSM).unwrap(),
+		)
+		.unwrap();
+		let total_reserves = TotalReserves::<Test>::get(KSM);
+		let total_supply = TotalSupply::<Test>::get(KSM);
+
+		assert_ok!(LendMarket::write_off_bad_debt(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			DOT_U
+		));
+		assert!(!AccountDeposits::<Test>::contains_key(KSM, ALICE));
+		assert_eq!(
+			TotalReserves::<Test>::get(KSM),
+			total_reserves + dust_amount
+		);
+		assert_eq!(TotalSupply::<Test>::get(KSM), total_supply - 1_000);
+		assert_eq!(LendMarket::get_bad_debt(&ALICE), Ok(vec![]));
+	})
+}
+
+#[test]
+fn bad_debt_covered_by_reserves_then_socialized() {
+	new_test_ext().execute_with(|| {
+		init_bad_debt();
+		// Donate some cash so that the exchange rate has room to decrease
+		assert_ok!(<Test as Config>::Assets::transfer(
+			DOT_U,
+			&DAVE,
+			&LendMarket::account_id(),
+			unit(100),
+			Preservation::Expendable,
+		));
+		TotalReserves::<Test>::insert(DOT_U, unit(10));
+		// The collateral is gone
+		AccountDeposits::<Test>::remove(KSM, ALICE);
+		assert_eq!(
+			LendMarket::get_bad_debt(&ALICE),
+			Ok(vec![(DOT_U, unit(40))])
+		);
+
+		assert_ok!(LendMarket::write_off_bad_debt(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			DOT_U
+		));
+		assert!(TotalReserves::<Test>::get(DOT_U).is_zero());
+		assert!(TotalBorrows::<Test>::get(DOT_U).is_zero());
+		assert!(LendMarket::current_borrow_balance(&ALICE, DOT_U)
+			.unwrap()
+			.is_zero());
+		// (260 cash + 0 borrows - 0 reserves) / 10000 supply
+		assert_eq!(
+			ExchangeRate::<Test>::get(DOT_U),
+			Rate::saturating_from_rational(26, 1000)
+		);
+		assert_eq!(LendMarket::get_bad_debt(&ALICE), Ok(vec![]));
+	})
+}
+
+#[test]
+fn bad_debt_covered_by_backstop() {
+	new_test_ext().execute_with(|| {
+		init_bad_debt();
+		assert_ok!(<Test as Config>::Assets::transfer(
+			DOT_U,
+			&DAVE,
+			&CHARLIE,
+			unit(100),
+			Preservation::Expendable,
+		));
+		assert_ok!(LendMarket::set_backstop_account(
+			RuntimeOrigin::root(),
+			Some(CHARLIE)
+		));
+		assert_eq!(BackstopAccount::<Test>::get(), Some(CHARLIE));
+		let exchange_rate = LendMarket::exchange_rate_stored(DOT_U).unwrap();
+		AccountDeposits::<Test>::remove(KSM, ALICE);
+
+		assert_ok!(LendMarket::write_off_bad_debt(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			DOT_U
+		));
+		assert_eq!(<Test as Config>::Assets::balance(DOT_U, &CHARLIE), unit(60));
+		assert!(TotalBorrows::<Test>::get(DOT_U).is_zero());
+		assert_eq!(LendMarket::exchange_rate_stored(DOT_U), Ok(exchange_rate));
+	})
+}
+
+#[test]
+fn bad_debt_socialization_keeps_exchange_rate_valid() {
+	new_test_ext().execute_with(|| {
+		init_bad_debt();
+		AccountDeposits::<Test>::remove(KSM, ALICE);
+
+		// The exchange rate is already at its lower bound, nothing can be socialized
+		assert_ok!(LendMarket::write_off_bad_debt(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			DOT_U
+		));
+		assert_eq!(
+			LendMarket::current_borrow_balance(&ALICE, DOT_U),
+			Ok(unit(40))
+		);
+		assert_eq!(TotalBorrows::<Test>::get(DOT_U), unit(40));
+	})
+}
@@ -0,0 +1,225 @@
+use crate::{
+	mock::*, tests::LendMarket, AccountBorrows, BorrowAllowances, Config, DelegatedBorrows, Error,
+};
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+fn alice_supplies_200_dot() {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		KSM,
+		vec![DOT, KSM]
+	));
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		DOT,
+		unit(200)
+	));
+	assert_ok!(LendMarket::collateral_asset(
+		RuntimeOrigin::signed(ALICE),
+		DOT,
+		true
+	));
+}
+
+#[test]
+fn approve_borrow_allowance_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::approve_borrow_allowance(
+				RuntimeOrigin::signed(ALICE),
+				ALICE,
+				KSM,
+				unit(50)
+			),
+			Error::<Test>::InvalidDelegatee
+		);
+		assert_noop!(
+			LendMarket::approve_borrow_allowance(RuntimeOrigin::signed(ALICE), EVE, VDOT, unit(50)),
+			Error::<Test>::MarketDoesNotExist
+		);
+
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(50)
+		));
+		assert_eq!(BorrowAllowances::<Test>::get(KSM, (ALICE, EVE)), unit(50));
+
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			0
+		));
+		assert!(!BorrowAllowances::<Test>::contains_key(KSM, (ALICE, EVE)));
+	})
+}
+
+#[test]
+fn borrow_on_behalf_draws_against_delegator_liquidity() {
+	new_test_ext().execute_with(|| {
+		alice_supplies_200_dot();
+		assert_noop!(
+			LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(10)),
+			Error::<Test>::BorrowAllowanceExceeded
+		);
+
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(1000)
+		));
+		// Alice's collateral is worth 200 * 50% = $100
+		assert_noop!(
+			LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(101)),
+			Error::<Test>::InsufficientLiquidity
+		);
+
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(50)
+		));
+		assert_ok!(LendMarket::borrow_on_behalf(
+			RuntimeOrigin::signed(EVE),
+			ALICE,
+			KSM,
+			unit(50)
+		));
+		assert_noop!(
+			LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(1)),
+			Error::<Test>::BorrowAllowanceExceeded
+		);
+
+		// The asset goes to EVE, while the debt is owed by ALICE
+		assert_eq!(<Test as Config>::Assets::balance(KSM, &EVE), unit(50));
+		assert_eq!(AccountBorrows::<Test>::get(KSM, ALICE).principal, unit(50));
+		assert_eq!(AccountBorrows::<Test>::get(KSM, EVE).principal, 0);
+		assert_eq!(
+			DelegatedBorrows::<Test>::get((KSM, ALICE, EVE)).principal,
+			unit(50)
+		);
+		assert_eq!(
+			LendMarket::current_delegated_borrow_balance(&ALICE, &EVE, KSM),
+			Ok(unit(50))
+		);
+	})
+}
+
+#[test]
+fn repay_delegated_borrow_works() {
+	new_test_ext().execute_with(|| {
+		alice_supplies_200_dot();
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(50)
+		));
+		assert_ok!(LendMarket::borrow_on_behalf(
+			RuntimeOrigin::signed(EVE),
+			ALICE,
+			KSM,
+			unit(50)
+		));
+
+		assert_noop!(
+			LendMarket::repay_delegated_borrow(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				EVE,
+				KSM,
+				unit(10)
+			),
+			Error::<Test>::NotDelegationParty
+		);
+		assert_noop!(
+			LendMarket::repay_delegated_borrow(
+				RuntimeOrigin::signed(EVE),
+				ALICE,
+				EVE,
+				KSM,
+				unit(51)
+			),
+			Error::<Test>::TooMuchRepay
+		);
+
+		// Either party can repay
+		assert_ok!(LendMarket::repay_delegated_borrow(
+			RuntimeOrigin::signed(EVE),
+			ALICE,
+			EVE,
+			KSM,
+			unit(20)
+		));
+		assert_eq!(<Test as Config>::Assets::balance(KSM, &EVE), unit(30));
+		assert_eq!(AccountBorrows::<Test>::get(KSM, ALICE).principal, unit(30));
+		assert_eq!(
+			DelegatedBorrows::<Test>::get((KSM, ALICE, EVE)).principal,
+			unit(30)
+		);
+
+		assert_ok!(LendMarket::repay_delegated_borrow(
+			RuntimeOrigin::signed(ALICE),
+			ALICE,
+			EVE,
+			KSM,
+			unit(30)
+		));
+		assert_eq!(<Test as Config>::Assets::balance(KSM, &ALICE), unit(970));
+		assert_eq!(AccountBorrows::<Test>::get(KSM, ALICE).principal, 0);
+		assert!(!DelegatedBorrows::<Test>::contains_key((KSM, ALICE, EVE)));
+	})
+}
+
+#[test]
+fn repay_borrow_reduces_delegated_borrows() {
+	new_test_ext().execute_with(|| {
+		alice_supplies_200_dot();
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(50)
+		));
+		assert_ok!(LendMarket::borrow_on_behalf(
+			RuntimeOrigin::signed(EVE),
+			ALICE,
+			KSM,
+			unit(40)
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(40)
+		));
+
+		// Repaying half of the borrows of ALICE frees half of the debt drawn by EVE
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(40)
+		));
+		assert_eq!(AccountBorrows::<Test>::get(KSM, ALICE).principal, unit(40));
+		assert_eq!(
+			DelegatedBorrows::<Test>::get((KSM, ALICE, EVE)).principal,
+			unit(20)
+		);
+		assert_ok!(LendMarket::borrow_on_behalf(
+			RuntimeOrigin::signed(EVE),
+			ALICE,
+			KSM,
+			unit(30)
+		));
+
+		assert_ok!(LendMarket::repay_borrow_all(
+			RuntimeOrigin::signed(ALICE),
+			KSM
+		));
+		assert!(!DelegatedBorrows::<Test>::contains_key((KSM, ALICE, EVE)));
+	})
+}
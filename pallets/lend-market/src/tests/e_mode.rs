@@ -0,0 +1,335 @@
+use crate::{
+	mock::*, tests::LendMarket, AccountEModeCategory, EModeCategory, Error, IsolationMode,
+	IsolationModeTotalDebt,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Permill;
+
+fn init_e_mode_category() {
+	assert_ok!(LendMarket::set_e_mode_category(
+		RuntimeOrigin::root(),
+		1,
+		Some(EModeCategory {
+			collateral_factor: Permill::from_percent(80),
+			liquidation_threshold: Permill::from_percent(90),
+		})
+	));
+	assert_ok!(LendMarket::set_asset_e_mode_category(
+		RuntimeOrigin::root(),
+		KSM,
+		Some(1)
+	));
+	assert_ok!(LendMarket::set_asset_e_mode_category(
+		RuntimeOrigin::root(),
+		DOT_U,
+		Some(1)
+	));
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		DOT_U,
+		vec![KSM]
+	));
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		DOT,
+		vec![KSM]
+	));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(DAVE),
+		DOT_U,
+		unit(200)
+	));
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+}
+
+#[test]
+fn set_e_mode_category_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::set_e_mode_category(
+				RuntimeOrigin::root(),
+				1,
+				Some(EModeCategory {
+					collateral_factor: Permill::from_percent(90),
+					liquidation_threshold: Permill::from_percent(80),
+				})
+			),
+			Error::<Test>::InvalidFactor
+		);
+		assert_noop!(
+			LendMarket::set_asset_e_mode_category(RuntimeOrigin::root(), KSM, Some(1)),
+			Error::<Test>::EModeCategoryDoesNotExist
+		);
+
+		init_e_mode_category();
+		assert_noop!(
+			LendMarket::set_e_mode_category(RuntimeOrigin::root(), 1, None),
+			Error::<Test>::EModeCategoryInUse
+		);
+		assert_noop!(
+			LendMarket::set_e_mode(RuntimeOrigin::signed(ALICE), Some(2)),
+			Error::<Test>::EModeCategoryDoesNotExist
+		);
+	})
+}
+
+#[test]
+fn e_mode_raises_collateral_factor() {
+	new_test_ext().execute_with(|| {
+		init_e_mode_category();
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(100)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			true
+		));
+
+		// 100 KSM * 50% collateral factor is not enough outside of the category
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT_U, unit(70)),
+			Error::<Test>::InsufficientLiquidity
+		);
+
+		assert_ok!(LendMarket::set_e_mode(
+			RuntimeOrigin::signed(ALICE),
+			Some(1)
+		));
+		assert_eq!(AccountEModeCategory::<Test>::get(ALICE), Some(1));
+		assert_eq!(
+			LendMarket::get_account_liquidity(&ALICE).unwrap().0,
+			sp_runtime::FixedU128::from_inner(unit(80))
+		);
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(70)
+		));
+
+		// Assets outside of the category cannot be borrowed
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(1)),
+			Error::<Test>::AssetNotInEModeCategory
+		);
+		// Leaving the category would leave a shortfall
+		assert_noop!(
+			LendMarket::set_e_mode(RuntimeOrigin::signed(ALICE), None),
+			Error::<Test>::InsufficientLiquidity
+		);
+	})
+}
+
+#[test]
+fn set_e_mode_requires_borrows_in_category() {
+	new_test_ext().execute_with(|| {
+		init_e_mode_category();
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(100)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(10)
+		));
+
+		assert_noop!(
+			LendMarket::set_e_mode(RuntimeOrigin::signed(ALICE), Some(1)),
+			Error::<Test>::AssetNotInEModeCategory
+		);
+
+		assert_ok!(LendMarket::repay_borrow_all(
+			RuntimeOrigin::signed(ALICE),
+			DOT
+		));
+		assert_ok!(LendMarket::set_e_mode(
+			RuntimeOrigin::signed(ALICE),
+			Some(1)
+		));
+	})
+}
+
+#[test]
+fn isolation_mode_works() {
+	new_test_ext().execute_with(|| {
+		init_e_mode_category();
+		assert_ok!(LendMarket::set_isolation_mode(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(IsolationMode {
+				debt_ceiling: unit(30),
+				borrowable_assets: vec![DOT_U].try_into().unwrap(),
+			})
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(100)
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(100)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			true
+		));
+
+		// Isolated collateral cannot be mixed with other collaterals
+		assert_noop!(
+			LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT, true),
+			Error::<Test>::IsolatedCollateralMixed
+		);
+
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(20)
+		));
+		assert_eq!(IsolationModeTotalDebt::<Test>::get(KSM, DOT_U), unit(20));
+
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT_U, unit(20)),
+			Error::<Test>::DebtCeilingExceeded
+		);
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(1)),
+			Error::<Test>::NotBorrowableInIsolationMode
+		);
+
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(10)
+		));
+		assert_eq!(IsolationModeTotalDebt::<Test>::get(KSM, DOT_U), unit(10));
+
+		// The debt is valued at the current price
+		MockOraclePriceProvider::set_price(DOT_U, 2.into());
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT_U, unit(6)),
+			Error::<Test>::DebtCeilingExceeded
+		);
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(5)
+		));
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(15)
+		));
+		assert_eq!(IsolationModeTotalDebt::<Test>::iter_prefix(KSM).count(), 0);
+	})
+}
+
+#[test]
+fn isolated_collateral_requires_no_other_collateral() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::set_isolation_mode(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(IsolationMode {
+				debt_ceiling: unit(30),
+				borrowable_assets: vec![DOT_U].try_into().unwrap(),
+			})
+		));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(100)));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(100)));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(BOB),
+			DOT,
+			true
+		));
+
+		assert_noop!(
+			LendMarket::collateral_asset(RuntimeOrigin::signed(BOB), KSM, true),
+			Error::<Test>::IsolatedCollateralMixed
+		);
+	})
+}
+
+#[test]
+fn isolation_mode_requires_market_without_depositors() {
+	new_test_ext().execute_with(|| {
+		init_e_mode_category();
+		let isolation_mode = IsolationMode {
+			debt_ceiling: unit(30),
+			borrowable_assets: vec![DOT_U].try_into().unwrap(),
+		};
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(100)
+		));
+		assert_noop!(
+			LendMarket::set_isolation_mode(
+				RuntimeOrigin::root(),
+				KSM,
+				Some(isolation_mode.clone())
+			),
+			Error::<Test>::IsolationMarketHasDepositors
+		);
+		assert_ok!(LendMarket::redeem_all(RuntimeOrigin::signed(ALICE), KSM));
+		assert_ok!(LendMarket::set_isolation_mode(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(isolation_mode.clone())
+		));
+
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(100)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(10)
+		));
+		assert_eq!(IsolationModeTotalDebt::<Test>::get(KSM, DOT_U), unit(10));
+
+		// The isolation mode of an isolated market can still be updated
+		assert_ok!(LendMarket::set_isolation_mode(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(isolation_mode)
+		));
+
+		// Removing the isolation clears the total debt and releases the accounts
+		assert_ok!(LendMarket::set_isolation_mode(
+			RuntimeOrigin::root(),
+			KSM,
+			None
+		));
+		assert_eq!(IsolationModeTotalDebt::<Test>::iter_prefix(KSM).count(), 0);
+		assert_eq!(LendMarket::isolated_collateral(&ALICE), None);
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(100)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			true
+		));
+	})
+}
@@ -0,0 +1,147 @@
+use crate::{
+	mock::*, tests::LendMarket, AccountDeposits, Config, Error, FlashLoans, TotalReserves,
+};
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+fn remark_call() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+#[test]
+fn flash_loan_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(DAVE),
+			DOT_U,
+			unit(200)
+		));
+
+		assert_ok!(LendMarket::flash_loan(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(100),
+			vec![remark_call()].try_into().unwrap()
+		));
+
+		// fee = 100 * 0.09% = 0.09
+		assert_eq!(
+			<Test as Config>::Assets::balance(DOT_U, &ALICE),
+			unit(1000) - milli_unit(90)
+		);
+		assert_eq!(TotalReserves::<Test>::get(DOT_U), milli_unit(90));
+		assert_eq!(
+			<Test as Config>::Assets::balance(DOT_U, &LendMarket::account_id()),
+			unit(200) + milli_unit(90)
+		);
+		assert_eq!(FlashLoans::<Test>::get(DOT_U), 0);
+	})
+}
+
+#[test]
+fn flash_loan_must_be_repaid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(DAVE),
+			DOT_U,
+			unit(200)
+		));
+
+		// EVE gives the loan away and cannot repay it
+		assert_noop!(
+			LendMarket::flash_loan(
+				RuntimeOrigin::signed(EVE),
+				DOT_U,
+				unit(100),
+				vec![RuntimeCall::Currencies(
+					bifrost_currencies::Call::transfer {
+						dest: BOB,
+						currency_id: DOT_U,
+						amount: unit(100),
+					}
+				)]
+				.try_into()
+				.unwrap()
+			),
+			Error::<Test>::FlashLoanNotRepaid
+		);
+		assert_noop!(
+			LendMarket::flash_loan(
+				RuntimeOrigin::signed(ALICE),
+				DOT_U,
+				unit(201),
+				vec![remark_call()].try_into().unwrap()
+			),
+			Error::<Test>::InsufficientCash
+		);
+	})
+}
+
+#[test]
+fn flash_loan_keeps_exchange_rate() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(DAVE),
+			DOT_U,
+			unit(200)
+		));
+
+		// Supplying the drained market during the loan still uses the original exchange rate
+		assert_ok!(LendMarket::flash_loan(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(100),
+			vec![RuntimeCall::LendMarket(crate::Call::mint {
+				asset_id: DOT_U,
+				mint_amount: unit(100),
+			})]
+			.try_into()
+			.unwrap()
+		));
+		assert_eq!(
+			AccountDeposits::<Test>::get(DOT_U, ALICE).voucher_balance,
+			unit(5000)
+		);
+	})
+}
+
+#[test]
+fn flash_loan_is_not_cash_of_the_market() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(DAVE),
+			DOT_U,
+			unit(200)
+		));
+
+		// The lent cash cannot be borrowed again while the loan is outstanding
+		assert_noop!(
+			LendMarket::flash_loan(
+				RuntimeOrigin::signed(ALICE),
+				DOT_U,
+				unit(150),
+				vec![RuntimeCall::LendMarket(crate::Call::borrow {
+					asset_id: DOT_U,
+					borrow_amount: unit(100),
+				})]
+				.try_into()
+				.unwrap()
+			),
+			Error::<Test>::InsufficientCash
+		);
+		assert_noop!(
+			LendMarket::flash_loan(
+				RuntimeOrigin::signed(ALICE),
+				DOT_U,
+				unit(150),
+				vec![RuntimeCall::LendMarket(crate::Call::flash_loan {
+					asset_id: DOT_U,
+					amount: unit(100),
+					calls: Default::default(),
+				})]
+				.try_into()
+				.unwrap()
+			),
+			Error::<Test>::InsufficientCash
+		);
+	})
+}
@@ -681,3 +681,164 @@ fn a_market_can_only_accrue_interest_once_in_a_block() {
 		);
 	})
 }
+
+fn adaptive_model() -> AdaptiveModel {
+	AdaptiveModel {
+		target_utilization: Ratio::from_percent(90),
+		initial_rate_at_target: Rate::saturating_from_rational(4, 100),
+		min_rate_at_target: Rate::saturating_from_rational(1, 1000),
+		max_rate_at_target: Rate::saturating_from_integer(2),
+		adjustment_speed: Rate::saturating_from_integer(50),
+		curve_steepness: Rate::saturating_from_integer(4),
+	}
+}
+
+// Supplies 100 DOT and borrows `borrow_amount` DOT against KSM
+fn setup_adaptive_dot_market(borrow_amount: u128) {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		DOT,
+		vec![DOT, BNC, KSM, DOT_U, PHA]
+	));
+	assert_ok!(LendMarket::update_rate_model(
+		RuntimeOrigin::root(),
+		DOT,
+		InterestRateModel::Adaptive(adaptive_model())
+	));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		DOT,
+		unit(100)
+	));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		unit(500)
+	));
+	assert_ok!(LendMarket::collateral_asset(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		true
+	));
+	assert_ok!(LendMarket::borrow(
+		RuntimeOrigin::signed(ALICE),
+		DOT,
+		borrow_amount
+	));
+}
+
+#[test]
+fn adaptive_rate_model_accrues_interest_with_moving_rate_at_target() {
+	new_test_ext().execute_with(|| {
+		// 95% utilization, above the 90% target
+		setup_adaptive_dot_market(unit(95));
+		let model = adaptive_model();
+		let hour = 60 * 60;
+
+		let mut rate_at_target = model.initial_rate_at_target;
+		for i in 1..=24 {
+			let total_borrows = TotalBorrows::<Test>::get(DOT);
+			let total_reserves = TotalReserves::<Test>::get(DOT);
+			let util = LendMarket::calc_utilization_ratio(
+				LendMarket::get_total_cash(DOT),
+				total_borrows,
+				total_reserves,
+			)
+			.unwrap();
+			let (borrow_rate, end_rate_at_target) =
+				model.get_borrow_rate(util, rate_at_target, hour).unwrap();
+
+			TimestampPallet::set_timestamp(6000 + i * hour * 1000);
+			assert_ok!(LendMarket::accrue_interest(DOT));
+
+			assert_eq!(BorrowRate::<Test>::get(DOT), borrow_rate);
+			assert_eq!(
+				AdaptiveRateAtTarget::<Test>::get(DOT),
+				Some(end_rate_at_target)
+			);
+			let interest_accumulated: u128 = borrow_rate
+				.saturating_mul_int(total_borrows)
+				.saturating_mul(hour.into())
+				.checked_div(SECONDS_PER_YEAR.into())
+				.unwrap();
+			assert_eq!(
+				TotalBorrows::<Test>::get(DOT),
+				total_borrows + interest_accumulated
+			);
+
+			// The rate at target keeps growing while the utilization stays above the target
+			assert!(end_rate_at_target > rate_at_target);
+			// The average rate lies between the curve at the start and at the end of the period
+			assert!(borrow_rate > model.get_borrow_rate(util, rate_at_target, 0).unwrap().0);
+			assert!(
+				borrow_rate
+					< model
+						.get_borrow_rate(util, end_rate_at_target, 0)
+						.unwrap()
+						.0
+			);
+			rate_at_target = end_rate_at_target;
+		}
+	})
+}
+
+#[test]
+fn adaptive_rate_at_target_stays_within_bounds() {
+	new_test_ext().execute_with(|| {
+		// 50% utilization, below the 90% target
+		setup_adaptive_dot_market(unit(50));
+		let model = adaptive_model();
+
+		TimestampPallet::set_timestamp(6000 + SECONDS_PER_YEAR * 1000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert_eq!(
+			AdaptiveRateAtTarget::<Test>::get(DOT),
+			Some(model.min_rate_at_target)
+		);
+		assert!(BorrowRate::<Test>::get(DOT) < model.initial_rate_at_target);
+		assert!(BorrowRate::<Test>::get(DOT) > model.min_rate_at_target / model.curve_steepness);
+
+		// Fully borrowed, the rate at target grows up to its upper bound
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(50)
+		));
+		TimestampPallet::set_timestamp(6000 + 2 * SECONDS_PER_YEAR * 1000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert_eq!(
+			AdaptiveRateAtTarget::<Test>::get(DOT),
+			Some(model.max_rate_at_target)
+		);
+	})
+}
+
+#[test]
+fn update_rate_model_accrues_interest_and_resets_adaptive_state() {
+	new_test_ext().execute_with(|| {
+		setup_adaptive_dot_market(unit(95));
+		TimestampPallet::set_timestamp(6000 + 3600 * 1000);
+		let (borrow_rate, _, _, _, total_borrows, _, _) =
+			LendMarket::get_market_status(DOT).unwrap();
+
+		// Switching to another adaptive model keeps the rate at target
+		let mut model = adaptive_model();
+		model.adjustment_speed = Rate::saturating_from_integer(10);
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			InterestRateModel::Adaptive(model)
+		));
+		assert_eq!(LastAccruedInterestTime::<Test>::get(DOT), 6 + 3600);
+		assert_eq!(BorrowRate::<Test>::get(DOT), borrow_rate);
+		assert_eq!(TotalBorrows::<Test>::get(DOT), total_borrows);
+		assert!(AdaptiveRateAtTarget::<Test>::get(DOT).unwrap() > model.initial_rate_at_target);
+
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			market_mock(LDOT).rate_model
+		));
+		assert_eq!(AdaptiveRateAtTarget::<Test>::get(DOT), None);
+	})
+}
@@ -6,9 +6,9 @@ use crate::{
 	tests::unit,
 	Error, MarketState,
 };
-use bifrost_primitives::Rate;
-use frame_support::{assert_err, assert_noop, assert_ok};
-use sp_runtime::FixedPointNumber;
+use bifrost_primitives::{Rate, TryConvertFrom};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::fungibles::Mutate};
+use sp_runtime::{traits::One, FixedPointNumber};
 
 #[test]
 fn liquidate_borrow_allowed_works() {
@@ -395,6 +395,321 @@ fn liquidator_must_not_be_borrower() {
 	})
 }
 
+#[test]
+fn liquidation_auction_works() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		alice_borrows_100_ksm();
+		// adjust KSM price to make ALICE generate shortfall
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_noop!(
+			LendMarket::open_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE, KSM, DOT_U),
+			Error::<Test>::LiquidationAuctionDisabled
+		);
+		assert_noop!(
+			LendMarket::set_liquidation_auction_config(
+				RuntimeOrigin::root(),
+				KSM,
+				Some(LiquidationAuctionConfig {
+					start_incentive: Rate::saturating_from_rational(110, 100),
+					end_incentive: Rate::one(),
+					duration: 100,
+				})
+			),
+			Error::<Test>::InvalidLiquidationAuctionConfig
+		);
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(LiquidationAuctionConfig {
+				start_incentive: Rate::one(),
+				end_incentive: Rate::saturating_from_rational(110, 100),
+				duration: 100,
+			})
+		));
+		assert_noop!(
+			LendMarket::liquidate_borrow(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(50), DOT_U),
+			Error::<Test>::LiquidationAuctionRequired
+		);
+
+		assert_ok!(LendMarket::open_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			DOT_U
+		));
+		assert_noop!(
+			LendMarket::open_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE, KSM, DOT_U),
+			Error::<Test>::LiquidationAuctionAlreadyOpened
+		);
+
+		// Half way through the auction, the incentive is 1.05
+		TimestampPallet::set_timestamp(56000);
+		assert_ok!(LendMarket::bid_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(10)
+		));
+
+		// incentive = repay KSM value * 1.05 = (10 * 2) * 1.05 = 21
+		// Alice DOT_U collateral: deposit - incentive = 200 - 21 = 179
+		// Bob DOT_U collateral: incentive = 21 - (21 / 1.05 * 0.03) = 20.4
+		assert_eq!(
+			ExchangeRate::<Test>::get(DOT_U)
+				.saturating_mul_int(AccountDeposits::<Test>::get(DOT_U, ALICE).voucher_balance),
+			unit(179),
+		);
+		assert_eq!(
+			ExchangeRate::<Test>::get(DOT_U)
+				.saturating_mul_int(AccountDeposits::<Test>::get(DOT_U, BOB).voucher_balance),
+			milli_unit(20400),
+		);
+		assert!(LiquidationAuctions::<Test>::contains_key(KSM, ALICE));
+
+		// ALICE is healthy again, a bid closes the auction without liquidating
+		MockOraclePriceProvider::set_price(KSM, 1.into());
+		assert_ok!(LendMarket::bid_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(10)
+		));
+		assert!(!LiquidationAuctions::<Test>::contains_key(KSM, ALICE));
+		assert_eq!(
+			ExchangeRate::<Test>::get(DOT_U)
+				.saturating_mul_int(AccountDeposits::<Test>::get(DOT_U, ALICE).voucher_balance),
+			unit(179),
+		);
+		System::assert_last_event(RuntimeEvent::LendMarket(
+			crate::Event::LiquidationAuctionClosed {
+				borrower: ALICE,
+				liquidation_asset_id: KSM,
+			},
+		));
+		assert_noop!(
+			LendMarket::bid_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(10)),
+			Error::<Test>::LiquidationAuctionDoesNotExist
+		);
+	})
+}
+
+#[test]
+fn close_liquidation_auction_fails_when_liquidatable() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		alice_borrows_100_ksm();
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(LiquidationAuctionConfig {
+				start_incentive: Rate::one(),
+				end_incentive: Rate::saturating_from_rational(110, 100),
+				duration: 100,
+			})
+		));
+		assert_ok!(LendMarket::open_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			DOT_U
+		));
+		assert_noop!(
+			LendMarket::close_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE, KSM),
+			Error::<Test>::BorrowerStillLiquidatable
+		);
+	})
+}
+
+#[test]
+fn liquidation_auction_is_closed_when_config_changes() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		alice_borrows_100_ksm();
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		let config = LiquidationAuctionConfig {
+			start_incentive: Rate::one(),
+			end_incentive: Rate::saturating_from_rational(110, 100),
+			duration: 100,
+		};
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(config)
+		));
+		assert_ok!(LendMarket::open_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			DOT_U
+		));
+
+		// The auction mode is disabled, ALICE is still liquidatable but the auction is stale
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			KSM,
+			None
+		));
+		assert_ok!(LendMarket::close_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM
+		));
+		assert!(!LiquidationAuctions::<Test>::contains_key(KSM, ALICE));
+
+		// A new config does not apply to an auction opened under the old one
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(config)
+		));
+		assert_ok!(LendMarket::open_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			DOT_U
+		));
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(LiquidationAuctionConfig {
+				duration: 200,
+				..config
+			})
+		));
+		TimestampPallet::set_timestamp(106000);
+		assert_ok!(LendMarket::bid_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(10)
+		));
+		assert!(!LiquidationAuctions::<Test>::contains_key(KSM, ALICE));
+		assert_eq!(
+			ExchangeRate::<Test>::get(DOT_U)
+				.saturating_mul_int(AccountDeposits::<Test>::get(DOT_U, ALICE).voucher_balance),
+			unit(200),
+		);
+	})
+}
+
+#[test]
+fn liquidate_borrow_and_swap_works() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		alice_borrows_100_ksm();
+		init_zenlink_pair(DOT_U, KSM);
+		// adjust KSM price to make ALICE generate shortfall
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+
+		// EVE holds nothing, the seized DOT_U is swapped to repay the borrowed KSM
+		assert_noop!(
+			LendMarket::liquidate_borrow_and_swap(
+				RuntimeOrigin::signed(EVE),
+				ALICE,
+				KSM,
+				unit(50),
+				DOT_U,
+				LiquidationSwapRoute::StablePool,
+				0,
+			),
+			Error::<Test>::SwapRouteNotFound
+		);
+		assert_noop!(
+			LendMarket::liquidate_borrow_and_swap(
+				RuntimeOrigin::signed(EVE),
+				ALICE,
+				KSM,
+				unit(50),
+				DOT_U,
+				LiquidationSwapRoute::Zenlink,
+				unit(107),
+			),
+			Error::<Test>::InsufficientLiquidationProfit
+		);
+		// Liquidations keep running while borrowing is frozen
+		assert_ok!(LendMarket::set_market_action_paused(
+			RuntimeOrigin::root(),
+			KSM,
+			crate::MarketAction::Borrow,
+			true
+		));
+		assert_ok!(LendMarket::liquidate_borrow_and_swap(
+			RuntimeOrigin::signed(EVE),
+			ALICE,
+			KSM,
+			unit(50),
+			DOT_U,
+			LiquidationSwapRoute::Zenlink,
+			unit(50),
+		));
+
+		// Alice KSM borrow balance: origin borrow balance - liquidate amount = 100 - 50 = 50
+		assert_eq!(AccountBorrows::<Test>::get(KSM, ALICE).principal, unit(50));
+		// The loan and its fee are repaid, the fee is added to the reserves
+		let fee = FlashLoanFee::get().mul_ceil(unit(50));
+		assert_eq!(FlashLoans::<Test>::get(KSM), 0);
+		assert_eq!(TotalReserves::<Test>::get(KSM), fee);
+		assert_eq!(<Test as Config>::Assets::balance(KSM, &EVE), 0);
+		// EVE keeps the seized collateral left after the swap, and no deposit
+		assert_eq!(AccountDeposits::<Test>::get(DOT_U, EVE).voucher_balance, 0);
+		let profit = <Test as Config>::Assets::balance(DOT_U, &EVE);
+		assert!(profit >= unit(50) && profit < unit(107));
+		System::assert_last_event(RuntimeEvent::LendMarket(
+			crate::Event::LiquidatedAndSwapped {
+				liquidator: EVE,
+				borrower: ALICE,
+				liquidation_asset_id: KSM,
+				collateral_asset_id: DOT_U,
+				repay_amount: unit(50),
+				profit,
+			},
+		));
+	})
+}
+
+#[test]
+fn liquidate_borrow_and_swap_fails_due_to_lf_collateral() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_noop!(
+			LendMarket::liquidate_borrow_and_swap(
+				RuntimeOrigin::signed(EVE),
+				ALICE,
+				KSM,
+				unit(50),
+				PHA,
+				LiquidationSwapRoute::Zenlink,
+				0,
+			),
+			Error::<Test>::CollateralReserved
+		);
+	})
+}
+
+fn init_zenlink_pair(asset_0: CurrencyId, asset_1: CurrencyId) {
+	let asset_0 = ZenlinkAssetId::try_convert_from(asset_0, 2001).unwrap();
+	let asset_1 = ZenlinkAssetId::try_convert_from(asset_1, 2001).unwrap();
+	assert_ok!(<Test as Config>::Assets::mint_into(KSM, &DAVE, unit(1000)));
+	assert_ok!(ZenlinkProtocol::create_pair(
+		RuntimeOrigin::root(),
+		asset_0,
+		asset_1,
+		DAVE
+	));
+	assert_ok!(ZenlinkProtocol::add_liquidity(
+		RuntimeOrigin::signed(DAVE),
+		asset_0,
+		asset_1,
+		unit(1000),
+		unit(1000),
+		1,
+		1,
+		100
+	));
+}
+
 fn alice_borrows_100_ksm() {
 	assert_ok!(LendMarket::borrow(
 		RuntimeOrigin::signed(ALICE),
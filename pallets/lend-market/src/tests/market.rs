@@ -1,9 +1,9 @@
 use crate::{
 	mock::{
-		market_mock, new_test_ext, LendMarket, RuntimeOrigin, Test, ACTIVE_MARKET_MOCK, ALICE, DOT,
-		LDOT, LUSDT, MARKET_MOCK, VDOT,
+		market_mock, new_test_ext, unit, LendMarket, RuntimeOrigin, Test, ACTIVE_MARKET_MOCK,
+		ALICE, DOT, KSM, LDOT, LUSDT, MARKET_MOCK, VDOT,
 	},
-	Error, InterestRateModel, MarketState,
+	Error, InterestRateModel, MarketAction, MarketState, PausedMarketActions,
 };
 use bifrost_primitives::{Rate, Ratio};
 use frame_support::{assert_noop, assert_ok, error::BadOrigin};
@@ -433,3 +433,117 @@ fn update_rate_model_works() {
 		);
 	})
 }
+
+#[test]
+fn set_market_action_paused_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::set_market_action_paused(
+				RuntimeOrigin::signed(ALICE),
+				DOT,
+				MarketAction::Borrow,
+				true
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			LendMarket::set_market_action_paused(
+				RuntimeOrigin::root(),
+				VDOT,
+				MarketAction::Borrow,
+				true
+			),
+			Error::<Test>::MarketDoesNotExist
+		);
+
+		assert_ok!(LendMarket::add_market_bond(
+			RuntimeOrigin::root(),
+			DOT,
+			vec![KSM]
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(100)
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(100)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(10)
+		));
+
+		// Only borrowing is frozen, users can still repay and withdraw
+		assert_ok!(LendMarket::set_market_action_paused(
+			RuntimeOrigin::root(),
+			DOT,
+			MarketAction::Borrow,
+			true
+		));
+		assert!(PausedMarketActions::<Test>::get(DOT, MarketAction::Borrow));
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(10)),
+			Error::<Test>::MarketActionPaused
+		);
+		assert_noop!(
+			LendMarket::flash_loan(
+				RuntimeOrigin::signed(ALICE),
+				DOT,
+				unit(10),
+				Default::default()
+			),
+			Error::<Test>::MarketActionPaused
+		);
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(5)
+		));
+		assert_ok!(LendMarket::redeem(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(10)
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(10)
+		));
+
+		assert_ok!(LendMarket::set_market_action_paused(
+			RuntimeOrigin::root(),
+			DOT,
+			MarketAction::CollateralToggle,
+			true
+		));
+		assert_noop!(
+			LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT, true),
+			Error::<Test>::MarketActionPaused
+		);
+
+		assert_ok!(LendMarket::set_market_action_paused(
+			RuntimeOrigin::root(),
+			DOT,
+			MarketAction::Borrow,
+			false
+		));
+		assert!(!PausedMarketActions::<Test>::contains_key(
+			DOT,
+			MarketAction::Borrow
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(10)
+		));
+	})
+}
@@ -1,7 +1,8 @@
 use crate::InterestRateModel;
-use bifrost_primitives::{CurrencyId, Rate, Ratio};
+use bifrost_primitives::{CurrencyId, Rate, Ratio, Timestamp};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
+use sp_runtime::{FixedPointNumber, Saturating};
 
 /// Container for borrow balance information
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
@@ -49,6 +50,43 @@ pub enum MarketState {
 	Supervision,
 }
 
+/// The user actions of an active market, which can be paused individually.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	parity_scale_codec::Decode,
+	parity_scale_codec::Encode,
+	MaxEncodedLen,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub enum MarketAction {
+	Mint,
+	Redeem,
+	Borrow,
+	Repay,
+	Liquidate,
+	CollateralToggle,
+}
+
+/// The venue where the seized collateral of `liquidate_borrow_and_swap` is swapped.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	parity_scale_codec::Decode,
+	parity_scale_codec::Encode,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub enum LiquidationSwapRoute {
+	StablePool,
+	Zenlink,
+}
+
 /// Market.
 ///
 /// A large pool of liquidity where accounts can lend and borrow.
@@ -95,3 +133,86 @@ pub struct RewardMarketState<BlockNumber, Balance> {
 	/// total amount of staking asset user deposited
 	pub block: BlockNumber,
 }
+
+/// Identifier of an efficiency mode category
+pub type EModeCategoryId = u8;
+
+/// Efficiency mode category.
+///
+/// Correlated assets, such as DOT and its derivatives, share a category. An account that opted
+/// into the category uses these factors instead of the market ones for collaterals in the
+/// category, and can only borrow assets in the category.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub struct EModeCategory {
+	/// The collateral utilization ratio within the category
+	pub collateral_factor: Ratio,
+	/// The liquidation threshold within the category
+	pub liquidation_threshold: Ratio,
+}
+
+/// Isolation mode of a collateral asset.
+///
+/// An account using an isolated collateral cannot use any other collateral, and can only borrow
+/// `borrowable_assets` while the total debt backed by the collateral stays under `debt_ceiling`.
+#[derive(
+	Encode,
+	Decode,
+	frame_support::CloneNoBound,
+	frame_support::EqNoBound,
+	frame_support::PartialEqNoBound,
+	frame_support::RuntimeDebugNoBound,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxAssets))]
+pub struct IsolationMode<Balance, MaxAssets>
+where
+	Balance: Clone + Eq + sp_std::fmt::Debug,
+	MaxAssets: Get<u32>,
+{
+	/// Upper bound of the total debt value backed by the collateral, in dollars with 18 decimals
+	pub debt_ceiling: Balance,
+	/// Assets that can be borrowed against the collateral
+	pub borrowable_assets: BoundedVec<CurrencyId, MaxAssets>,
+}
+
+/// Dutch auction liquidation parameters of a market.
+///
+/// The liquidation incentive rises linearly from `start_incentive` to `end_incentive` over
+/// `duration`, i.e. the price of the seized collateral descends until a bidder repays the debt.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub struct LiquidationAuctionConfig {
+	/// Liquidation incentive when the auction opens
+	pub start_incentive: Rate,
+	/// Liquidation incentive when the auction ends
+	pub end_incentive: Rate,
+	/// Duration of the auction in seconds
+	pub duration: Timestamp,
+}
+
+impl LiquidationAuctionConfig {
+	/// Returns the liquidation incentive `elapsed` seconds after the auction opened
+	pub fn incentive_at(&self, elapsed: Timestamp) -> Rate {
+		if elapsed >= self.duration {
+			return self.end_incentive;
+		}
+		let progress = Rate::saturating_from_rational(elapsed, self.duration);
+		self.start_incentive.saturating_add(
+			self.end_incentive
+				.saturating_sub(self.start_incentive)
+				.saturating_mul(progress),
+		)
+	}
+}
+
+/// Liquidation auction over the collateral of an unhealthy borrower
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub struct LiquidationAuction {
+	/// The collateral to seize from the borrower
+	pub collateral_asset_id: CurrencyId,
+	/// The timestamp the auction was opened at
+	pub start_time: Timestamp,
+	/// The auction parameters at the time the auction was opened
+	pub config: LiquidationAuctionConfig,
+}
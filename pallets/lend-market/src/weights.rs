@@ -74,6 +74,16 @@ pub trait WeightInfo {
 	fn add_reserves() -> Weight;
 	fn reduce_reserves() -> Weight;
 	fn update_liquidation_free_collateral() -> Weight;
+	fn write_off_bad_debt() -> Weight;
+	fn set_isolation_mode() -> Weight;
+	fn set_liquidation_auction_config() -> Weight;
+	fn open_liquidation_auction() -> Weight;
+	fn bid_liquidation_auction() -> Weight;
+	fn close_liquidation_auction() -> Weight;
+	fn set_market_action_paused() -> Weight;
+	fn approve_borrow_allowance() -> Weight;
+	fn borrow_on_behalf() -> Weight;
+	fn repay_delegated_borrow() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -108,14 +118,36 @@ impl WeightInfo for () {
 	}
 	/// Storage: `LendMarket::Markets` (r:1 w:1)
 	/// Proof: `LendMarket::Markets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `LendMarket::LastAccruedInterestTime` (r:1 w:1)
+	/// Proof: `LendMarket::LastAccruedInterestTime` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::TotalSupply` (r:1 w:0)
+	/// Proof: `LendMarket::TotalSupply` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:1 w:0)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `LendMarket::FlashLoans` (r:1 w:0)
+	/// Proof: `LendMarket::FlashLoans` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::TotalBorrows` (r:1 w:1)
+	/// Proof: `LendMarket::TotalBorrows` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::TotalReserves` (r:1 w:1)
+	/// Proof: `LendMarket::TotalReserves` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::BorrowIndex` (r:1 w:1)
+	/// Proof: `LendMarket::BorrowIndex` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::AdaptiveRateAtTarget` (r:1 w:1)
+	/// Proof: `LendMarket::AdaptiveRateAtTarget` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::UtilizationRatio` (r:0 w:1)
+	/// Proof: `LendMarket::UtilizationRatio` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::BorrowRate` (r:0 w:1)
+	/// Proof: `LendMarket::BorrowRate` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::SupplyRate` (r:0 w:1)
+	/// Proof: `LendMarket::SupplyRate` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::ExchangeRate` (r:0 w:1)
+	/// Proof: `LendMarket::ExchangeRate` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn update_rate_model() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `346`
-		//  Estimated: `3811`
-		// Minimum execution time: 8_885_000 picoseconds.
-		Weight::from_parts(9_299_000, 3811)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(39_481_000, 4628)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
 	}
 	/// Storage: `LendMarket::Markets` (r:1 w:1)
 	/// Proof: `LendMarket::Markets` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -591,4 +623,70 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `LendMarket::Markets` (r:3 w:0)
+	/// Proof: `LendMarket::Markets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::AccountDeposits` (r:2 w:0)
+	/// Proof: `LendMarket::AccountDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::AccountBorrows` (r:1 w:1)
+	/// Proof: `LendMarket::AccountBorrows` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::TotalBorrows` (r:1 w:1)
+	/// Proof: `LendMarket::TotalBorrows` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::TotalReserves` (r:1 w:1)
+	/// Proof: `LendMarket::TotalReserves` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LendMarket::BackstopAccount` (r:1 w:0)
+	/// Proof: `LendMarket::BackstopAccount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `LendMarket::ExchangeRate` (r:0 w:1)
+	/// Proof: `LendMarket::ExchangeRate` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn write_off_bad_debt() -> Weight {
+		Weight::from_parts(160_000_000, 13800)
+			.saturating_add(RocksDbWeight::get().reads(30_u64))
+			.saturating_add(RocksDbWeight::get().writes(14_u64))
+	}
+	fn set_isolation_mode() -> Weight {
+		Weight::from_parts(20_000_000, 3811)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_liquidation_auction_config() -> Weight {
+		Weight::from_parts(18_000_000, 3597)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn open_liquidation_auction() -> Weight {
+		Weight::from_parts(160_000_000, 13800)
+			.saturating_add(RocksDbWeight::get().reads(28_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn bid_liquidation_auction() -> Weight {
+		Weight::from_parts(280_000_000, 13800)
+			.saturating_add(RocksDbWeight::get().reads(45_u64))
+			.saturating_add(RocksDbWeight::get().writes(20_u64))
+	}
+	fn close_liquidation_auction() -> Weight {
+		Weight::from_parts(110_000_000, 13800)
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_market_action_paused() -> Weight {
+		Weight::from_parts(12_000_000, 3811)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn approve_borrow_allowance() -> Weight {
+		Weight::from_parts(16_000_000, 3811)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn borrow_on_behalf() -> Weight {
+		Weight::from_parts(155_000_000, 7867)
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn repay_delegated_borrow() -> Weight {
+		Weight::from_parts(95_000_000, 7728)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
 }
@@ -0,0 +1,33 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bifrost_primitives::{CurrencyId, Rate};
+pub use leverage_staking::LeveragePosition;
+use parity_scale_codec::Codec;
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+	pub trait LeverageStakingApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec {
+		fn get_leverage_position(account: AccountId, asset_id: CurrencyId) -> Result<LeveragePosition<Balance>, DispatchError>;
+		fn simulate_leverage_position(account: AccountId, asset_id: CurrencyId, rate: Rate) -> Result<LeveragePosition<Balance>, DispatchError>;
+	}
+}
@@ -0,0 +1,178 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+pub use leverage_staking_rpc_runtime_api::LeverageStakingApi as LeverageStakingRuntimeApi;
+
+use bifrost_primitives::{CurrencyId, Rate};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{ErrorCode, ErrorObject},
+};
+use leverage_staking_rpc_runtime_api::LeveragePosition;
+use parity_scale_codec::Codec;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+#[rpc(client, server)]
+pub trait LeverageStakingApi<BlockHash, AccountId, Balance>
+where
+	Balance: Codec + Copy + TryFrom<NumberOrHex>,
+{
+	#[method(name = "leverage_staking_getLeveragePosition")]
+	fn get_leverage_position(
+		&self,
+		account: AccountId,
+		asset_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<LeveragePosition<NumberOrHex>>;
+	#[method(name = "leverage_staking_simulateLeveragePosition")]
+	fn simulate_leverage_position(
+		&self,
+		account: AccountId,
+		asset_id: CurrencyId,
+		rate: Rate,
+		at: Option<BlockHash>,
+	) -> RpcResult<LeveragePosition<NumberOrHex>>;
+}
+
+/// A struct that implements the [`LeverageStakingApi`].
+pub struct LeverageStaking<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> LeverageStaking<C, B> {
+	/// Create new `LeverageStaking` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	LeveragePositionError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::LeveragePositionError => 2,
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance>
+	LeverageStakingApiServer<<Block as BlockT>::Hash, AccountId, Balance> for LeverageStaking<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: LeverageStakingRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec + Copy + TryFrom<NumberOrHex> + Into<NumberOrHex> + std::fmt::Display,
+{
+	fn get_leverage_position(
+		&self,
+		account: AccountId,
+		asset_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<LeveragePosition<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let position = api
+			.get_leverage_position(at, account, asset_id)
+			.map_err(runtime_error_into_rpc_error)?
+			.map_err(leverage_position_error_into_rpc_error)?;
+		into_rpc_position(position)
+	}
+
+	fn simulate_leverage_position(
+		&self,
+		account: AccountId,
+		asset_id: CurrencyId,
+		rate: Rate,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<LeveragePosition<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let position = api
+			.simulate_leverage_position(at, account, asset_id, rate)
+			.map_err(runtime_error_into_rpc_error)?
+			.map_err(leverage_position_error_into_rpc_error)?;
+		into_rpc_position(position)
+	}
+}
+
+fn into_rpc_position<Balance: std::fmt::Display + Copy + TryInto<NumberOrHex>>(
+	position: LeveragePosition<Balance>,
+) -> RpcResult<LeveragePosition<NumberOrHex>> {
+	Ok(LeveragePosition {
+		leverage_rate: position.leverage_rate,
+		collateral_value: try_into_rpc_balance(position.collateral_value)?,
+		debt: try_into_rpc_balance(position.debt)?,
+		health_factor: position.health_factor,
+		max_leverage_rate: position.max_leverage_rate,
+	})
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObject<'static> {
+	ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Runtime trapped",
+		Some(format!("{:?}", err)),
+	)
+}
+
+/// Converts a leverage position error into an RPC error.
+fn leverage_position_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObject<'static> {
+	ErrorObject::owned(
+		Error::LeveragePositionError.into(),
+		"Not able to get leverage position",
+		Some(format!("{:?}", err)),
+	)
+}
+
+fn try_into_rpc_balance<T: std::fmt::Display + Copy + TryInto<NumberOrHex>>(
+	value: T,
+) -> RpcResult<NumberOrHex> {
+	value.try_into().map_err(|_| {
+		ErrorObject::owned(
+			ErrorCode::InvalidParams.code(),
+			format!("{} doesn't fit in NumberOrHex representation", value),
+			None::<()>,
+		)
+	})
+}
@@ -188,7 +188,102 @@ mod benchmarks {
 		let rate = FixedU128::from_inner(unit(990_000));
 
 		#[extrinsic_call]
-		Pallet::<T>::flash_loan_deposit(SystemOrigin::Signed(caller.clone()), coin0.into(), rate);
+		Pallet::<T>::flash_loan_deposit(
+			SystemOrigin::Signed(caller.clone()),
+			coin0.into(),
+			rate,
+			None,
+			Ratio::from_percent(50),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_deleverage_guard() -> Result<(), BenchmarkError> {
+		init::<T>()?;
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+		assert_ok!(Pallet::<T>::flash_loan_deposit(
+			SystemOrigin::Signed(caller.clone()).into(),
+			KSM.into(),
+			FixedU128::from_inner(unit(990_000)),
+			None,
+			Ratio::from_percent(50)
+		));
+		let guard = DeleverageGuard {
+			min_health_factor: FixedU128::from_inner(unit(1_200_000)),
+			target_rate: FixedU128::from_inner(unit(500_000)),
+			max_slippage: Ratio::from_percent(50),
+		};
+
+		#[extrinsic_call]
+		Pallet::<T>::set_deleverage_guard(
+			SystemOrigin::Signed(caller.clone()),
+			KSM.into(),
+			Some(guard),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deleverage() -> Result<(), BenchmarkError> {
+		init::<T>()?;
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+		assert_ok!(Pallet::<T>::flash_loan_deposit(
+			SystemOrigin::Signed(caller.clone()).into(),
+			KSM.into(),
+			FixedU128::from_inner(unit(990_000)),
+			None,
+			Ratio::from_percent(50)
+		));
+		// The health factor of the position is below the guard after the deposit
+		let guard = DeleverageGuard {
+			min_health_factor: FixedU128::from_inner(unit(2_000_000)),
+			target_rate: FixedU128::from_inner(unit(500_000)),
+			max_slippage: Ratio::from_percent(50),
+		};
+		assert_ok!(Pallet::<T>::set_deleverage_guard(
+			SystemOrigin::Signed(caller.clone()).into(),
+			KSM.into(),
+			Some(guard)
+		));
+
+		#[block]
+		{
+			Pallet::<T>::deleverage_inner(caller, KSM.into())?;
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn check_deleverage_guard() -> Result<(), BenchmarkError> {
+		init::<T>()?;
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+		assert_ok!(Pallet::<T>::flash_loan_deposit(
+			SystemOrigin::Signed(caller.clone()).into(),
+			KSM.into(),
+			FixedU128::from_inner(unit(990_000)),
+			None,
+			Ratio::from_percent(50)
+		));
+		// The position stays above the guard, only its health factor is checked
+		let guard = DeleverageGuard {
+			min_health_factor: FixedU128::from_inner(unit(1_000_001)),
+			target_rate: FixedU128::from_inner(unit(500_000)),
+			max_slippage: Ratio::from_percent(50),
+		};
+		assert_ok!(Pallet::<T>::set_deleverage_guard(
+			SystemOrigin::Signed(caller.clone()).into(),
+			KSM.into(),
+			Some(guard)
+		));
+
+		#[block]
+		{
+			assert!(Pallet::<T>::deleverage_inner(caller, KSM.into()).is_err());
+		}
 
 		Ok(())
 	}
@@ -28,11 +28,11 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 
-use bifrost_primitives::{CurrencyIdConversion, Rate, VtokenMintingInterface};
+use bifrost_primitives::{CurrencyIdConversion, Rate, Ratio, VtokenMintingInterface};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		fungibles::Mutate,
+		fungibles::{Inspect, Mutate},
 		tokens::{Fortitude, Precision, Preservation},
 	},
 	transactional, BoundedVec,
@@ -44,14 +44,53 @@ pub use pallet_traits::{
 };
 pub use parity_scale_codec::{Decode, Encode};
 use sp_runtime::{
-	traits::{CheckedSub, Zero},
-	ArithmeticError, FixedPointNumber, FixedU128, SaturatedConversion,
+	traits::{CheckedSub, One, Zero},
+	ArithmeticError, FixedPointNumber, FixedU128, PerThing, SaturatedConversion,
 };
-use sp_std::{cmp::Ordering, marker::PhantomData};
+use sp_std::{cmp::Ordering, marker::PhantomData, vec, vec::Vec};
 pub use weights::WeightInfo;
 
 use bifrost_stable_pool::traits::StablePoolHandler;
-use lend_market::{AccountDeposits, AccountIdOf, AssetIdOf, BalanceOf};
+use lend_market::{AccountBorrows, AccountDeposits, AccountIdOf, AssetIdOf, BalanceOf};
+use zenlink_protocol::ExportZenlink;
+
+/// The venue where tokens and vtokens are exchanged when changing a leverage rate.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum LeverageRoute {
+	StablePool,
+	/// Only mints vtokens, as redeeming them takes an unlocking period.
+	VtokenMinting,
+	Zenlink,
+}
+
+/// An opt-in stop-loss of a leverage position.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DeleverageGuard {
+	/// The position is deleveraged once its health factor drops below this value.
+	pub min_health_factor: FixedU128,
+	/// The leverage rate of the position after deleveraging.
+	pub target_rate: Rate,
+	/// The maximum slippage of the deleveraging against the vtoken-minting exchange rate.
+	pub max_slippage: Ratio,
+}
+
+/// LeveragePosition contains the leverage staking position of an account on a token, with values
+/// denominated in the token
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct LeveragePosition<Balance> {
+	/// Debt over the net value of the position
+	pub leverage_rate: Rate,
+	/// Value of the vtoken collateral
+	pub collateral_value: Balance,
+	/// Amount borrowed, interest included
+	pub debt: Balance,
+	/// Liquidation threshold value of the collateral over the debt, `None` without debt
+	pub health_factor: Option<FixedU128>,
+	/// Highest leverage rate allowed by the collateral factor of the vtoken market
+	pub max_leverage_rate: Rate,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -83,14 +122,48 @@ pub mod pallet {
 		>;
 
 		type CurrencyIdConversion: CurrencyIdConversion<AssetIdOf<Self>>;
+
+		/// Share of the debt repaid by a deleveraging, charged in the token of the position and
+		/// added to the reserves of its lend-market market.
+		#[pallet::constant]
+		type KeeperFee: Get<Ratio>;
 	}
 
+	/// Deleveraging guards registered by users, kept only while their position has debt.
+	/// AccountId -> CurrencyId -> DeleverageGuard
+	#[pallet::storage]
+	pub type DeleverageGuards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		DeleverageGuard,
+		OptionQuery,
+	>;
+
+	/// Raw storage key of the last guard checked in `on_idle`, the next check resumes after it.
+	#[pallet::storage]
+	pub type DeleverageGuardCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Arguments error, old rate is equal to new rate
 		ArgumentsError,
 		/// Not support token type
 		NotSupportTokenType,
+		/// The minimum health factor of a guard must be greater than one
+		InvalidDeleverageGuard,
+		/// No deleveraging guard is registered for the position
+		DeleverageGuardNotFound,
+		/// The position has no debt to guard
+		PositionWithoutDebt,
+		/// The health factor of the position is above the minimum of its guard
+		PositionHealthy,
+		/// The route cannot exchange the tokens of the position
+		RouteNotSupported,
+		/// The exchange rate of the route exceeds the slippage limit
+		SlippageExceeded,
 	}
 
 	#[pallet::event]
@@ -106,7 +179,49 @@ pub mod pallet {
 			old_rate: Rate,
 			/// The new leverage rate.
 			new_rate: Rate,
+			/// The route where tokens and vtokens have been exchanged.
+			route: LeverageRoute,
+			/// The amount of vtoken exchanged per token.
+			realized_rate: Rate,
+		},
+		/// User's deleveraging guard has been set or removed.
+		DeleverageGuardUpdated {
+			/// Account who owns the position.
+			who: AccountIdOf<T>,
+			/// The asset id of the token.
+			asset_id: AssetIdOf<T>,
+			/// The new guard, `None` if removed.
+			guard: Option<DeleverageGuard>,
 		},
+		/// A position has been deleveraged by its guard.
+		Deleveraged {
+			/// Account who owns the position.
+			who: AccountIdOf<T>,
+			/// The asset id of the token.
+			asset_id: AssetIdOf<T>,
+			/// The old leverage rate.
+			old_rate: Rate,
+			/// The new leverage rate.
+			new_rate: Rate,
+			/// The amount of token added to the lend-market reserves.
+			keeper_fee: BalanceOf<T>,
+		},
+		/// A guarded position could not be deleveraged, its guard has been removed.
+		DeleverageFailed {
+			/// Account who owns the position.
+			who: AccountIdOf<T>,
+			/// The asset id of the token.
+			asset_id: AssetIdOf<T>,
+			/// The error of the deleveraging.
+			error: DispatchError,
+		},
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::check_deleverage_guards(remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -117,16 +232,64 @@ pub mod pallet {
 		///
 		/// - `asset_id`: The asset id of the token
 		/// - `rate`: Leverage rate
+		/// - `route`: Where tokens and vtokens are exchanged, `None` for the best quote
+		/// - `max_slippage`: The maximum slippage against the vtoken-minting exchange rate, net of
+		///   the mint fee
 		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::flash_loan_deposit())]
 		pub fn flash_loan_deposit(
 			origin: OriginFor<T>,
 			asset_id: AssetIdOf<T>,
 			rate: Rate,
+			route: Option<LeverageRoute>,
+			max_slippage: Ratio,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			Pallet::<T>::flash_loan_deposit_inner(who, asset_id, rate)
+			Pallet::<T>::flash_loan_deposit_inner(who, asset_id, rate, route, max_slippage)
+		}
+
+		/// Set or remove the deleveraging guard of a position
+		///
+		/// Once the health factor of the sender drops below `min_health_factor`, the leverage
+		/// rate of the position is reduced to `target_rate` in the idle time of a later block,
+		/// and `KeeperFee` of the repaid debt is added to the lend-market reserves. Only
+		/// positions with debt can be guarded, the guard is removed once the debt is repaid or
+		/// once the position fails to be deleveraged.
+		///
+		/// - `asset_id`: The asset id of the token
+		/// - `guard`: The guard of the position, `None` to remove it
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_deleverage_guard())]
+		pub fn set_deleverage_guard(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			guard: Option<DeleverageGuard>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+				.map_err(|_| Error::<T>::NotSupportTokenType)?;
+
+			match guard {
+				Some(guard) => {
+					ensure!(
+						guard.min_health_factor > FixedU128::one(),
+						Error::<T>::InvalidDeleverageGuard
+					);
+					ensure!(
+						Self::has_debt(&who, asset_id),
+						Error::<T>::PositionWithoutDebt
+					);
+					DeleverageGuards::<T>::insert(&who, asset_id, guard);
+				}
+				None => DeleverageGuards::<T>::remove(&who, asset_id),
+			}
+			Self::deposit_event(Event::<T>::DeleverageGuardUpdated {
+				who,
+				asset_id,
+				guard,
+			});
+			Ok(())
 		}
 	}
 }
@@ -137,6 +300,8 @@ impl<T: Config> Pallet<T> {
 		who: AccountIdOf<T>,
 		asset_id: AssetIdOf<T>,
 		rate: Rate,
+		route: Option<LeverageRoute>,
+		max_slippage: Ratio,
 	) -> DispatchResult {
 		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
 			.map_err(|_| Error::<T>::NotSupportTokenType)?;
@@ -145,33 +310,27 @@ impl<T: Config> Pallet<T> {
 		if !deposits.is_collateral {
 			T::LendMarket::do_collateral_asset(&who, vtoken_id, true)?;
 		}
-		let account_deposits = Self::current_collateral_amount(&who, vtoken_id)?;
-		let account_borrows = lend_market::Pallet::<T>::get_current_borrow_balance(&who, asset_id)?;
+		let (current_rate, base_token_value, account_borrows) =
+			Self::current_leverage_rate(&who, asset_id, vtoken_id)?;
 
-		// Formula
-		// current_rate = account_borrows / (
-		// get_currency_amount_by_v_currency_amount(account_deposits) - account_borrows )
-		let deposits_token_value = T::VtokenMinting::get_currency_amount_by_v_currency_amount(
-			asset_id,
-			vtoken_id,
-			account_deposits,
-		)?;
-		let base_token_value = deposits_token_value
-			.checked_sub(account_borrows)
-			.ok_or(ArithmeticError::Overflow)?;
-		let current_rate = FixedU128::saturating_from_rational(account_borrows, base_token_value);
-
-		match rate.cmp(&current_rate) {
+		let (route, token_amount, vtoken_amount) = match rate.cmp(&current_rate) {
 			Ordering::Less => {
-				let reduce_amount = if rate.is_zero() {
-					account_borrows
-				} else {
-					current_rate
-						.checked_sub(&rate)
-						.and_then(|r| r.checked_mul_int(base_token_value))
-						.ok_or(ArithmeticError::Overflow)?
-				};
-				Self::reduce_leverage(&who, asset_id, vtoken_id, reduce_amount)?;
+				let reduce_amount = Self::calc_reduce_amount(
+					rate,
+					current_rate,
+					base_token_value,
+					account_borrows,
+				)?;
+				let (route, redeem_amount) = Self::reduce_leverage(
+					&who,
+					asset_id,
+					vtoken_id,
+					reduce_amount,
+					Zero::zero(),
+					route,
+					max_slippage,
+				)?;
+				(route, reduce_amount, redeem_amount)
 			}
 			Ordering::Equal => return Err(Error::<T>::ArgumentsError.into()),
 			Ordering::Greater => {
@@ -179,48 +338,294 @@ impl<T: Config> Pallet<T> {
 					.checked_sub(&current_rate)
 					.and_then(|r| r.checked_mul_int(base_token_value))
 					.ok_or(ArithmeticError::Overflow)?;
-				Self::increase_leverage(&who, asset_id, vtoken_id, increase_amount)?;
+				let (route, vtoken_value) = Self::increase_leverage(
+					&who,
+					asset_id,
+					vtoken_id,
+					increase_amount,
+					route,
+					max_slippage,
+				)?;
+				(route, increase_amount, vtoken_value)
 			}
-		}
+		};
 		Self::deposit_event(Event::<T>::FlashLoanDeposited {
 			who,
 			asset_id,
 			old_rate: current_rate,
 			new_rate: rate,
+			route,
+			realized_rate: FixedU128::saturating_from_rational(vtoken_amount, token_amount),
+		});
+		Ok(())
+	}
+
+	/// Deleverages the guarded positions whose health factor is below the minimum of their guard,
+	/// resuming after `DeleverageGuardCursor` while `remaining_weight` allows it. Guards of
+	/// positions without debt, or of positions which fail to be deleveraged, are removed.
+	pub(crate) fn check_deleverage_guards(remaining_weight: Weight) -> Weight {
+		let check_weight = <T as Config>::WeightInfo::check_deleverage_guard();
+		let deleverage_weight = <T as Config>::WeightInfo::deleverage();
+		let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+		let mut guards = match DeleverageGuardCursor::<T>::get() {
+			Some(raw_key) => DeleverageGuards::<T>::iter_from(raw_key),
+			None => DeleverageGuards::<T>::iter(),
+		};
+		loop {
+			// Every check must leave room to deleverage the position
+			let next_weight = used_weight
+				.saturating_add(check_weight)
+				.saturating_add(deleverage_weight);
+			if remaining_weight.any_lt(next_weight) {
+				DeleverageGuardCursor::<T>::put(guards.last_raw_key().to_vec());
+				break;
+			}
+			let Some((who, asset_id, _)) = guards.next() else {
+				DeleverageGuardCursor::<T>::kill();
+				break;
+			};
+			used_weight.saturating_accrue(check_weight);
+			if !Self::has_debt(&who, asset_id) {
+				DeleverageGuards::<T>::remove(&who, asset_id);
+				Self::deposit_event(Event::<T>::DeleverageGuardUpdated {
+					who,
+					asset_id,
+					guard: None,
+				});
+				continue;
+			}
+			match Self::deleverage_inner(who.clone(), asset_id) {
+				Ok(()) => used_weight.saturating_accrue(deleverage_weight),
+				Err(e) if e == DispatchError::from(Error::<T>::PositionHealthy) => {}
+				Err(error) => {
+					used_weight.saturating_accrue(deleverage_weight);
+					// A failing position would be retried in every sweep, the owner has to set
+					// a new guard instead
+					DeleverageGuards::<T>::remove(&who, asset_id);
+					Self::deposit_event(Event::<T>::DeleverageFailed {
+						who,
+						asset_id,
+						error,
+					});
+				}
+			}
+		}
+		used_weight
+	}
+
+	fn has_debt(who: &AccountIdOf<T>, asset_id: AssetIdOf<T>) -> bool {
+		!AccountBorrows::<T>::get(asset_id, who).principal.is_zero()
+	}
+
+	#[transactional]
+	pub fn deleverage_inner(who: AccountIdOf<T>, asset_id: AssetIdOf<T>) -> DispatchResult {
+		let guard = DeleverageGuards::<T>::get(&who, asset_id)
+			.ok_or(Error::<T>::DeleverageGuardNotFound)?;
+		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+			.map_err(|_| Error::<T>::NotSupportTokenType)?;
+
+		let health_factor = lend_market::Pallet::<T>::get_account_position(&who)?.health_factor;
+		ensure!(
+			health_factor.is_some_and(|health_factor| health_factor < guard.min_health_factor),
+			Error::<T>::PositionHealthy
+		);
+
+		let (current_rate, base_token_value, account_borrows) =
+			Self::current_leverage_rate(&who, asset_id, vtoken_id)?;
+		ensure!(guard.target_rate < current_rate, Error::<T>::ArgumentsError);
+		let reduce_amount = Self::calc_reduce_amount(
+			guard.target_rate,
+			current_rate,
+			base_token_value,
+			account_borrows,
+		)?;
+		// The keeper fee replaces the liquidation incentive of lend-market
+		let keeper_fee = T::KeeperFee::get().mul_floor(reduce_amount);
+		Self::reduce_leverage(
+			&who,
+			asset_id,
+			vtoken_id,
+			reduce_amount,
+			keeper_fee,
+			None,
+			guard.max_slippage,
+		)?;
+
+		Self::deposit_event(Event::<T>::Deleveraged {
+			who,
+			asset_id,
+			old_rate: current_rate,
+			new_rate: guard.target_rate,
+			keeper_fee,
 		});
 		Ok(())
 	}
 
+	/// Returns the leverage position of `who` on `asset_id`.
+	pub fn get_leverage_position(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+	) -> Result<LeveragePosition<BalanceOf<T>>, DispatchError> {
+		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+			.map_err(|_| Error::<T>::NotSupportTokenType)?;
+		let (current_rate, base_token_value, account_borrows) =
+			Self::current_leverage_rate(who, asset_id, vtoken_id)?;
+
+		Self::leverage_position(vtoken_id, current_rate, base_token_value, account_borrows)
+	}
+
+	/// Returns the leverage position of `who` on `asset_id` once its leverage rate is changed to
+	/// `rate`, assuming tokens and vtokens are exchanged at the vtoken-minting rate.
+	pub fn simulate_leverage_position(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		rate: Rate,
+	) -> Result<LeveragePosition<BalanceOf<T>>, DispatchError> {
+		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+			.map_err(|_| Error::<T>::NotSupportTokenType)?;
+		let (_, base_token_value, _) = Self::current_leverage_rate(who, asset_id, vtoken_id)?;
+		let account_borrows = rate
+			.checked_mul_int(base_token_value)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		Self::leverage_position(vtoken_id, rate, base_token_value, account_borrows)
+	}
+
+	fn leverage_position(
+		vtoken_id: AssetIdOf<T>,
+		rate: Rate,
+		base_token_value: BalanceOf<T>,
+		account_borrows: BalanceOf<T>,
+	) -> Result<LeveragePosition<BalanceOf<T>>, DispatchError> {
+		let market = lend_market::Pallet::<T>::market(vtoken_id)?;
+		let collateral_value = base_token_value
+			.checked_add(account_borrows)
+			.ok_or(ArithmeticError::Overflow)?;
+		let health_factor = (!account_borrows.is_zero()).then(|| {
+			FixedU128::saturating_from_rational(
+				market.liquidation_threshold.mul_floor(collateral_value),
+				account_borrows,
+			)
+		});
+		// Formula
+		// account_borrows <= collateral_factor * (base_token_value + account_borrows)
+		// max_leverage_rate = collateral_factor / (1 - collateral_factor)
+		let max_leverage_rate = FixedU128::saturating_from_rational(
+			market.collateral_factor.deconstruct(),
+			market.collateral_factor.left_from_one().deconstruct(),
+		);
+
+		Ok(LeveragePosition {
+			leverage_rate: rate,
+			collateral_value,
+			debt: account_borrows,
+			health_factor,
+			max_leverage_rate,
+		})
+	}
+
+	// Returns the leverage rate, the net token value and the borrows of a position.
+	fn current_leverage_rate(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+	) -> Result<(Rate, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let account_deposits = Self::current_collateral_amount(who, vtoken_id)?;
+		let account_borrows = lend_market::Pallet::<T>::get_current_borrow_balance(who, asset_id)?;
+
+		// Formula
+		// current_rate = account_borrows / (
+		// get_currency_amount_by_v_currency_amount(account_deposits) - account_borrows )
+		let deposits_token_value = T::VtokenMinting::get_currency_amount_by_v_currency_amount(
+			asset_id,
+			vtoken_id,
+			account_deposits,
+		)?;
+		let base_token_value = deposits_token_value
+			.checked_sub(account_borrows)
+			.ok_or(ArithmeticError::Overflow)?;
+		let current_rate = FixedU128::saturating_from_rational(account_borrows, base_token_value);
+
+		Ok((current_rate, base_token_value, account_borrows))
+	}
+
+	fn calc_reduce_amount(
+		rate: Rate,
+		current_rate: Rate,
+		base_token_value: BalanceOf<T>,
+		account_borrows: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		if rate.is_zero() {
+			return Ok(account_borrows);
+		}
+		let reduce_amount = current_rate
+			.checked_sub(&rate)
+			.and_then(|r| r.checked_mul_int(base_token_value))
+			.ok_or(ArithmeticError::Overflow)?;
+		Ok(reduce_amount)
+	}
+
+	// Returns the route and the amount of vtoken redeemed to repay `reduce_amount` and add
+	// `fee` to the lend-market reserves.
 	fn reduce_leverage(
 		who: &T::AccountId,
 		asset_id: AssetIdOf<T>,
 		vtoken_id: AssetIdOf<T>,
 		reduce_amount: BalanceOf<T>,
-	) -> DispatchResult {
-		let (pool_id, currency_id_in, currency_id_out) =
-			T::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
-				.ok_or(Error::<T>::NotSupportTokenType)?;
+		fee: BalanceOf<T>,
+		route: Option<LeverageRoute>,
+		max_slippage: Ratio,
+	) -> Result<(LeverageRoute, BalanceOf<T>), DispatchError> {
+		let token_amount = reduce_amount
+			.checked_add(fee)
+			.ok_or(ArithmeticError::Overflow)?;
+		let route = match route {
+			Some(route) => route,
+			None => Self::best_redeem_route(asset_id, vtoken_id, token_amount)?,
+		};
+		let redeem_amount = Self::quote_redeem(route, asset_id, vtoken_id, token_amount)?;
+		let fair_amount = T::VtokenMinting::get_v_currency_amount_by_currency_amount(
+			asset_id,
+			vtoken_id,
+			token_amount,
+		)?;
+		ensure!(
+			redeem_amount <= fair_amount.saturating_add(max_slippage.mul_floor(fair_amount)),
+			Error::<T>::SlippageExceeded
+		);
 
 		<T as lend_market::Config>::Assets::mint_into(asset_id, &who, reduce_amount)?;
 
 		T::LendMarket::do_repay_borrow(&who, asset_id, reduce_amount)?;
-		let redeem_amount = T::StablePoolHandler::get_swap_input(
-			pool_id,
-			currency_id_in,
-			currency_id_out,
-			reduce_amount,
-		)?;
 		// Do redeem
 		T::LendMarket::do_redeem(&who, vtoken_id, redeem_amount)?;
 
-		T::StablePoolHandler::swap(
-			&who,
-			pool_id,
-			currency_id_in,
-			currency_id_out,
-			redeem_amount,
-			reduce_amount,
-		)?;
+		match route {
+			LeverageRoute::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					<T as Config>::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
+						.ok_or(Error::<T>::RouteNotSupported)?;
+				<T as Config>::StablePoolHandler::swap(
+					&who,
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					redeem_amount,
+					token_amount,
+				)?;
+			}
+			LeverageRoute::Zenlink => {
+				let path = Self::zenlink_path(vtoken_id, asset_id)?;
+				<T as lend_market::Config>::DexOperator::inner_swap_assets_for_exact_assets(
+					who,
+					token_amount,
+					redeem_amount,
+					&path,
+					who,
+				)?;
+			}
+			LeverageRoute::VtokenMinting => return Err(Error::<T>::RouteNotSupported.into()),
+		}
 		<T as lend_market::Config>::Assets::burn_from(
 			asset_id,
 			&who,
@@ -229,23 +634,80 @@ impl<T: Config> Pallet<T> {
 			Precision::Exact,
 			Fortitude::Force,
 		)?;
-		Ok(())
+		if !fee.is_zero() {
+			lend_market::Pallet::<T>::do_add_reserves(who, asset_id, fee)?;
+		}
+		Ok((route, redeem_amount))
 	}
 
+	// Returns the route and the amount of vtoken obtained with `increase_amount`.
 	fn increase_leverage(
 		who: &T::AccountId,
 		asset_id: AssetIdOf<T>,
 		vtoken_id: AssetIdOf<T>,
 		increase_amount: BalanceOf<T>,
-	) -> DispatchResult {
-		<T as lend_market::Config>::Assets::mint_into(asset_id, &who, increase_amount)?;
-		let vtoken_value = T::VtokenMinting::mint(
-			who.clone(),
+		route: Option<LeverageRoute>,
+		max_slippage: Ratio,
+	) -> Result<(LeverageRoute, BalanceOf<T>), DispatchError> {
+		let route = match route {
+			Some(route) => route,
+			None => Self::best_mint_route(asset_id, vtoken_id, increase_amount)?,
+		};
+		// The slippage is measured against minting net of its fee, as routes are compared
+		let fair_amount = Self::quote_mint(
+			LeverageRoute::VtokenMinting,
 			asset_id,
+			vtoken_id,
 			increase_amount,
-			BoundedVec::default(),
-			None,
 		)?;
+		let min_vtoken_value = fair_amount.saturating_sub(max_slippage.mul_ceil(fair_amount));
+
+		<T as lend_market::Config>::Assets::mint_into(asset_id, &who, increase_amount)?;
+		let vtoken_value = match route {
+			LeverageRoute::VtokenMinting => T::VtokenMinting::mint(
+				who.clone(),
+				asset_id,
+				increase_amount,
+				BoundedVec::default(),
+				None,
+			)?,
+			LeverageRoute::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					<T as Config>::StablePoolHandler::get_pool_id(&asset_id, &vtoken_id)
+						.ok_or(Error::<T>::RouteNotSupported)?;
+				let vtoken_balance = <T as lend_market::Config>::Assets::balance(vtoken_id, who);
+				<T as Config>::StablePoolHandler::swap(
+					&who,
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					increase_amount,
+					min_vtoken_value,
+				)?;
+				<T as lend_market::Config>::Assets::balance(vtoken_id, who)
+					.checked_sub(vtoken_balance)
+					.ok_or(ArithmeticError::Underflow)?
+			}
+			LeverageRoute::Zenlink => {
+				let path = Self::zenlink_path(asset_id, vtoken_id)?;
+				let vtoken_balance = <T as lend_market::Config>::Assets::balance(vtoken_id, who);
+				<T as lend_market::Config>::DexOperator::inner_swap_exact_assets_for_assets(
+					who,
+					increase_amount,
+					min_vtoken_value,
+					&path,
+					who,
+				)?;
+				<T as lend_market::Config>::Assets::balance(vtoken_id, who)
+					.checked_sub(vtoken_balance)
+					.ok_or(ArithmeticError::Underflow)?
+			}
+		};
+		ensure!(
+			vtoken_value >= min_vtoken_value,
+			Error::<T>::SlippageExceeded
+		);
+
 		T::LendMarket::do_mint(&who, vtoken_id, vtoken_value)?;
 		T::LendMarket::do_borrow(&who, asset_id, increase_amount)?;
 		<T as lend_market::Config>::Assets::burn_from(
@@ -256,7 +718,128 @@ impl<T: Config> Pallet<T> {
 			Precision::Exact,
 			Fortitude::Force,
 		)?;
-		Ok(())
+		Ok((route, vtoken_value))
+	}
+
+	// Returns the amount of vtoken obtained with `token_amount` through `route`.
+	fn quote_mint(
+		route: LeverageRoute,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match route {
+			LeverageRoute::VtokenMinting => {
+				let mint_fee = T::VtokenMinting::get_mint_fee(token_amount);
+				T::VtokenMinting::get_v_currency_amount_by_currency_amount(
+					asset_id,
+					vtoken_id,
+					token_amount.saturating_sub(mint_fee),
+				)
+			}
+			LeverageRoute::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					<T as Config>::StablePoolHandler::get_pool_id(&asset_id, &vtoken_id)
+						.ok_or(Error::<T>::RouteNotSupported)?;
+				<T as Config>::StablePoolHandler::get_swap_output(
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					token_amount,
+				)
+			}
+			LeverageRoute::Zenlink => {
+				let path = Self::zenlink_path(asset_id, vtoken_id)?;
+				<T as lend_market::Config>::DexOperator::get_amount_out_by_path(
+					token_amount,
+					&path,
+				)?
+				.last()
+				.copied()
+				.ok_or(Error::<T>::RouteNotSupported.into())
+			}
+		}
+	}
+
+	// Returns the amount of vtoken spent to obtain `token_amount` through `route`.
+	fn quote_redeem(
+		route: LeverageRoute,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match route {
+			LeverageRoute::VtokenMinting => Err(Error::<T>::RouteNotSupported.into()),
+			LeverageRoute::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					<T as Config>::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
+						.ok_or(Error::<T>::RouteNotSupported)?;
+				<T as Config>::StablePoolHandler::get_swap_input(
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					token_amount,
+				)
+			}
+			LeverageRoute::Zenlink => {
+				let path = Self::zenlink_path(vtoken_id, asset_id)?;
+				<T as lend_market::Config>::DexOperator::get_amount_in_by_path(token_amount, &path)?
+					.first()
+					.copied()
+					.ok_or(Error::<T>::RouteNotSupported.into())
+			}
+		}
+	}
+
+	// The route giving the most vtoken.
+	fn best_mint_route(
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<LeverageRoute, DispatchError> {
+		[
+			LeverageRoute::StablePool,
+			LeverageRoute::VtokenMinting,
+			LeverageRoute::Zenlink,
+		]
+		.into_iter()
+		.filter_map(|route| {
+			Self::quote_mint(route, asset_id, vtoken_id, token_amount)
+				.ok()
+				.map(|amount| (route, amount))
+		})
+		.max_by_key(|(_, amount)| *amount)
+		.map(|(route, _)| route)
+		.ok_or(Error::<T>::RouteNotSupported.into())
+	}
+
+	// The route spending the least vtoken.
+	fn best_redeem_route(
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<LeverageRoute, DispatchError> {
+		[LeverageRoute::StablePool, LeverageRoute::Zenlink]
+			.into_iter()
+			.filter_map(|route| {
+				Self::quote_redeem(route, asset_id, vtoken_id, token_amount)
+					.ok()
+					.filter(|amount| !amount.is_zero())
+					.map(|amount| (route, amount))
+			})
+			.min_by_key(|(_, amount)| *amount)
+			.map(|(route, _)| route)
+			.ok_or(Error::<T>::RouteNotSupported.into())
+	}
+
+	fn zenlink_path(
+		asset_in: AssetIdOf<T>,
+		asset_out: AssetIdOf<T>,
+	) -> Result<Vec<zenlink_protocol::AssetId>, DispatchError> {
+		Ok(vec![
+			lend_market::Pallet::<T>::zenlink_asset_id(asset_in)?,
+			lend_market::Pallet::<T>::zenlink_asset_id(asset_out)?,
+		])
 	}
 
 	fn current_collateral_amount(
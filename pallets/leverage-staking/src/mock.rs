@@ -25,8 +25,10 @@ pub use bifrost_primitives::{
 use bifrost_primitives::{
 	BifrostEntranceAccount, BifrostExitAccount, IncentivePoolAccount, LendMarketPalletId, Moment,
 	MoonbeamChainId, OraclePriceProvider, Price, PriceDetail, Ratio, StableAssetPalletId,
+	ZenlinkPalletId,
 };
 use bifrost_runtime_common::milli;
+use cumulus_primitives_core::ParaId;
 use frame_support::{
 	derive_impl, ord_parameter_types, parameter_types,
 	traits::{ConstU128, ConstU32, Everything, Nothing},
@@ -35,9 +37,13 @@ use frame_system::{EnsureRoot, EnsureSignedBy};
 use lend_market::{InterestRateModel, JumpModel, Market, MarketState};
 use orml_traits::{
 	location::RelativeReserveProvider, parameter_type_with_key, DataFeeder, DataProvider,
-	DataProviderExtended,
+	DataProviderExtended, MultiCurrency,
 };
-use sp_runtime::{traits::IdentityLookup, BuildStorage, FixedPointNumber};
+use sp_runtime::{
+	traits::{IdentityLookup, SaturatedConversion, UniqueSaturatedInto},
+	BuildStorage, DispatchError, DispatchResult, FixedPointNumber,
+};
+use sp_std::marker::PhantomData;
 use std::{
 	cell::RefCell,
 	collections::HashMap,
@@ -46,6 +52,9 @@ use std::{
 use xcm::{prelude::*, v3::Weight};
 use xcm_builder::{FixedWeightBounds, FrameTransactionalProcessor};
 use xcm_executor::XcmExecutor;
+use zenlink_protocol::{
+	AssetBalance, AssetId as ZenlinkAssetId, LocalAssetHandler, PairLpGenerate, ZenlinkMultiAssets,
+};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -66,6 +75,7 @@ frame_support::construct_runtime!(
 		TimestampPallet: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		LeverageStaking: leverage_staking::{Pallet, Storage, Call, Event<T>},
 		Prices: pallet_prices::{Pallet, Storage, Call, Event<T>},
+		ZenlinkProtocol: zenlink_protocol,
 		// PolkadotXcm: pallet_xcm,
 	}
 );
@@ -266,6 +276,10 @@ impl bifrost_stable_pool::Config for Test {
 	type CurrencyIdRegister = AssetIdMaps<Test>;
 }
 
+parameter_types! {
+	pub const KeeperFee: Ratio = Ratio::from_percent(1);
+}
+
 impl leverage_staking::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
@@ -274,6 +288,7 @@ impl leverage_staking::Config for Test {
 	type LendMarket = LendMarket;
 	type StablePoolHandler = StablePool;
 	type CurrencyIdConversion = AssetIdMaps<Test>;
+	type KeeperFee = KeeperFee;
 }
 
 parameter_types! {
@@ -305,6 +320,7 @@ impl bifrost_vtoken_minting::Config for Test {
 	type RelayChainToken = RelayCurrencyId;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = ();
 	type XcmTransfer = XTokens;
 	type MoonbeamChainId = MoonbeamChainId;
 	type BifrostSlpx = SlpxInterface;
@@ -437,10 +453,95 @@ impl OraclePriceProvider for MockOraclePriceProvider {
 	}
 }
 
+parameter_types! {
+	pub const SelfParaId: u32 = 2001;
+}
+
+impl zenlink_protocol::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkPalletId;
+	type SelfParaId = SelfParaId;
+	type TargetChains = ();
+	type WeightInfo = ();
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PairLpGenerate<Self>;
+}
+
+type MultiAssets = ZenlinkMultiAssets<ZenlinkProtocol, Balances, LocalAssetAdaptor<Currencies>>;
+
+// Below is the implementation of tokens manipulation functions other than native token.
+pub struct LocalAssetAdaptor<Local>(PhantomData<Local>);
+
+impl<Local, AccountId> LocalAssetHandler<AccountId> for LocalAssetAdaptor<Local>
+where
+	Local: MultiCurrency<AccountId, CurrencyId = CurrencyId>,
+{
+	fn local_balance_of(asset_id: ZenlinkAssetId, who: &AccountId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::free_balance(currency_id, &who).saturated_into()
+	}
+
+	fn local_total_supply(asset_id: ZenlinkAssetId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::total_issuance(currency_id).saturated_into()
+	}
+
+	fn local_is_exists(asset_id: ZenlinkAssetId) -> bool {
+		let rs: Result<CurrencyId, _> = asset_id.try_into();
+		rs.is_ok()
+	}
+
+	fn local_transfer(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		target: &AccountId,
+		amount: AssetBalance,
+	) -> DispatchResult {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::transfer(
+			currency_id,
+			&origin,
+			&target,
+			amount.unique_saturated_into(),
+		)
+	}
+
+	fn local_deposit(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::deposit(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+
+	fn local_withdraw(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::withdraw(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+}
+
+pub struct ParachainId;
+impl Get<ParaId> for ParachainId {
+	fn get() -> ParaId {
+		ParaId::from(2001)
+	}
+}
+
 parameter_types! {
 	pub const RewardAssetId: CurrencyId = BNC;
 	pub const LiquidationFreeAssetId: CurrencyId = DOT;
 	pub const MaxLengthLimit: u32 = 500;
+	pub const DustCollateralValue: Balance = 1_000_000_000_000;
+	pub const FlashLoanFee: Ratio = Ratio::from_parts(900);
+	pub const MaxFlashLoanCalls: u32 = 10;
 }
 
 impl lend_market::Config for Test {
@@ -449,6 +550,7 @@ impl lend_market::Config for Test {
 	type PalletId = LendMarketPalletId;
 	type ReserveOrigin = EnsureRoot<u128>;
 	type UpdateOrigin = EnsureRoot<u128>;
+	type ControlOrigin = EnsureRoot<u128>;
 	type WeightInfo = ();
 	type UnixTime = TimestampPallet;
 	type Assets = Currencies;
@@ -456,6 +558,13 @@ impl lend_market::Config for Test {
 	type LiquidationFreeAssetId = LiquidationFreeAssetId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanFee = FlashLoanFee;
+	type MaxFlashLoanCalls = MaxFlashLoanCalls;
+	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = StablePool;
+	type ParachainId = ParachainId;
+	type DustCollateralValue = DustCollateralValue;
 }
 
 impl pallet_prices::Config for Test {
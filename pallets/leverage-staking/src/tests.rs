@@ -17,9 +17,19 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 #![cfg(test)]
 
-use crate::mock::*;
-use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect, BoundedVec};
-use lend_market::{AccountBorrows, BorrowSnapshot, Deposits};
+use crate::{
+	mock::*, weights::WeightInfo, DeleverageGuard, DeleverageGuardCursor, DeleverageGuards, Error,
+	Event, LeveragePosition, LeverageRoute,
+};
+use bifrost_primitives::Ratio;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungibles::Inspect, Get, Hooks},
+	weights::Weight,
+	BoundedVec,
+};
+use lend_market::{AccountBorrows, BorrowSnapshot, Deposits, FlashLoans, TotalReserves};
+use sp_runtime::{PerThing, Permill};
 
 fn init() {
 	env_logger::try_init().unwrap_or(());
@@ -105,6 +115,8 @@ fn increase_leverage_should_not_work() {
 					RuntimeOrigin::signed(1),
 					DOT,
 					FixedU128::from_inner(unit(1_000_100)),
+					Some(LeverageRoute::StablePool),
+					Ratio::from_percent(5),
 				),
 				lend_market::Error::<Test>::InsufficientLiquidity
 			);
@@ -122,6 +134,8 @@ fn increase_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(100_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
 			));
 			assert_eq!(
 				AccountDeposits::<Test>::get(VDOT, 1),
@@ -141,6 +155,8 @@ fn increase_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(800_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
 			));
 			assert_eq!(
 				AccountDeposits::<Test>::get(VDOT, 1),
@@ -160,6 +176,8 @@ fn increase_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(900_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
 			));
 			assert_eq!(
 				AccountBorrows::<Test>::get(DOT, 1),
@@ -197,6 +215,8 @@ fn reduce_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(900_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
 			));
 			assert_eq!(
 				AccountBorrows::<Test>::get(DOT, 1),
@@ -218,6 +238,8 @@ fn reduce_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(800_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
 			));
 			assert_eq!(Tokens::balance(VDOT, &1), 9999999900000);
 			assert_eq!(Tokens::balance(DOT, &1), 990000000000098);
@@ -239,6 +261,8 @@ fn reduce_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(0),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
 			));
 			assert_eq!(Tokens::balance(VDOT, &1), 9999999900000);
 			assert_eq!(Tokens::balance(DOT, &1), 990000000000196);
@@ -258,3 +282,455 @@ fn reduce_leverage_should_work() {
 			);
 		});
 }
+
+#[test]
+fn flash_loan_deposit_reports_route_and_realized_rate() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(100_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
+			));
+			System::assert_last_event(RuntimeEvent::LeverageStaking(Event::FlashLoanDeposited {
+				who: 1,
+				asset_id: DOT,
+				old_rate: FixedU128::from_inner(0),
+				new_rate: FixedU128::from_inner(unit(100_000)),
+				route: LeverageRoute::StablePool,
+				realized_rate: FixedU128::from_inner(unit(1_000_000)),
+			}));
+		});
+}
+
+#[test]
+fn vtoken_minting_quote_should_be_net_of_mint_fee() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let quote = |amount| {
+				LeverageStaking::quote_mint(LeverageRoute::VtokenMinting, DOT, VDOT, amount)
+			};
+			let net_quote = quote(9_000);
+			assert_ok!(VtokenMinting::set_fees(
+				RuntimeOrigin::root(),
+				Permill::from_percent(10),
+				Permill::zero()
+			));
+			assert_eq!(quote(10_000), net_quote);
+
+			// The mint fee exceeds the maximum slippage, but the slippage is net of the fee
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(500_000)),
+				Some(LeverageRoute::VtokenMinting),
+				Ratio::from_percent(5),
+			));
+		});
+}
+
+#[test]
+fn reduce_leverage_should_respect_slippage_and_route() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(900_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
+			));
+			// Redeeming takes an unlocking period, so vtoken-minting can only mint
+			assert_noop!(
+				LeverageStaking::flash_loan_deposit(
+					RuntimeOrigin::signed(1),
+					DOT,
+					FixedU128::from_inner(unit(800_000)),
+					Some(LeverageRoute::VtokenMinting),
+					Ratio::from_percent(5),
+				),
+				Error::<Test>::RouteNotSupported
+			);
+			// The stable pool asks 10_119 vDOT for 10_000 DOT
+			assert_noop!(
+				LeverageStaking::flash_loan_deposit(
+					RuntimeOrigin::signed(1),
+					DOT,
+					FixedU128::from_inner(unit(800_000)),
+					Some(LeverageRoute::StablePool),
+					Ratio::from_percent(1),
+				),
+				Error::<Test>::SlippageExceeded
+			);
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(800_000)),
+				None,
+				Ratio::from_percent(2),
+			));
+			assert_eq!(
+				AccountBorrows::<Test>::get(DOT, 1),
+				BorrowSnapshot {
+					principal: 80_000,
+					borrow_index: 1.into()
+				},
+			);
+		});
+}
+
+#[test]
+fn leverage_position_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(900_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
+			));
+			// Max leverage rate = 50% / (1 - 50%) = 1
+			assert_eq!(
+				LeverageStaking::get_leverage_position(&1, DOT),
+				Ok(LeveragePosition {
+					leverage_rate: FixedU128::from_inner(unit(900_000)),
+					collateral_value: 190_000,
+					debt: 90_000,
+					health_factor: Some(FixedU128::saturating_from_rational(104_500, 90_000)),
+					max_leverage_rate: FixedU128::from_inner(unit(1_000_000)),
+				})
+			);
+			assert_eq!(
+				LeverageStaking::simulate_leverage_position(
+					&1,
+					DOT,
+					FixedU128::from_inner(unit(500_000))
+				),
+				Ok(LeveragePosition {
+					leverage_rate: FixedU128::from_inner(unit(500_000)),
+					collateral_value: 150_000,
+					debt: 50_000,
+					health_factor: Some(FixedU128::saturating_from_rational(82_500, 50_000)),
+					max_leverage_rate: FixedU128::from_inner(unit(1_000_000)),
+				})
+			);
+			assert_noop!(
+				LeverageStaking::get_leverage_position(&1, VDOT),
+				Error::<Test>::NotSupportTokenType
+			);
+		});
+}
+
+#[test]
+fn deleverage_guard_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let guard = DeleverageGuard {
+				min_health_factor: FixedU128::from_inner(unit(1_100_000)),
+				target_rate: FixedU128::from_inner(unit(800_000)),
+				max_slippage: Ratio::from_percent(5),
+			};
+			assert_noop!(
+				LeverageStaking::set_deleverage_guard(RuntimeOrigin::signed(1), DOT, Some(guard)),
+				Error::<Test>::PositionWithoutDebt
+			);
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(900_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
+			));
+			assert_noop!(
+				LeverageStaking::deleverage_inner(1, DOT),
+				Error::<Test>::DeleverageGuardNotFound
+			);
+			assert_noop!(
+				LeverageStaking::set_deleverage_guard(
+					RuntimeOrigin::signed(1),
+					DOT,
+					Some(DeleverageGuard {
+						min_health_factor: FixedU128::from_inner(unit(1_000_000)),
+						target_rate: FixedU128::from_inner(unit(800_000)),
+						max_slippage: Ratio::from_percent(5),
+					}),
+				),
+				Error::<Test>::InvalidDeleverageGuard
+			);
+
+			// Health factor = 190_000 * 55% / 90_000 = 1.16
+			assert_ok!(LeverageStaking::set_deleverage_guard(
+				RuntimeOrigin::signed(1),
+				DOT,
+				Some(DeleverageGuard {
+					min_health_factor: FixedU128::from_inner(unit(1_100_000)),
+					target_rate: FixedU128::from_inner(unit(800_000)),
+					max_slippage: Ratio::from_percent(5),
+				}),
+			));
+			LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+			assert_eq!(
+				AccountBorrows::<Test>::get(DOT, 1),
+				BorrowSnapshot {
+					principal: 90_000,
+					borrow_index: 1.into()
+				},
+			);
+			assert_eq!(DeleverageGuardCursor::<Test>::get(), None);
+
+			assert_ok!(LeverageStaking::set_deleverage_guard(
+				RuntimeOrigin::signed(1),
+				DOT,
+				Some(DeleverageGuard {
+					min_health_factor: FixedU128::from_inner(unit(1_200_000)),
+					target_rate: FixedU128::from_inner(unit(800_000)),
+					max_slippage: Ratio::from_percent(5),
+				}),
+			));
+			// No room left to deleverage a position
+			let reserves = TotalReserves::<Test>::get(DOT);
+			LeverageStaking::on_idle(
+				System::block_number(),
+				<Test as crate::Config>::WeightInfo::deleverage(),
+			);
+			assert_eq!(TotalReserves::<Test>::get(DOT), reserves);
+
+			LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+			assert_eq!(
+				AccountBorrows::<Test>::get(DOT, 1),
+				BorrowSnapshot {
+					principal: 80_000,
+					borrow_index: 1.into()
+				},
+			);
+			// 1% of the 10_000 DOT repaid is added to the reserves
+			assert_eq!(TotalReserves::<Test>::get(DOT), reserves + 100);
+			assert!(AccountDeposits::<Test>::get(VDOT, 1).voucher_balance < 9_000_000);
+			assert!(matches!(
+				System::events().last().map(|record| &record.event),
+				Some(RuntimeEvent::LeverageStaking(Event::Deleveraged {
+					who: 1,
+					asset_id: DOT,
+					keeper_fee: 100,
+					..
+				}))
+			));
+
+			assert_ok!(LeverageStaking::set_deleverage_guard(
+				RuntimeOrigin::signed(1),
+				DOT,
+				None,
+			));
+			assert!(!DeleverageGuards::<Test>::contains_key(1, DOT));
+
+			// The guard is removed once the debt of the position is repaid
+			assert_ok!(LeverageStaking::set_deleverage_guard(
+				RuntimeOrigin::signed(1),
+				DOT,
+				Some(guard),
+			));
+			assert_ok!(LendMarket::repay_borrow_all(RuntimeOrigin::signed(1), DOT));
+			LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+			assert!(!DeleverageGuards::<Test>::contains_key(1, DOT));
+		});
+}
+
+fn flash_loan_fee(amount: u128) -> u128 {
+	FlashLoanFee::get().mul_ceil(amount)
+}
+
+#[test]
+fn flash_loan_into_stable_pool_swap_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let dot_balance = Tokens::balance(DOT, &1);
+			let vdot_balance = Tokens::balance(VDOT, &1);
+			let reserves = TotalReserves::<Test>::get(DOT);
+
+			assert_ok!(LendMarket::flash_loan(
+				RuntimeOrigin::signed(1),
+				DOT,
+				unit(10),
+				vec![RuntimeCall::StablePool(bifrost_stable_pool::Call::swap {
+					pool_id: 0,
+					i: 0,
+					j: 1,
+					dx: unit(10),
+					min_dy: 0,
+				})]
+				.try_into()
+				.unwrap()
+			));
+
+			// The swap is paid with the loan, the loan and its fee with the own DOT of the sender
+			assert_eq!(
+				Tokens::balance(DOT, &1),
+				dot_balance - unit(10) - flash_loan_fee(unit(10))
+			);
+			assert!(Tokens::balance(VDOT, &1) > vdot_balance);
+			assert_eq!(
+				TotalReserves::<Test>::get(DOT),
+				reserves + flash_loan_fee(unit(10))
+			);
+			assert_eq!(FlashLoans::<Test>::get(DOT), 0);
+		});
+}
+
+#[test]
+fn flash_loan_into_vtoken_minting_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let dot_balance = Tokens::balance(DOT, &1);
+			let vdot_balance = Tokens::balance(VDOT, &1);
+
+			assert_ok!(LendMarket::flash_loan(
+				RuntimeOrigin::signed(1),
+				DOT,
+				unit(10),
+				vec![RuntimeCall::VtokenMinting(
+					bifrost_vtoken_minting::Call::mint {
+						currency_id: DOT,
+						currency_amount: unit(10),
+						remark: BoundedVec::default(),
+						channel_id: None,
+					}
+				)]
+				.try_into()
+				.unwrap()
+			));
+
+			assert_eq!(
+				Tokens::balance(DOT, &1),
+				dot_balance - unit(10) - flash_loan_fee(unit(10))
+			);
+			assert_eq!(Tokens::balance(VDOT, &1), vdot_balance + unit(10));
+			assert_eq!(FlashLoans::<Test>::get(DOT), 0);
+
+			// Minting more than the own DOT of the sender cannot repay the loan
+			assert_noop!(
+				LendMarket::flash_loan(
+					RuntimeOrigin::signed(1),
+					DOT,
+					unit(50),
+					vec![RuntimeCall::VtokenMinting(
+						bifrost_vtoken_minting::Call::mint {
+							currency_id: DOT,
+							currency_amount: Tokens::balance(DOT, &1) + unit(50),
+							remark: BoundedVec::default(),
+							channel_id: None,
+						}
+					)]
+					.try_into()
+					.unwrap()
+				),
+				lend_market::Error::<Test>::FlashLoanNotRepaid
+			);
+		});
+}
+
+#[test]
+fn flash_loan_repaid_by_mint_and_swap_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let dot_balance = Tokens::balance(DOT, &3);
+			let reserves = TotalReserves::<Test>::get(DOT);
+			assert_eq!(Tokens::balance(VDOT, &3), 0);
+
+			// The loan is minted into vDOT and swapped back to DOT in the same call
+			assert_ok!(LendMarket::flash_loan(
+				RuntimeOrigin::signed(3),
+				DOT,
+				unit(10),
+				vec![
+					RuntimeCall::VtokenMinting(bifrost_vtoken_minting::Call::mint {
+						currency_id: DOT,
+						currency_amount: unit(10),
+						remark: BoundedVec::default(),
+						channel_id: None,
+					}),
+					RuntimeCall::StablePool(bifrost_stable_pool::Call::swap {
+						pool_id: 0,
+						i: 1,
+						j: 0,
+						dx: unit(10),
+						min_dy: 0,
+					}),
+				]
+				.try_into()
+				.unwrap()
+			));
+
+			// The swapped DOT repays the loan, the sender only pays the fee and the swap cost
+			assert_eq!(Tokens::balance(VDOT, &3), 0);
+			let cost = dot_balance - Tokens::balance(DOT, &3);
+			assert!(cost > flash_loan_fee(unit(10)) && cost < unit(1));
+			assert_eq!(
+				TotalReserves::<Test>::get(DOT),
+				reserves + flash_loan_fee(unit(10))
+			);
+			assert_eq!(FlashLoans::<Test>::get(DOT), 0);
+		});
+}
+
+#[test]
+fn deleverage_guard_is_removed_when_deleverage_fails() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(900_000)),
+				Some(LeverageRoute::StablePool),
+				Ratio::from_percent(5),
+			));
+			// The target rate is above the current leverage rate of 0.9
+			assert_ok!(LeverageStaking::set_deleverage_guard(
+				RuntimeOrigin::signed(1),
+				DOT,
+				Some(DeleverageGuard {
+					min_health_factor: FixedU128::from_inner(unit(1_200_000)),
+					target_rate: FixedU128::from_inner(unit(950_000)),
+					max_slippage: Ratio::from_percent(5),
+				}),
+			));
+			LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+
+			assert!(!DeleverageGuards::<Test>::contains_key(1, DOT));
+			assert_eq!(DeleverageGuardCursor::<Test>::get(), None);
+			System::assert_last_event(RuntimeEvent::LeverageStaking(Event::DeleverageFailed {
+				who: 1,
+				asset_id: DOT,
+				error: Error::<Test>::ArgumentsError.into(),
+			}));
+		});
+}
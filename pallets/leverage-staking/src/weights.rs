@@ -51,6 +51,9 @@ use frame_support::{
 
 pub trait WeightInfo {
 	fn flash_loan_deposit() -> Weight;
+	fn set_deleverage_guard() -> Weight;
+	fn deleverage() -> Weight;
+	fn check_deleverage_guard() -> Weight;
 }
 
 /// Weight functions for `leverage_staking`.
@@ -120,6 +123,96 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(41))
 			.saturating_add(T::DbWeight::get().writes(21))
 	}
+	/// Storage: AssetRegistry CurrencyMetadatas (r:1 w:0)
+	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:1 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking DeleverageGuards (r:0 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageGuards (max_values: None, max_size: None, mode: Measured)
+	fn set_deleverage_guard() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4567))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking DeleverageGuards (r:1 w:0)
+	/// Proof Skipped: LeverageStaking DeleverageGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:6 w:6)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: LendMarket Markets (r:3 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StablePool PoolTokenIndex (r:2 w:0)
+	/// Proof Skipped: StablePool PoolTokenIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset Pools (r:1 w:1)
+	/// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset TokenRateCaches (r:2 w:0)
+	/// Proof Skipped: StableAsset TokenRateCaches (max_values: None, max_size: None, mode: Measured)
+	/// Storage: VtokenMinting TokenPool (r:1 w:0)
+	/// Proof: VtokenMinting TokenPool (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: Tokens TotalIssuance (r:2 w:2)
+	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: AssetRegistry CurrencyMetadatas (r:2 w:0)
+	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:3 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: LendMarket LastAccruedInterestTime (r:2 w:2)
+	/// Proof Skipped: LendMarket LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardSupplyState (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardSupplyState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardSupplySpeed (r:1 w:0)
+	/// Proof Skipped: LendMarket RewardSupplySpeed (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardSupplierIndex (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardSupplierIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardAccured (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardAccured (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:3 w:1)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalSupply (r:2 w:1)
+	/// Proof Skipped: LendMarket TotalSupply (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalBorrows (r:2 w:1)
+	/// Proof Skipped: LendMarket TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalReserves (r:2 w:0)
+	/// Proof Skipped: LendMarket TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountEarned (r:1 w:1)
+	/// Proof Skipped: LendMarket AccountEarned (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:2 w:1)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationFreeCollaterals (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationFreeCollaterals (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardBorrowState (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardBorrowState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardBorrowSpeed (r:1 w:0)
+	/// Proof Skipped: LendMarket RewardBorrowSpeed (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardBorrowerIndex (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardBorrowerIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket BorrowIndex (r:1 w:0)
+	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	fn deleverage() -> Weight {
+		Weight::from_parts(346_093_000, 0)
+			.saturating_add(Weight::from_parts(0, 15734))
+			.saturating_add(T::DbWeight::get().reads(48))
+			.saturating_add(T::DbWeight::get().writes(22))
+	}
+	/// Storage: LeverageStaking DeleverageGuards (r:1 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:3 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:2 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:2 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	fn check_deleverage_guard() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8000))
+			.saturating_add(T::DbWeight::get().reads(16))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
 
 // For backwards compatibility and tests
@@ -188,4 +281,94 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(41))
 			.saturating_add(RocksDbWeight::get().writes(21))
 	}
+	/// Storage: AssetRegistry CurrencyMetadatas (r:1 w:0)
+	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:1 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking DeleverageGuards (r:0 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageGuards (max_values: None, max_size: None, mode: Measured)
+	fn set_deleverage_guard() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4567))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking DeleverageGuards (r:1 w:0)
+	/// Proof Skipped: LeverageStaking DeleverageGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:6 w:6)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: LendMarket Markets (r:3 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StablePool PoolTokenIndex (r:2 w:0)
+	/// Proof Skipped: StablePool PoolTokenIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset Pools (r:1 w:1)
+	/// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset TokenRateCaches (r:2 w:0)
+	/// Proof Skipped: StableAsset TokenRateCaches (max_values: None, max_size: None, mode: Measured)
+	/// Storage: VtokenMinting TokenPool (r:1 w:0)
+	/// Proof: VtokenMinting TokenPool (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: Tokens TotalIssuance (r:2 w:2)
+	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: AssetRegistry CurrencyMetadatas (r:2 w:0)
+	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:3 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: LendMarket LastAccruedInterestTime (r:2 w:2)
+	/// Proof Skipped: LendMarket LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardSupplyState (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardSupplyState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardSupplySpeed (r:1 w:0)
+	/// Proof Skipped: LendMarket RewardSupplySpeed (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardSupplierIndex (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardSupplierIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardAccured (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardAccured (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:3 w:1)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalSupply (r:2 w:1)
+	/// Proof Skipped: LendMarket TotalSupply (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalBorrows (r:2 w:1)
+	/// Proof Skipped: LendMarket TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalReserves (r:2 w:0)
+	/// Proof Skipped: LendMarket TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountEarned (r:1 w:1)
+	/// Proof Skipped: LendMarket AccountEarned (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:2 w:1)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationFreeCollaterals (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationFreeCollaterals (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardBorrowState (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardBorrowState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardBorrowSpeed (r:1 w:0)
+	/// Proof Skipped: LendMarket RewardBorrowSpeed (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket RewardBorrowerIndex (r:1 w:1)
+	/// Proof Skipped: LendMarket RewardBorrowerIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket BorrowIndex (r:1 w:0)
+	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	fn deleverage() -> Weight {
+		Weight::from_parts(346_093_000, 0)
+			.saturating_add(Weight::from_parts(0, 15734))
+			.saturating_add(RocksDbWeight::get().reads(48))
+			.saturating_add(RocksDbWeight::get().writes(22))
+	}
+	/// Storage: LeverageStaking DeleverageGuards (r:1 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:3 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:2 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:2 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	fn check_deleverage_guard() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8000))
+			.saturating_add(RocksDbWeight::get().reads(16))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
 }
\ No newline at end of file
@@ -244,6 +244,7 @@ impl bifrost_vtoken_minting::Config for Test {
 	type BifrostSlpx = SlpxInterface;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = ();
 	type XcmTransfer = MockXcmTransfer;
 	type MoonbeamChainId = ConstU32<2023>;
 	type ChannelCommission = ();
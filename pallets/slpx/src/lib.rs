@@ -18,13 +18,15 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 use crate::types::{
-	AccountIdOf, BalanceOf, CurrencyIdOf, EthereumCallConfiguration, EthereumXcmCall,
-	EthereumXcmTransaction, EthereumXcmTransactionV2, MoonbeamCall, Order, OrderCaller, OrderType,
-	SupportChain, TargetChain, EVM_FUNCTION_SELECTOR, MAX_GAS_LIMIT,
+	AccountIdOf, AstarCall, BalanceOf, CheckedEthereumTx, CurrencyIdOf, EthereumCheckedCall,
+	EthereumXcmCall, EthereumXcmTransaction, EthereumXcmTransactionV2, HyperbridgeOracleDispatcher,
+	MoonbeamCall, OracleChain, OracleConfiguration, OraclePayloadEncoder, Order, OrderCaller,
+	OrderType, SetTokenAmountEncoder, SupportChain, TargetChain, MAX_GAS_LIMIT,
+	MAX_ORACLE_CONFIGURATIONS,
 };
 use bifrost_asset_registry::AssetMetadata;
 use bifrost_primitives::{
-	currency::{BNC, MOVR, VFIL},
+	currency::{ASTR, BNC, MOVR, VFIL},
 	AstarChainId, AstarEvmChainId, Balance, BifrostKusamaChainId, CurrencyId, CurrencyIdMapping,
 	HydrationChainId, InterlayChainId, MantaChainId, MoonbeamEvmChainId, MoonriverEvmChainId,
 	RedeemType, SlpxOperator, TokenInfo, VtokenMintingInterface, GLMR,
@@ -85,7 +87,7 @@ pub mod pallet {
 	use frame_system::ensure_root;
 	use sp_runtime::traits::BlockNumberProvider;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -124,6 +126,10 @@ pub mod pallet {
 		/// The maximum number of order is 500
 		#[pallet::constant]
 		type MaxOrderSize: Get<u32>;
+		/// Encode the contract call input of oracle pushes
+		type OraclePayloadEncoder: OraclePayloadEncoder;
+		/// Dispatch oracle pushes to EVM chains connected through Hyperbridge
+		type HyperbridgeOracleDispatcher: HyperbridgeOracleDispatcher<AccountIdOf<Self>>;
 	}
 
 	#[pallet::event]
@@ -167,6 +173,10 @@ pub mod pallet {
 			currency_id: CurrencyId,
 			/// Whether to support the xcm oracle
 			is_support: bool,
+			/// The chain of the oracle contract
+			chain: OracleChain,
+			/// The address of the oracle contract
+			contract: H160,
 		},
 		/// Set the xcm oracle configuration
 		SetXcmOracleConfiguration {
@@ -176,11 +186,33 @@ pub mod pallet {
 			xcm_weight: Weight,
 			/// The period of Sending Xcm
 			period: BlockNumberFor<T>,
+			/// The chain of the oracle contract
+			chain: OracleChain,
+			/// The address of XcmOracle
+			contract: H160,
+		},
+		/// Set the fee budget of the xcm oracle configuration
+		SetXcmOracleFeeBudget {
+			/// The chain of the oracle contract
+			chain: OracleChain,
+			/// The address of XcmOracle
+			contract: H160,
+			/// The remaining fee that can be spent on pushes
+			fee_budget: u128,
+		},
+		/// Remove the xcm oracle configuration
+		RemoveXcmOracleConfiguration {
+			/// The chain of the oracle contract
+			chain: OracleChain,
 			/// The address of XcmOracle
 			contract: H160,
 		},
 		/// Send Xcm message
 		XcmOracle {
+			/// The chain of the oracle contract
+			chain: OracleChain,
+			/// The address of XcmOracle
+			contract: H160,
 			/// The currency id of the token
 			currency_id: CurrencyId,
 			/// The currency amount of staking
@@ -216,6 +248,8 @@ pub mod pallet {
 		},
 		/// Xcm oracle failed
 		XcmOracleFailed { error: DispatchError },
+		/// The fee budget of the xcm oracle configuration is not enough for the next push
+		XcmOracleFeeBudgetExhausted { chain: OracleChain, contract: H160 },
 		/// Withdraw xcm fee
 		InsufficientAssets,
 	}
@@ -252,6 +286,10 @@ pub mod pallet {
 		ErrorChargeFee,
 		ErrorArguments,
 		Unsupported,
+		/// The xcm oracle configuration does not exist
+		OracleConfigurationNotFound,
+		/// The maximum number of xcm oracle configurations is 10
+		OracleConfigurationOverflow,
 	}
 
 	/// Contract whitelist
@@ -274,15 +312,21 @@ pub mod pallet {
 	pub type TransferToFee<T: Config> =
 		StorageMap<_, Blake2_128Concat, SupportChain, BalanceOf<T>, OptionQuery>;
 
-	/// Xcm Oracle configuration
+	/// Xcm Oracle configurations, keyed by the chain and the address of the oracle contract
 	#[pallet::storage]
-	pub type XcmEthereumCallConfiguration<T: Config> =
-		StorageValue<_, EthereumCallConfiguration<BlockNumberFor<T>>>;
+	pub type XcmOracleConfigurations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		OracleChain,
+		Blake2_128Concat,
+		H160,
+		OracleConfiguration<BlockNumberFor<T>>,
+	>;
 
-	/// Currency to support xcm oracle
+	/// Raw storage key of the last xcm oracle configuration pushed, the next push looks for a
+	/// due configuration after it.
 	#[pallet::storage]
-	pub type CurrencyIdList<T: Config> =
-		StorageValue<_, BoundedVec<CurrencyId, ConstU32<10>>, ValueQuery>;
+	pub type XcmOracleCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
 
 	/// Currency to support xcm fee
 	#[pallet::storage]
@@ -310,7 +354,9 @@ pub mod pallet {
 			let mut weight = Weight::default();
 
 			if WeightMeter::with_limit(limit)
-				.try_consume(T::DbWeight::get().reads_writes(14, 8))
+				.try_consume(
+					T::DbWeight::get().reads_writes(14 + 2 * MAX_ORACLE_CONFIGURATIONS as u64, 9),
+				)
 				.is_err()
 			{
 				return weight;
@@ -519,12 +565,16 @@ pub mod pallet {
 		/// Parameters:
 		/// - `currency_id`: The currency id of the token
 		/// - `is_support`: Whether to support the Ethereum call switch
+		/// - `chain`: The chain of the oracle contract
+		/// - `contract`: The address of the oracle contract
 		#[pallet::call_index(8)]
 		#[pallet::weight(<T as Config>::WeightInfo::set_transfer_to_fee())]
 		pub fn support_xcm_oracle(
 			origin: OriginFor<T>,
 			currency_id: CurrencyId,
 			is_support: bool,
+			chain: OracleChain,
+			contract: H160,
 		) -> DispatchResultWithPostInfo {
 			// Check the validity of origin
 			T::ControlOrigin::ensure_origin(origin)?;
@@ -532,36 +582,48 @@ pub mod pallet {
 			currency_id
 				.to_vtoken()
 				.map_err(|_| Error::<T>::ErrorConvertVtoken)?;
-			let mut currency_list = CurrencyIdList::<T>::get();
-			if is_support {
-				ensure!(
-					!currency_list.contains(&currency_id),
-					Error::<T>::CurrencyAlreadyExists
-				);
-				currency_list
-					.try_push(currency_id)
-					.map_err(|_| Error::<T>::CurrencyListOverflow)?;
-			} else {
-				ensure!(
-					currency_list.contains(&currency_id),
-					Error::<T>::CurrencyNotFound
-				);
-				currency_list.retain(|&x| x != currency_id);
-			}
-			CurrencyIdList::<T>::put(currency_list);
+			XcmOracleConfigurations::<T>::try_mutate(
+				chain,
+				contract,
+				|maybe_config| -> DispatchResult {
+					let config = maybe_config
+						.as_mut()
+						.ok_or(Error::<T>::OracleConfigurationNotFound)?;
+					if is_support {
+						ensure!(
+							!config.currency_list.contains(&currency_id),
+							Error::<T>::CurrencyAlreadyExists
+						);
+						config
+							.currency_list
+							.try_push(currency_id)
+							.map_err(|_| Error::<T>::CurrencyListOverflow)?;
+					} else {
+						ensure!(
+							config.currency_list.contains(&currency_id),
+							Error::<T>::CurrencyNotFound
+						);
+						config.currency_list.retain(|&x| x != currency_id);
+					}
+					Ok(())
+				},
+			)?;
 			Self::deposit_event(Event::SupportXcmOracle {
 				currency_id,
 				is_support,
+				chain,
+				contract,
 			});
 			Ok(().into())
 		}
 
-		/// Set the Ethereum call configuration
+		/// Set the Ethereum call configuration, creating it if it does not exist
 		/// Parameters:
-		/// - `xcm_fee`: The XCM fee of Sending Xcm
+		/// - `xcm_fee`: The XCM fee of Sending Xcm, or the relayer fee for Hyperbridge
 		/// - `xcm_weight`: The XCM weight of Sending Xcm
 		/// - `period`: The period of Sending Xcm
 		/// - `contract`: The address of XcmOracle
+		/// - `chain`: The chain of the oracle contract
 		#[pallet::call_index(9)]
 		#[pallet::weight(<T as Config>::WeightInfo::set_transfer_to_fee())]
 		pub fn set_xcm_oracle_configuration(
@@ -570,19 +632,47 @@ pub mod pallet {
 			xcm_weight: Weight,
 			period: BlockNumberFor<T>,
 			contract: H160,
+			chain: OracleChain,
 		) -> DispatchResultWithPostInfo {
 			T::ControlOrigin::ensure_origin(origin)?;
-			XcmEthereumCallConfiguration::<T>::put(EthereumCallConfiguration {
-				xcm_fee,
-				xcm_weight,
-				period,
-				last_block: T::BlockNumberProvider::current_block_number(),
-				contract,
-			});
+			ensure!(
+				Self::is_oracle_chain_supported(chain),
+				Error::<T>::Unsupported
+			);
+			let last_block = T::BlockNumberProvider::current_block_number();
+			match XcmOracleConfigurations::<T>::get(chain, contract) {
+				Some(mut config) => {
+					config.xcm_fee = xcm_fee;
+					config.xcm_weight = xcm_weight;
+					config.period = period;
+					config.last_block = last_block;
+					XcmOracleConfigurations::<T>::insert(chain, contract, config);
+				}
+				None => {
+					ensure!(
+						(XcmOracleConfigurations::<T>::iter().count() as u32)
+							< MAX_ORACLE_CONFIGURATIONS,
+						Error::<T>::OracleConfigurationOverflow
+					);
+					XcmOracleConfigurations::<T>::insert(
+						chain,
+						contract,
+						OracleConfiguration {
+							xcm_fee,
+							xcm_weight,
+							period,
+							last_block,
+							currency_list: BoundedVec::default(),
+							fee_budget: Default::default(),
+						},
+					);
+				}
+			}
 			Self::deposit_event(Event::SetXcmOracleConfiguration {
 				xcm_fee,
 				xcm_weight,
 				period,
+				chain,
 				contract,
 			});
 			Ok(().into())
@@ -756,6 +846,60 @@ pub mod pallet {
 			)
 		}
 
+		/// Set the fee budget of the Ethereum call configuration
+		/// Parameters:
+		/// - `chain`: The chain of the oracle contract
+		/// - `contract`: The address of XcmOracle
+		/// - `fee_budget`: The remaining fee that can be spent on pushes
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_transfer_to_fee())]
+		pub fn set_xcm_oracle_fee_budget(
+			origin: OriginFor<T>,
+			chain: OracleChain,
+			contract: H160,
+			fee_budget: u128,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			XcmOracleConfigurations::<T>::try_mutate(
+				chain,
+				contract,
+				|maybe_config| -> DispatchResult {
+					let config = maybe_config
+						.as_mut()
+						.ok_or(Error::<T>::OracleConfigurationNotFound)?;
+					config.fee_budget = fee_budget;
+					Ok(())
+				},
+			)?;
+			Self::deposit_event(Event::SetXcmOracleFeeBudget {
+				chain,
+				contract,
+				fee_budget,
+			});
+			Ok(().into())
+		}
+
+		/// Remove the Ethereum call configuration
+		/// Parameters:
+		/// - `chain`: The chain of the oracle contract
+		/// - `contract`: The address of XcmOracle
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_transfer_to_fee())]
+		pub fn remove_xcm_oracle_configuration(
+			origin: OriginFor<T>,
+			chain: OracleChain,
+			contract: H160,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				XcmOracleConfigurations::<T>::contains_key(chain, contract),
+				Error::<T>::OracleConfigurationNotFound
+			);
+			XcmOracleConfigurations::<T>::remove(chain, contract);
+			Self::deposit_event(Event::RemoveXcmOracleConfiguration { chain, contract });
+			Ok(().into())
+		}
+
 		// TODO: Substrate user create order
 		// #[pallet::call_index(14)]
 		// #[pallet::weight(<T as Config>::WeightInfo::mint())]
@@ -899,20 +1043,15 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn send_xcm_to_set_token_amount(
+		dest: Location,
+		fee_asset: Location,
+		beneficiary: Junction,
 		call: Vec<u8>,
 		xcm_weight: Weight,
 		xcm_fee: u128,
 	) -> DispatchResult {
-		let dest = Location::new(
-			1,
-			[Parachain(
-				T::VtokenMintingInterface::get_moonbeam_parachain_id(),
-			)],
-		);
-
-		// Moonbeam Native Token
 		let asset = Asset {
-			id: AssetId::from(Location::new(0, [PalletInstance(10)])),
+			id: AssetId::from(fee_asset),
 			fun: Fungible(xcm_fee),
 		};
 
@@ -930,13 +1069,7 @@ impl<T: Config> Pallet<T> {
 			RefundSurplus,
 			DepositAsset {
 				assets: AllCounted(8).into(),
-				beneficiary: Location::new(
-					0,
-					[AccountKey20 {
-						network: None,
-						key: Sibling::from(T::ParachainId::get()).into_account_truncating(),
-					}],
-				),
+				beneficiary: Location::new(0, [beneficiary]),
 			},
 		]);
 
@@ -948,43 +1081,128 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Push the encoded oracle payload to the oracle contract on the target chain
+	fn push_oracle_payload(
+		chain: OracleChain,
+		contract: H160,
+		payload: Vec<u8>,
+		config: &OracleConfiguration<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		let sovereign_account: [u8; 32] =
+			Sibling::from(T::ParachainId::get()).into_account_truncating();
+		match chain {
+			OracleChain::Moonbeam => {
+				let call = Self::encode_transact_call(contract, payload)?;
+				Self::send_xcm_to_set_token_amount(
+					Location::new(
+						1,
+						[Parachain(
+							T::VtokenMintingInterface::get_moonbeam_parachain_id(),
+						)],
+					),
+					// Moonbeam Native Token
+					Location::new(0, [PalletInstance(10)]),
+					AccountKey20 {
+						network: None,
+						key: Sibling::from(T::ParachainId::get()).into_account_truncating(),
+					},
+					call,
+					config.xcm_weight,
+					config.xcm_fee,
+				)
+			}
+			OracleChain::Astar => {
+				ensure!(
+					Self::is_oracle_chain_supported(chain),
+					Error::<T>::Unsupported
+				);
+				let call = Self::encode_astar_transact_call(contract, payload)?;
+				Self::send_xcm_to_set_token_amount(
+					Location::new(1, [Parachain(AstarChainId::get())]),
+					// Astar Native Token
+					Location::here(),
+					AccountId32 {
+						network: None,
+						id: sovereign_account,
+					},
+					call,
+					config.xcm_weight,
+					config.xcm_fee,
+				)
+			}
+			OracleChain::Hyperbridge(evm_chain_id) => T::HyperbridgeOracleDispatcher::dispatch(
+				&T::TreasuryAccount::get(),
+				evm_chain_id,
+				contract,
+				payload,
+				config.xcm_fee,
+			),
+		}
+	}
+
+	/// Astar is only reachable from Bifrost Polkadot, its Kusama counterpart Shiden is not
+	/// supported.
+	fn is_oracle_chain_supported(chain: OracleChain) -> bool {
+		match chain {
+			OracleChain::Astar => T::ParachainId::get() != Id::from(BifrostKusamaChainId::get()),
+			OracleChain::Moonbeam | OracleChain::Hyperbridge(_) => true,
+		}
+	}
+
+	/// The currency burned from the treasury to account for the XCM fee paid by the sovereign
+	/// account on the target chain. Hyperbridge relayer fees are charged by the dispatcher.
+	fn oracle_fee_currency_id(chain: OracleChain) -> Option<CurrencyId> {
+		match chain {
+			OracleChain::Moonbeam => {
+				if T::ParachainId::get() == Id::from(BifrostKusamaChainId::get()) {
+					Some(MOVR)
+				} else {
+					Some(GLMR)
+				}
+			}
+			OracleChain::Astar => Some(ASTR),
+			OracleChain::Hyperbridge(_) => None,
+		}
+	}
+
 	/// setTokenAmount(bytes2,uint256,uint256)
 	pub fn encode_ethereum_call(
 		currency_id: CurrencyId,
 		token_amount: BalanceOf<T>,
 		vtoken_amount: BalanceOf<T>,
 	) -> Vec<u8> {
-		let bytes2_currency_id: Vec<u8> = currency_id.encode()[..2].to_vec();
-		let uint256_token_amount = U256::from(token_amount.saturated_into::<u128>());
-		let uint256_vtoken_amount = U256::from(vtoken_amount.saturated_into::<u128>());
-
-		let mut call = ethabi::encode(&[
-			ethabi::Token::FixedBytes(bytes2_currency_id),
-			ethabi::Token::Uint(uint256_token_amount),
-			ethabi::Token::Uint(uint256_vtoken_amount),
-		]);
-
-		call.splice(0..0, EVM_FUNCTION_SELECTOR);
-		call
+		SetTokenAmountEncoder::encode(
+			OracleChain::Moonbeam,
+			H160::zero(),
+			currency_id,
+			token_amount.saturated_into::<u128>(),
+			vtoken_amount.saturated_into::<u128>(),
+		)
+		.unwrap_or_default()
 	}
 
-	pub fn encode_transact_call(
-		contract: H160,
-		currency_id: CurrencyId,
-		token_amount: BalanceOf<T>,
-		vtoken_amount: BalanceOf<T>,
-	) -> Result<Vec<u8>, Error<T>> {
-		let ethereum_call = Self::encode_ethereum_call(currency_id, token_amount, vtoken_amount);
+	pub fn encode_transact_call(contract: H160, input: Vec<u8>) -> Result<Vec<u8>, Error<T>> {
 		let transaction = EthereumXcmTransaction::V2(EthereumXcmTransactionV2 {
 			gas_limit: U256::from(MAX_GAS_LIMIT),
 			action: TransactionAction::Call(contract),
 			value: U256::zero(),
-			input: BoundedVec::try_from(ethereum_call).map_err(|_| Error::<T>::ErrorEncode)?,
+			input: BoundedVec::try_from(input).map_err(|_| Error::<T>::ErrorEncode)?,
 			access_list: None,
 		});
 		Ok(MoonbeamCall::EthereumXcm(EthereumXcmCall::Transact(transaction)).encode())
 	}
 
+	pub fn encode_astar_transact_call(contract: H160, input: Vec<u8>) -> Result<Vec<u8>, Error<T>> {
+		let transaction = CheckedEthereumTx {
+			gas_limit: U256::from(MAX_GAS_LIMIT),
+			target: contract,
+			value: U256::zero(),
+			input: BoundedVec::try_from(input).map_err(|_| Error::<T>::ErrorEncode)?,
+			maybe_access_list: None,
+		};
+		Ok(AstarCall::EthereumChecked(EthereumCheckedCall::Transact(transaction)).encode())
+	}
+
 	/// Check if the signer is in the whitelist
 	fn ensure_singer_on_whitelist(
 		origin: OriginFor<T>,
@@ -1256,80 +1474,114 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
-	#[transactional]
 	pub fn handle_xcm_oracle(
 		current_block_number: BlockNumberFor<T>,
 		is_handle_xcm_oracle: &mut bool,
 		weight: &mut Weight,
 	) -> DispatchResult {
-		let mut currency_list = CurrencyIdList::<T>::get().to_vec();
-		if currency_list.is_empty() {
-			*weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 0));
+		let is_due = |config: &OracleConfiguration<BlockNumberFor<T>>| {
+			!config.currency_list.is_empty()
+				&& config.last_block + config.period < current_block_number
+		};
+		// Push at most one configuration per block, taking turns from the last one pushed so that
+		// a short period configuration does not starve the others.
+		*weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		let mut configurations = match XcmOracleCursor::<T>::get() {
+			Some(raw_key) => XcmOracleConfigurations::<T>::iter_from(raw_key),
+			None => XcmOracleConfigurations::<T>::iter(),
+		};
+		let mut due_configuration = configurations.find(|(_, _, config)| {
+			*weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			is_due(config)
+		});
+		let mut raw_key = configurations.last_raw_key().to_vec();
+		if due_configuration.is_none() {
+			let mut configurations = XcmOracleConfigurations::<T>::iter();
+			due_configuration = configurations.find(|(_, _, config)| {
+				*weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				is_due(config)
+			});
+			raw_key = configurations.last_raw_key().to_vec();
+		}
+
+		let Some((chain, contract, config)) = due_configuration else {
 			return Ok(());
 		};
+		XcmOracleCursor::<T>::put(raw_key);
+		*is_handle_xcm_oracle = true;
 
-		let configuration = XcmEthereumCallConfiguration::<T>::get();
-		if let Some(mut config) = configuration {
-			let currency_id = currency_list[0];
-			let staking_currency_amount = T::VtokenMintingInterface::get_token_pool(currency_id);
-			let v_currency_id = currency_id
-				.to_vtoken()
-				.map_err(|_| Error::<T>::ErrorConvertVtoken)?;
-			let v_currency_total_supply = T::MultiCurrency::total_issuance(v_currency_id);
-
-			if config.last_block + config.period < current_block_number {
-				let encoded_call = Self::encode_transact_call(
-					config.contract,
-					currency_id,
-					staking_currency_amount,
-					v_currency_total_supply,
-				)
-				.map_err(|_| Error::<T>::ErrorEncode)?;
+		Self::push_xcm_oracle(current_block_number, chain, contract, config, weight)
+	}
 
-				Self::send_xcm_to_set_token_amount(encoded_call, config.xcm_weight, config.xcm_fee)
-					.map_err(|_| Error::<T>::ErrorDelivering)?;
+	#[transactional]
+	fn push_xcm_oracle(
+		current_block_number: BlockNumberFor<T>,
+		chain: OracleChain,
+		contract: H160,
+		mut config: OracleConfiguration<BlockNumberFor<T>>,
+		weight: &mut Weight,
+	) -> DispatchResult {
+		config.last_block = current_block_number;
 
-				Self::deposit_event(Event::XcmOracle {
-					currency_id,
-					staking_currency_amount,
-					v_currency_id,
-					v_currency_total_supply,
-				});
+		if config.fee_budget < config.xcm_fee {
+			XcmOracleConfigurations::<T>::insert(chain, contract, config);
+			Self::deposit_event(Event::XcmOracleFeeBudgetExhausted { chain, contract });
+			*weight = weight.saturating_add(T::DbWeight::get().reads_writes(0, 1));
+			return Ok(());
+		}
 
-				let mut target_fee_currency_id = GLMR;
-				if T::ParachainId::get() == Id::from(BifrostKusamaChainId::get()) {
-					target_fee_currency_id = MOVR;
-				}
+		let currency_id = config.currency_list[0];
+		let staking_currency_amount = T::VtokenMintingInterface::get_token_pool(currency_id);
+		let v_currency_id = currency_id
+			.to_vtoken()
+			.map_err(|_| Error::<T>::ErrorConvertVtoken)?;
+		let v_currency_total_supply = T::MultiCurrency::total_issuance(v_currency_id);
 
-				// Will not check results and will be sent regardless of the success of
-				// the burning
-				if T::MultiCurrency::withdraw(
-					target_fee_currency_id,
-					&T::TreasuryAccount::get(),
-					BalanceOf::<T>::unique_saturated_from(config.xcm_fee),
-				)
-				.is_err()
-				{
-					Self::deposit_event(Event::InsufficientAssets);
-				}
+		let payload = T::OraclePayloadEncoder::encode(
+			chain,
+			contract,
+			currency_id,
+			staking_currency_amount.saturated_into::<u128>(),
+			v_currency_total_supply.saturated_into::<u128>(),
+		)
+		.ok_or(Error::<T>::ErrorEncode)?;
 
-				config.last_block = current_block_number;
-				XcmEthereumCallConfiguration::<T>::put(config);
-				currency_list.rotate_left(1);
-				CurrencyIdList::<T>::put(
-					BoundedVec::try_from(currency_list).map_err(|_| Error::<T>::ErrorEncode)?,
-				);
+		Self::push_oracle_payload(chain, contract, payload, &config)
+			.map_err(|_| Error::<T>::ErrorDelivering)?;
 
-				*weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 2));
+		Self::deposit_event(Event::XcmOracle {
+			chain,
+			contract,
+			currency_id,
+			staking_currency_amount,
+			v_currency_id,
+			v_currency_total_supply,
+		});
 
-				*is_handle_xcm_oracle = true;
+		// Will not check results and will be sent regardless of the success of
+		// the burning
+		if let Some(fee_currency_id) = Self::oracle_fee_currency_id(chain) {
+			if T::MultiCurrency::withdraw(
+				fee_currency_id,
+				&T::TreasuryAccount::get(),
+				BalanceOf::<T>::unique_saturated_from(config.xcm_fee),
+			)
+			.is_err()
+			{
+				Self::deposit_event(Event::InsufficientAssets);
 			}
-
-			return Ok(());
-		} else {
-			*weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 0));
-			return Ok(());
 		}
+
+		config.fee_budget = config.fee_budget.saturating_sub(config.xcm_fee);
+		let mut currency_list = config.currency_list.to_vec();
+		currency_list.rotate_left(1);
+		config.currency_list =
+			BoundedVec::try_from(currency_list).map_err(|_| Error::<T>::ErrorEncode)?;
+		XcmOracleConfigurations::<T>::insert(chain, contract, config);
+
+		*weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 2));
+
+		Ok(())
 	}
 }
 
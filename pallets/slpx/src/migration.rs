@@ -192,7 +192,24 @@ pub mod v1 {
 
 pub mod v2 {
 	use super::*;
-	use frame_support::traits::GetStorageVersion;
+	use frame_support::{pallet_prelude::ValueQuery, traits::GetStorageVersion};
+
+	#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+	pub struct EthereumCallConfiguration<BlockNumber> {
+		pub xcm_fee: u128,
+		pub xcm_weight: Weight,
+		pub period: BlockNumber,
+		pub last_block: BlockNumber,
+		pub contract: H160,
+	}
+
+	#[storage_alias]
+	pub(super) type XcmEthereumCallConfiguration<T: Config> =
+		StorageValue<Pallet<T>, EthereumCallConfiguration<BlockNumberFor<T>>>;
+
+	#[storage_alias]
+	pub(super) type CurrencyIdList<T: Config> =
+		StorageValue<Pallet<T>, BoundedVec<CurrencyId, ConstU32<10>>, ValueQuery>;
 
 	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
 	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
@@ -291,3 +308,76 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 
 	weight
 }
+
+pub mod v3 {
+	use super::*;
+	use frame_support::traits::GetStorageVersion;
+
+	pub struct MigrateToV3<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
+			let in_code_storage_version = Pallet::<T>::in_code_storage_version();
+			if on_chain_storage_version == 2 && in_code_storage_version == 3 {
+				let weight_consumed = migrate_to_v3::<T>();
+				log::info!("Migrating slpx storage to v3");
+				in_code_storage_version.put::<Pallet<T>>();
+				weight_consumed.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!("slpx migration should be removed.");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			Ok(v2::XcmEthereumCallConfiguration::<T>::exists().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			let had_configuration = bool::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the pre-upgrade state")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 3,
+				"on_chain_storage_version should be 3"
+			);
+			ensure!(
+				!v2::XcmEthereumCallConfiguration::<T>::exists(),
+				"XcmEthereumCallConfiguration should be removed"
+			);
+			ensure!(
+				XcmOracleConfigurations::<T>::iter_prefix(OracleChain::Moonbeam).count()
+					== had_configuration as usize,
+				"XcmOracleConfigurations post-migrate storage count not match"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Move the single Moonbeam oracle configuration and its currency list into
+/// `XcmOracleConfigurations`.
+///
+/// The migrated configuration starts with a zero fee budget. Pushes pause with an
+/// `XcmOracleFeeBudgetExhausted` event until governance sets the budget with
+/// `set_xcm_oracle_fee_budget`, so no fee is spent that was not approved.
+pub fn migrate_to_v3<T: Config>() -> Weight {
+	let currency_list = v2::CurrencyIdList::<T>::take();
+	if let Some(old_config) = v2::XcmEthereumCallConfiguration::<T>::take() {
+		XcmOracleConfigurations::<T>::insert(
+			OracleChain::Moonbeam,
+			old_config.contract,
+			OracleConfiguration {
+				xcm_fee: old_config.xcm_fee,
+				xcm_weight: old_config.xcm_weight,
+				period: old_config.period,
+				last_block: old_config.last_block,
+				currency_list,
+				fee_budget: Default::default(),
+			},
+		);
+	}
+
+	T::DbWeight::get().reads_writes(2, 3)
+}
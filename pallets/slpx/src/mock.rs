@@ -185,6 +185,7 @@ impl bifrost_vtoken_minting::Config for Test {
 	type BifrostSlpx = SlpxInterface;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = ();
 	type XcmTransfer = MockXcmTransfer;
 	type MoonbeamChainId = MoonbeamChainId;
 	type ChannelCommission = ();
@@ -320,6 +321,8 @@ impl slpx::Config for Test {
 	type WeightInfo = ();
 	type MaxOrderSize = ConstU32<500>;
 	type BlockNumberProvider = System;
+	type OraclePayloadEncoder = slpx::types::SetTokenAmountEncoder;
+	type HyperbridgeOracleDispatcher = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -306,64 +306,266 @@ fn test_ethereum_call() {
 		});
 		let call = MoonbeamCall::EthereumXcm(EthereumXcmCall::Transact(r));
 		println!("{}", hex::encode(call.encode()));
-		assert_eq!("6d000180fc0a000000000000000000000000000000000000000000000000000000000000ae0daa9bfc50f03ce23d30c796709a58470b5f42000000000000000000000000000000000000000000000000000000000000000091019a41b9240001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b00000000000000000000000000000000000000000000000000000000000001c800", hex::encode(Slpx::encode_transact_call(H160::from(addr), BNC, 123u128, 456u128).unwrap()));
+		assert_eq!("6d000180fc0a000000000000000000000000000000000000000000000000000000000000ae0daa9bfc50f03ce23d30c796709a58470b5f42000000000000000000000000000000000000000000000000000000000000000091019a41b9240001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b00000000000000000000000000000000000000000000000000000000000001c800", hex::encode(Slpx::encode_transact_call(H160::from(addr), Slpx::encode_ethereum_call(BNC, 123u128, 456u128)).unwrap()));
 	})
 }
 
 #[test]
 fn test_set_currency_ethereum_call_switch() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Slpx::support_xcm_oracle(RuntimeOrigin::root(), BNC, true));
-		assert_eq!(CurrencyIdList::<Test>::get().to_vec(), vec![BNC]);
+		let contract = H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]);
+		assert_noop!(
+			Slpx::support_xcm_oracle(
+				RuntimeOrigin::root(),
+				BNC,
+				true,
+				OracleChain::Moonbeam,
+				contract
+			),
+			Error::<Test>::OracleConfigurationNotFound
+		);
+		assert_ok!(Slpx::set_xcm_oracle_configuration(
+			RuntimeOrigin::root(),
+			1u128,
+			Weight::default(),
+			5u32.into(),
+			contract,
+			OracleChain::Moonbeam,
+		));
 
-		assert_ok!(Slpx::support_xcm_oracle(RuntimeOrigin::root(), KSM, true));
-		assert_eq!(CurrencyIdList::<Test>::get().to_vec(), vec![BNC, KSM]);
+		assert_ok!(Slpx::support_xcm_oracle(
+			RuntimeOrigin::root(),
+			BNC,
+			true,
+			OracleChain::Moonbeam,
+			contract
+		));
+		assert_eq!(
+			XcmOracleConfigurations::<Test>::get(OracleChain::Moonbeam, contract)
+				.unwrap()
+				.currency_list
+				.to_vec(),
+			vec![BNC]
+		);
 
-		assert_ok!(Slpx::support_xcm_oracle(RuntimeOrigin::root(), BNC, false));
-		assert_eq!(CurrencyIdList::<Test>::get().to_vec(), vec![KSM]);
+		assert_ok!(Slpx::support_xcm_oracle(
+			RuntimeOrigin::root(),
+			KSM,
+			true,
+			OracleChain::Moonbeam,
+			contract
+		));
+		assert_eq!(
+			XcmOracleConfigurations::<Test>::get(OracleChain::Moonbeam, contract)
+				.unwrap()
+				.currency_list
+				.to_vec(),
+			vec![BNC, KSM]
+		);
+
+		assert_ok!(Slpx::support_xcm_oracle(
+			RuntimeOrigin::root(),
+			BNC,
+			false,
+			OracleChain::Moonbeam,
+			contract
+		));
+		assert_eq!(
+			XcmOracleConfigurations::<Test>::get(OracleChain::Moonbeam, contract)
+				.unwrap()
+				.currency_list
+				.to_vec(),
+			vec![KSM]
+		);
 	})
 }
 
 #[test]
 fn test_set_ethereum_call_configration() {
 	new_test_ext().execute_with(|| {
+		let contract = H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]);
 		assert_ok!(Slpx::set_xcm_oracle_configuration(
 			RuntimeOrigin::root(),
 			1_000_000_000_000_000_000u128,
 			Weight::default(),
 			5u32.into(),
-			H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"])
+			contract,
+			OracleChain::Moonbeam,
 		));
 
 		assert_eq!(
-			XcmEthereumCallConfiguration::<Test>::get().unwrap(),
-			EthereumCallConfiguration {
+			XcmOracleConfigurations::<Test>::get(OracleChain::Moonbeam, contract).unwrap(),
+			OracleConfiguration {
 				xcm_fee: 1_000_000_000_000_000_000u128,
 				xcm_weight: Weight::default(),
 				period: 5u32.into(),
 				last_block: 0u32.into(),
-				contract: H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]),
+				currency_list: BoundedVec::default(),
+				fee_budget: 0u128,
 			}
 		);
 
+		assert_ok!(Slpx::support_xcm_oracle(
+			RuntimeOrigin::root(),
+			BNC,
+			true,
+			OracleChain::Moonbeam,
+			contract
+		));
+		assert_ok!(Slpx::set_xcm_oracle_fee_budget(
+			RuntimeOrigin::root(),
+			OracleChain::Moonbeam,
+			contract,
+			100u128,
+		));
 		assert_ok!(Slpx::set_xcm_oracle_configuration(
 			RuntimeOrigin::root(),
 			1u128,
 			Weight::default(),
 			10u32.into(),
-			H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"])
+			contract,
+			OracleChain::Moonbeam,
 		));
 
 		assert_eq!(
-			XcmEthereumCallConfiguration::<Test>::get().unwrap(),
-			EthereumCallConfiguration {
+			XcmOracleConfigurations::<Test>::get(OracleChain::Moonbeam, contract).unwrap(),
+			OracleConfiguration {
 				xcm_fee: 1u128,
 				xcm_weight: Weight::default(),
 				period: 10u32.into(),
 				last_block: 0u32.into(),
-				contract: H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]),
+				currency_list: BoundedVec::try_from(vec![BNC]).unwrap(),
+				fee_budget: 100u128,
 			}
 		);
+
+		// The same contract on another chain is a separate configuration
+		assert_ok!(Slpx::set_xcm_oracle_configuration(
+			RuntimeOrigin::root(),
+			1u128,
+			Weight::default(),
+			10u32.into(),
+			contract,
+			OracleChain::Hyperbridge(1),
+		));
+		assert_eq!(XcmOracleConfigurations::<Test>::iter().count(), 2);
+
+		assert_ok!(Slpx::remove_xcm_oracle_configuration(
+			RuntimeOrigin::root(),
+			OracleChain::Hyperbridge(1),
+			contract,
+		));
+		assert_noop!(
+			Slpx::remove_xcm_oracle_configuration(
+				RuntimeOrigin::root(),
+				OracleChain::Hyperbridge(1),
+				contract,
+			),
+			Error::<Test>::OracleConfigurationNotFound
+		);
+		assert_eq!(XcmOracleConfigurations::<Test>::iter().count(), 1);
+	})
+}
+
+#[test]
+fn test_xcm_oracle_fee_budget_exhausted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let contract = H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]);
+		// Shiden, the Kusama counterpart of Astar, is not supported
+		assert_noop!(
+			Slpx::set_xcm_oracle_configuration(
+				RuntimeOrigin::root(),
+				10u128,
+				Weight::default(),
+				1u32.into(),
+				contract,
+				OracleChain::Astar,
+			),
+			Error::<Test>::Unsupported
+		);
+		assert_ok!(Slpx::set_xcm_oracle_configuration(
+			RuntimeOrigin::root(),
+			10u128,
+			Weight::default(),
+			1u32.into(),
+			contract,
+			OracleChain::Moonbeam,
+		));
+		assert_ok!(Slpx::support_xcm_oracle(
+			RuntimeOrigin::root(),
+			KSM,
+			true,
+			OracleChain::Moonbeam,
+			contract
+		));
+		assert_ok!(Slpx::set_xcm_oracle_fee_budget(
+			RuntimeOrigin::root(),
+			OracleChain::Moonbeam,
+			contract,
+			9u128,
+		));
+
+		let mut is_handle_xcm_oracle = false;
+		let mut weight = Weight::default();
+		assert_ok!(Slpx::handle_xcm_oracle(
+			5u32.into(),
+			&mut is_handle_xcm_oracle,
+			&mut weight
+		));
+		assert!(is_handle_xcm_oracle);
+		System::assert_last_event(RuntimeEvent::Slpx(Event::XcmOracleFeeBudgetExhausted {
+			chain: OracleChain::Moonbeam,
+			contract,
+		}));
+		let config = XcmOracleConfigurations::<Test>::get(OracleChain::Moonbeam, contract).unwrap();
+		assert_eq!(config.last_block, 5u32.into());
+		assert_eq!(config.fee_budget, 9u128);
+	})
+}
+
+#[test]
+fn test_xcm_oracle_takes_turns() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let contract = H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]);
+		for chain in [OracleChain::Moonbeam, OracleChain::Hyperbridge(1)] {
+			assert_ok!(Slpx::set_xcm_oracle_configuration(
+				RuntimeOrigin::root(),
+				10u128,
+				Weight::default(),
+				1u32.into(),
+				contract,
+				chain,
+			));
+			assert_ok!(Slpx::support_xcm_oracle(
+				RuntimeOrigin::root(),
+				KSM,
+				true,
+				chain,
+				contract
+			));
+		}
+
+		// Both configurations stay due, but they are pushed in turn
+		let mut pushed = vec![];
+		for block in [5u32, 10, 15] {
+			let mut is_handle_xcm_oracle = false;
+			let mut weight = Weight::default();
+			assert_ok!(Slpx::handle_xcm_oracle(
+				block.into(),
+				&mut is_handle_xcm_oracle,
+				&mut weight
+			));
+			assert!(is_handle_xcm_oracle);
+			let chain = XcmOracleConfigurations::<Test>::iter()
+				.find(|(_, _, config)| config.last_block == block.into())
+				.map(|(chain, _, _)| chain)
+				.unwrap();
+			pushed.push(chain);
+		}
+		assert_ne!(pushed[0], pushed[1]);
+		assert_eq!(pushed[0], pushed[2]);
 	})
 }
 
@@ -17,12 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::pallet;
+use bifrost_primitives::CurrencyId;
 use ethereum::TransactionAction;
 use orml_traits::MultiCurrency;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::{H160, H256, U256};
-use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use sp_runtime::{traits::ConstU32, BoundedVec, DispatchError, DispatchResult, RuntimeDebug};
 use sp_std::vec::Vec;
 use xcm::prelude::Weight;
 
@@ -35,6 +36,9 @@ pub const MAX_GAS_LIMIT: u32 = 720_000;
 /// EVM function selector: setTokenAmount(bytes2,uint256,uint256)
 pub const EVM_FUNCTION_SELECTOR: [u8; 4] = [154, 65, 185, 36];
 
+/// The maximum number of oracle push configurations across all chains and contracts.
+pub const MAX_ORACLE_CONFIGURATIONS: u32 = 10;
+
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type CurrencyIdOf<T> = <<T as pallet::Config>::MultiCurrency as MultiCurrency<
 	<T as frame_system::Config>::AccountId,
@@ -72,18 +76,82 @@ impl<AccountId> TargetChain<AccountId> {
 	}
 }
 
+/// The chain an oracle configuration pushes token pool data to.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum OracleChain {
+	/// Moonbeam(Moonriver on Kusama) through `EthereumXcm::transact`
+	Moonbeam,
+	/// Astar EVM through `EthereumChecked::transact`
+	Astar,
+	/// An EVM chain connected through Hyperbridge, identified by its EVM chain id
+	Hyperbridge(u32),
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen)]
-pub struct EthereumCallConfiguration<BlockNumber> {
-	/// XCM message execution costs to be consumed
+pub struct OracleConfiguration<BlockNumber> {
+	/// XCM message execution costs, or the relayer fee for Hyperbridge
 	pub xcm_fee: u128,
-	/// XCM message execution weight to be consumed
+	/// XCM message execution weight to be consumed, unused for Hyperbridge
 	pub xcm_weight: Weight,
-	/// Wait for the period to call XCM once
+	/// Wait for the period to push once
 	pub period: BlockNumber,
-	/// Block number of the last call
+	/// Block number of the last push
 	pub last_block: BlockNumber,
-	/// Specify the address of the calling contract
-	pub contract: H160,
+	/// Currencies whose token pool and vtoken supply are pushed, one per period in turn
+	pub currency_list: BoundedVec<CurrencyId, ConstU32<10>>,
+	/// The remaining fee that can be spent on pushes for this configuration
+	pub fee_budget: u128,
+}
+
+/// Encode the contract call input pushed to an oracle contract.
+pub trait OraclePayloadEncoder {
+	fn encode(
+		chain: OracleChain,
+		contract: H160,
+		currency_id: CurrencyId,
+		token_amount: u128,
+		vtoken_amount: u128,
+	) -> Option<Vec<u8>>;
+}
+
+/// The default encoding, setTokenAmount(bytes2,uint256,uint256)
+pub struct SetTokenAmountEncoder;
+impl OraclePayloadEncoder for SetTokenAmountEncoder {
+	fn encode(
+		_chain: OracleChain,
+		_contract: H160,
+		currency_id: CurrencyId,
+		token_amount: u128,
+		vtoken_amount: u128,
+	) -> Option<Vec<u8>> {
+		let bytes2_currency_id: Vec<u8> = currency_id.encode()[..2].to_vec();
+
+		let mut call = ethabi::encode(&[
+			ethabi::Token::FixedBytes(bytes2_currency_id),
+			ethabi::Token::Uint(U256::from(token_amount)),
+			ethabi::Token::Uint(U256::from(vtoken_amount)),
+		]);
+
+		call.splice(0..0, EVM_FUNCTION_SELECTOR);
+		Some(call)
+	}
+}
+
+/// Dispatch an oracle payload to an EVM chain connected through Hyperbridge.
+pub trait HyperbridgeOracleDispatcher<AccountId> {
+	fn dispatch(
+		payer: &AccountId,
+		evm_chain_id: u32,
+		contract: H160,
+		body: Vec<u8>,
+		relayer_fee: u128,
+	) -> DispatchResult;
+}
+
+impl<AccountId> HyperbridgeOracleDispatcher<AccountId> for () {
+	fn dispatch(_: &AccountId, _: u32, _: H160, _: Vec<u8>, _: u128) -> DispatchResult {
+		Err(DispatchError::Other("Hyperbridge is not supported"))
+	}
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
@@ -119,6 +187,33 @@ pub enum MoonbeamCall {
 	EthereumXcm(EthereumXcmCall),
 }
 
+/// Astar's `CheckedEthereumTx`.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+pub struct CheckedEthereumTx {
+	/// Gas limit.
+	pub gas_limit: U256,
+	/// Action type, only Call is supported.
+	pub target: H160,
+	/// Amount to transfer.
+	pub value: U256,
+	/// Input of a contract call. Max. size 65_536 bytes.
+	pub input: BoundedVec<u8, ConstU32<MAX_ETHEREUM_XCM_INPUT_SIZE>>,
+	/// Optional access list, specified in EIP-2930.
+	pub maybe_access_list: Option<Vec<(H160, Vec<H256>)>>,
+}
+
+#[derive(Encode, Decode, RuntimeDebug, Clone)]
+pub enum EthereumCheckedCall {
+	#[codec(index = 0)]
+	Transact(CheckedEthereumTx),
+}
+
+#[derive(Encode, Decode, RuntimeDebug, Clone)]
+pub enum AstarCall {
+	#[codec(index = 64)]
+	EthereumChecked(EthereumCheckedCall),
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum OrderCaller<AccountId> {
 	Substrate(AccountId),
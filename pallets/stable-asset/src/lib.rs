@@ -52,9 +52,13 @@ pub type PoolTokenIndex = u32;
 
 pub type StableAssetPoolId = u32;
 
+/// The maximum number of pools in which an asset can have a token rate.
+pub const MAX_TOKEN_RATE_POOLS: u32 = 8;
+
 const NUMBER_OF_ITERATIONS_TO_CONVERGE: i32 = 255; // the number of iterations to sum d and y
 
 #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct StableAssetPoolInfo<AssetId, AtLeast64BitUnsigned, Balance, AccountId, BlockNumber> {
 	pub pool_id: StableAssetPoolId,
 	pub pool_asset: AssetId,
@@ -134,6 +138,15 @@ pub mod traits {
 
 		fn pool_count() -> StableAssetPoolId;
 
+		/// Set the base pool of the metapool `pool_id`, or remove it with `None`. The pool asset
+		/// of the base pool must be one of the metapool assets.
+		fn set_base_pool(
+			pool_id: StableAssetPoolId,
+			base_pool_id: Option<StableAssetPoolId>,
+		) -> DispatchResult;
+
+		fn get_base_pool(pool_id: StableAssetPoolId) -> Option<StableAssetPoolId>;
+
 		fn pool(
 			id: StableAssetPoolId,
 		) -> Option<
@@ -407,8 +420,11 @@ pub mod pallet {
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// The last pool id.
@@ -441,10 +457,33 @@ pub mod pallet {
 		(T::AtLeast64BitUnsigned, T::AtLeast64BitUnsigned),
 	>;
 
+	/// The pools in which an asset has a token rate in `TokenRateCaches`.
+	#[pallet::storage]
+	pub type TokenRatePools<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AssetId,
+		BoundedVec<StableAssetPoolId, ConstU32<MAX_TOKEN_RATE_POOLS>>,
+		ValueQuery,
+	>;
+
 	/// Record the maximum percentage that can exceed the token rate.
 	#[pallet::storage]
 	pub type TokenRateHardcap<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, Permill>;
 
+	/// The base pool of a metapool. The pool asset of the base pool is one of the metapool assets
+	/// and is priced at the virtual price of the base pool.
+	#[pallet::storage]
+	pub type BasePools<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, StableAssetPoolId>;
+
+	/// The off-peg swap fee multiplier of a pool, in `FeePrecision` units. The swap fee grows
+	/// towards `swap_fee * multiplier` as the two swapped balances drift apart. Pools without an
+	/// entry charge a flat `swap_fee`.
+	#[pallet::storage]
+	pub type SwapFeeMultipliers<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, T::AtLeast64BitUnsigned>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -667,6 +706,35 @@ pub mod pallet {
 			/// The pool id.
 			pool_id: StableAssetPoolId,
 		},
+		/// The token rate reported for an auto-refreshed vToken moved up or down by more than its
+		/// hardcap, so it is not applied.
+		TokenRateHardcapExceeded {
+			/// The pool id.
+			pool_id: StableAssetPoolId,
+			/// The vToken.
+			vtoken: T::AssetId,
+			/// The reported token rate (denominator, numerator).
+			token_rate: (T::AtLeast64BitUnsigned, T::AtLeast64BitUnsigned),
+		},
+		/// The base pool of a metapool is set.
+		BasePoolSet {
+			/// The metapool id.
+			pool_id: StableAssetPoolId,
+			/// The base pool id.
+			base_pool_id: StableAssetPoolId,
+		},
+		/// The base pool of a metapool is removed.
+		BasePoolRemoved {
+			/// The metapool id.
+			pool_id: StableAssetPoolId,
+		},
+		/// The off-peg swap fee multiplier of a pool is modified.
+		SwapFeeMultiplierModified {
+			/// The pool id.
+			pool_id: StableAssetPoolId,
+			/// The new multiplier, `None` if the pool charges a flat swap fee.
+			multiplier: Option<T::AtLeast64BitUnsigned>,
+		},
 	}
 
 	#[pallet::error]
@@ -697,6 +765,12 @@ pub mod pallet {
 		RedeemOverMax,
 		/// The old token rate is not cleared.
 		TokenRateNotCleared,
+		/// The base pool cannot be used for the metapool.
+		InvalidBasePool,
+		/// The base pool of a pool with liquidity cannot be changed.
+		PoolNotEmpty,
+		/// The asset has a token rate in `MAX_TOKEN_RATE_POOLS` pools already.
+		TooManyTokenRatePools,
 	}
 
 	/// The add liquidity result.
@@ -935,6 +1009,7 @@ pub mod pallet {
 				if let Some(fee) = redeem_fee {
 					pool_info.redeem_fee = fee;
 				}
+				Self::ensure_max_swap_fee(pool_id, pool_info.swap_fee)?;
 				Self::deposit_event(Event::FeeModified {
 					pool_id,
 					mint_fee: pool_info.mint_fee,
@@ -1273,10 +1348,20 @@ impl<T: Config> Pallet<T> {
 			.ok_or(Error::<T>::Math)?
 			.checked_div(&pool_info.precisions[output_index_usize])
 			.ok_or(Error::<T>::Math)?;
-		if pool_info.swap_fee > zero {
+		let swap_fee = Self::get_dynamic_swap_fee(
+			pool_info.pool_id,
+			pool_info.swap_fee,
+			(
+				pool_info.balances[input_index_usize].into(),
+				balances[output_index_usize],
+			),
+			(balances[input_index_usize], y),
+		)
+		.ok_or(Error::<T>::Math)?;
+		if swap_fee > zero {
 			let fee_amount = u128::try_from(
 				U256::from(dy.saturated_into::<u128>())
-					.checked_mul(U256::from(pool_info.swap_fee.saturated_into::<u128>()))
+					.checked_mul(U256::from(swap_fee.saturated_into::<u128>()))
 					.ok_or(Error::<T>::Math)?
 					.checked_div(U256::from(fee_denominator.saturated_into::<u128>()))
 					.ok_or(Error::<T>::Math)?,
@@ -1327,19 +1412,7 @@ impl<T: Config> Pallet<T> {
 		if output_index_usize >= balance_size {
 			return None;
 		}
-		let fee_denominator: T::AtLeast64BitUnsigned = T::FeePrecision::get();
 		let swap_exact_over_amount = T::SwapExactOverAmount::get();
-		if pool_info.swap_fee > zero {
-			let diff = fee_denominator.checked_sub(&pool_info.swap_fee)?;
-			dy = u128::try_from(
-				U256::from(dy.saturated_into::<u128>())
-					.checked_mul(U256::from(fee_denominator.saturated_into::<u128>()))?
-					.checked_div(U256::from(diff.saturated_into::<u128>()))?,
-			)
-			.ok()?
-			.into();
-		}
-
 		let a: T::AtLeast64BitUnsigned = Self::get_a(
 			pool_info.a,
 			pool_info.a_block,
@@ -1349,9 +1422,24 @@ impl<T: Config> Pallet<T> {
 		let d: T::AtLeast64BitUnsigned = pool_info.total_supply.into();
 		let mut balances: Vec<T::AtLeast64BitUnsigned> =
 			Self::convert_vec_balance_to_number(pool_info.balances.clone());
-		balances[output_index_usize] = balances[output_index_usize]
-			.checked_sub(&dy.checked_mul(&pool_info.precisions[output_index_usize])?)?;
-		let y: T::AtLeast64BitUnsigned = Self::get_y(&balances, input_index, d, a)?;
+		let balance_j = balances[output_index_usize];
+		dy = Self::get_swap_amount_before_fee(dy, pool_info.swap_fee)?;
+		balances[output_index_usize] =
+			balance_j.checked_sub(&dy.checked_mul(&pool_info.precisions[output_index_usize])?)?;
+		let mut y: T::AtLeast64BitUnsigned = Self::get_y(&balances, input_index, d, a)?;
+		if SwapFeeMultipliers::<T>::contains_key(pool_info.pool_id) {
+			// Charge the dynamic fee of the balances the flat fee estimate leaves behind.
+			let swap_fee = Self::get_dynamic_swap_fee(
+				pool_info.pool_id,
+				pool_info.swap_fee,
+				(balances[input_index_usize], balance_j),
+				(y, balances[output_index_usize]),
+			)?;
+			dy = Self::get_swap_amount_before_fee(dy_bal.into(), swap_fee)?;
+			balances[output_index_usize] = balance_j
+				.checked_sub(&dy.checked_mul(&pool_info.precisions[output_index_usize])?)?;
+			y = Self::get_y(&balances, input_index, d, a)?;
+		}
 		let dx: T::AtLeast64BitUnsigned = y
 			.checked_sub(&balances[input_index_usize])?
 			.checked_sub(&one)?
@@ -1737,6 +1825,118 @@ impl<T: Config> Pallet<T> {
 		cloned_stable_asset_info.balances = updated_balances;
 		Ok(cloned_stable_asset_info)
 	}
+
+	/// The swap fee of a swap moving the balances of the input and output assets from `before`
+	/// to `after`. Without a multiplier this is the flat `swap_fee`. Otherwise the off-peg factor
+	/// of the balances over the swap, which rises from 1 for even balances towards `multiplier`
+	/// as they drift apart, scales `swap_fee` up for swaps that worsen the imbalance and down for
+	/// swaps that restore it.
+	pub fn get_dynamic_swap_fee(
+		pool_id: StableAssetPoolId,
+		swap_fee: T::AtLeast64BitUnsigned,
+		before: (T::AtLeast64BitUnsigned, T::AtLeast64BitUnsigned),
+		after: (T::AtLeast64BitUnsigned, T::AtLeast64BitUnsigned),
+	) -> Option<T::AtLeast64BitUnsigned> {
+		let Some(multiplier) = SwapFeeMultipliers::<T>::get(pool_id) else {
+			return Some(swap_fee);
+		};
+		let to_u256 = |x: T::AtLeast64BitUnsigned| U256::from(x.saturated_into::<u128>());
+		let fee_denominator = to_u256(T::FeePrecision::get());
+		let multiplier = to_u256(multiplier);
+		let (xi0, xj0) = (to_u256(before.0), to_u256(before.1));
+		let (xi1, xj1) = (to_u256(after.0), to_u256(after.1));
+		let xpi = xi0.checked_add(xi1)?;
+		let xpj = xj0.checked_add(xj1)?;
+		let xps = xpi.checked_add(xpj)?;
+		if xps.is_zero() {
+			return Some(swap_fee);
+		}
+		// The off-peg factor is `multiplier / denominator`.
+		let denominator = multiplier
+			.checked_sub(fee_denominator)?
+			.checked_mul(U256::from(4u32))?
+			.checked_mul(xpi)?
+			.checked_mul(xpj)?
+			.checked_div(xps.checked_mul(xps)?)?
+			.checked_add(fee_denominator)?;
+		// The imbalances `|xi - xj| / (xi + xj)` before and after the swap, over a common
+		// denominator.
+		let abs_diff = |a: U256, b: U256| if a > b { a - b } else { b - a };
+		let imbalance_before = abs_diff(xi0, xj0).checked_mul(xi1.checked_add(xj1)?)?;
+		let imbalance_after = abs_diff(xi1, xj1).checked_mul(xi0.checked_add(xj0)?)?;
+		let swap_fee = to_u256(swap_fee);
+		let fee = if imbalance_after < imbalance_before {
+			swap_fee.checked_mul(denominator)?.checked_div(multiplier)?
+		} else {
+			swap_fee.checked_mul(multiplier)?.checked_div(denominator)?
+		};
+		u128::try_from(fee).ok().map(Into::into)
+	}
+
+	/// Ensures the highest dynamic swap fee of a pool, `swap_fee * multiplier`, stays below 100%.
+	pub fn ensure_max_swap_fee(
+		pool_id: StableAssetPoolId,
+		swap_fee: T::AtLeast64BitUnsigned,
+	) -> DispatchResult {
+		if let Some(multiplier) = SwapFeeMultipliers::<T>::get(pool_id) {
+			let fee_denominator: T::AtLeast64BitUnsigned = T::FeePrecision::get();
+			ensure!(
+				swap_fee
+					.checked_mul(&multiplier)
+					.zip(fee_denominator.checked_mul(&fee_denominator))
+					.map(|(max_fee, limit)| max_fee < limit)
+					.unwrap_or(false),
+				Error::<T>::ArgumentsError
+			);
+		}
+		Ok(())
+	}
+
+	/// The output amount which leaves `dy` after charging `swap_fee`.
+	fn get_swap_amount_before_fee(
+		dy: T::AtLeast64BitUnsigned,
+		swap_fee: T::AtLeast64BitUnsigned,
+	) -> Option<T::AtLeast64BitUnsigned> {
+		if swap_fee.is_zero() {
+			return Some(dy);
+		}
+		let fee_denominator: T::AtLeast64BitUnsigned = T::FeePrecision::get();
+		let diff = fee_denominator.checked_sub(&swap_fee)?;
+		u128::try_from(
+			U256::from(dy.saturated_into::<u128>())
+				.checked_mul(U256::from(fee_denominator.saturated_into::<u128>()))?
+				.checked_div(U256::from(diff.saturated_into::<u128>()))?,
+		)
+		.ok()
+		.map(Into::into)
+	}
+
+	/// The token rate `(total_supply, D)` of the pool asset, which prices it at the virtual price
+	/// of the pool.
+	pub fn get_virtual_price_rate(
+		pool_info: &StableAssetPoolInfo<
+			T::AssetId,
+			T::AtLeast64BitUnsigned,
+			T::Balance,
+			T::AccountId,
+			BlockNumberFor<T>,
+		>,
+	) -> Option<(T::AtLeast64BitUnsigned, T::AtLeast64BitUnsigned)> {
+		let pool_info = Self::get_balance_update_amount(pool_info).ok()?;
+		let a: T::AtLeast64BitUnsigned = Self::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)?;
+		let d: T::AtLeast64BitUnsigned =
+			Self::get_d(&Self::convert_vec_balance_to_number(pool_info.balances), a)?;
+		let total_supply: T::AtLeast64BitUnsigned = pool_info.total_supply.into();
+		if total_supply.is_zero() || d.is_zero() {
+			return None;
+		}
+		Some((total_supply, d))
+	}
 }
 
 impl<T: Config> StableAsset for Pallet<T> {
@@ -1756,12 +1956,25 @@ impl<T: Config> StableAsset for Pallet<T> {
 	) -> DispatchResult {
 		ensure!(pool_id < PoolCount::<T>::get(), Error::<T>::ArgumentsError);
 		if token_rate_info.last().is_none() {
+			for asset_id in TokenRateCaches::<T>::iter_key_prefix(pool_id) {
+				TokenRatePools::<T>::mutate(asset_id, |pool_ids| {
+					pool_ids.retain(|id| *id != pool_id)
+				});
+			}
 			let res = TokenRateCaches::<T>::clear_prefix(pool_id, u32::max_value(), None);
 			ensure!(res.maybe_cursor.is_none(), Error::<T>::TokenRateNotCleared);
 		} else {
 			let mut token_rate_info = token_rate_info.into_iter();
 			let mut token_rate = token_rate_info.next();
 			while let Some((asset_id, is_token_rate)) = token_rate {
+				TokenRatePools::<T>::try_mutate(asset_id, |pool_ids| -> DispatchResult {
+					if !pool_ids.contains(&pool_id) {
+						pool_ids
+							.try_push(pool_id)
+							.map_err(|_| Error::<T>::TooManyTokenRatePools)?;
+					}
+					Ok(())
+				})?;
 				TokenRateCaches::<T>::insert(pool_id, asset_id, is_token_rate);
 				token_rate = token_rate_info.next();
 			}
@@ -1780,6 +1993,12 @@ impl<T: Config> StableAsset for Pallet<T> {
 		pool_id: StableAssetPoolId,
 		asset_id: Self::AssetId,
 	) -> Option<(Self::AtLeast64BitUnsigned, Self::AtLeast64BitUnsigned)> {
+		// The pool asset of the base pool is priced at its virtual price in the metapool.
+		if let Some(base_pool_info) = BasePools::<T>::get(pool_id).and_then(Pools::<T>::get) {
+			if base_pool_info.pool_asset == asset_id {
+				return Self::get_virtual_price_rate(&base_pool_info);
+			}
+		}
 		TokenRateCaches::<T>::get(pool_id, asset_id)
 	}
 
@@ -1800,6 +2019,43 @@ impl<T: Config> StableAsset for Pallet<T> {
 		PoolCount::<T>::get()
 	}
 
+	fn set_base_pool(
+		pool_id: StableAssetPoolId,
+		base_pool_id: Option<StableAssetPoolId>,
+	) -> DispatchResult {
+		let pool_info = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		// The base pool token is re-priced, which would move the value of the existing liquidity.
+		ensure!(pool_info.total_supply.is_zero(), Error::<T>::PoolNotEmpty);
+		match base_pool_id {
+			Some(base_pool_id) => {
+				let base_pool_info =
+					Pools::<T>::get(base_pool_id).ok_or(Error::<T>::PoolNotFound)?;
+				// Metapools cannot be nested.
+				ensure!(
+					base_pool_id != pool_id
+						&& pool_info.assets.contains(&base_pool_info.pool_asset)
+						&& !BasePools::<T>::contains_key(base_pool_id)
+						&& !BasePools::<T>::iter_values().any(|id| id == pool_id),
+					Error::<T>::InvalidBasePool
+				);
+				BasePools::<T>::insert(pool_id, base_pool_id);
+				Self::deposit_event(Event::BasePoolSet {
+					pool_id,
+					base_pool_id,
+				});
+			}
+			None => {
+				BasePools::<T>::take(pool_id).ok_or(Error::<T>::InvalidBasePool)?;
+				Self::deposit_event(Event::BasePoolRemoved { pool_id });
+			}
+		}
+		Ok(())
+	}
+
+	fn get_base_pool(pool_id: StableAssetPoolId) -> Option<StableAssetPoolId> {
+		BasePools::<T>::get(pool_id)
+	}
+
 	fn pool(
 		id: StableAssetPoolId,
 	) -> Option<
@@ -109,3 +109,59 @@ impl<T: super::Config> OnRuntimeUpgrade for StableAssetOnRuntimeUpgrade<T> {
 		Ok(())
 	}
 }
+
+pub mod v1 {
+	use super::*;
+	use frame_support::traits::GetStorageVersion;
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
+			let in_code_storage_version = Pallet::<T>::in_code_storage_version();
+			if on_chain_storage_version == 0 && in_code_storage_version == 1 {
+				let weight_consumed = migrate_to_v1::<T>();
+				log::info!("Migrating stable-asset storage to v1");
+				in_code_storage_version.put::<Pallet<T>>();
+				weight_consumed.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!("stable-asset migration should be removed.");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_: sp_std::prelude::Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"on_chain_storage_version should be 1"
+			);
+			for (pool_id, asset_id) in TokenRateCaches::<T>::iter_keys() {
+				ensure!(
+					TokenRatePools::<T>::get(asset_id).contains(&pool_id),
+					"TokenRatePools should index every token rate"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Index the pools of the token rates in `TokenRateCaches` by asset in `TokenRatePools`.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	let mut count: u64 = 0;
+	for (pool_id, asset_id) in TokenRateCaches::<T>::iter_keys() {
+		count += 1;
+		TokenRatePools::<T>::mutate(asset_id, |pool_ids| {
+			if pool_ids.try_push(pool_id).is_err() {
+				log::warn!(
+					"Token rate of asset {:?} in pool {:?} not indexed",
+					asset_id,
+					pool_id
+				);
+			}
+		});
+	}
+
+	T::DbWeight::get().reads_writes(count.saturating_mul(2), count)
+}
@@ -18,7 +18,8 @@
 
 use crate::{
 	mock::*, traits::StableAsset as StableAssetInterface, Error, MintResult, PoolCount, Pools,
-	RedeemMultiResult, RedeemProportionResult, RedeemSingleResult, StableAssetPoolInfo, SwapResult,
+	RedeemMultiResult, RedeemProportionResult, RedeemSingleResult, StableAssetPoolInfo,
+	SwapFeeMultipliers, SwapResult,
 };
 use frame_support::{assert_noop, assert_ok};
 use orml_traits::MultiCurrency;
@@ -1198,6 +1199,45 @@ fn modify_fees_successful() {
 	});
 }
 
+#[test]
+fn modify_fees_should_keep_dynamic_swap_fee_below_one() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		SwapFeeMultipliers::<Test>::insert(0, 50_000_000_000u128);
+
+		// 20% * 5 reaches 100%.
+		assert_noop!(
+			StableAsset::modify_fees(RuntimeOrigin::signed(1), 0, None, Some(2_000_000_000), None),
+			Error::<Test>::ArgumentsError
+		);
+		assert_ok!(StableAsset::modify_fees(
+			RuntimeOrigin::signed(1),
+			0,
+			None,
+			Some(1_000_000_000),
+			None
+		));
+	});
+}
+
+#[test]
+fn dynamic_swap_fee_should_follow_the_imbalance() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let swap_fee = 20_000_000u128;
+		assert_eq!(
+			StableAsset::get_dynamic_swap_fee(0, swap_fee, (100, 20), (110, 10)),
+			Some(swap_fee)
+		);
+
+		SwapFeeMultipliers::<Test>::insert(0, 50_000_000_000u128);
+		let worsen = StableAsset::get_dynamic_swap_fee(0, swap_fee, (100, 20), (110, 10)).unwrap();
+		let restore = StableAsset::get_dynamic_swap_fee(0, swap_fee, (100, 20), (90, 30)).unwrap();
+		assert!(worsen > swap_fee && worsen < 5 * swap_fee);
+		assert!(restore < swap_fee && restore > swap_fee / 5);
+	});
+}
+
 #[test]
 fn get_mint_amount_same_as_mint() {
 	new_test_ext().execute_with(|| {
@@ -1740,3 +1780,72 @@ fn swap_should_work_with_unbalance_coin1() {
 		));
 	});
 }
+
+#[test]
+fn set_base_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		let (coin0, coin1, pool_asset, _) = create_pool();
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10_000_000u128, 10_000_000u128],
+			0
+		));
+		let coin2 = TestAssets::create_asset().expect("asset should be created");
+		let meta_pool_asset = TestAssets::create_asset().expect("asset should be created");
+		assert_ok!(StableAsset::create_pool(
+			RuntimeOrigin::signed(1),
+			meta_pool_asset,
+			vec![coin2, pool_asset],
+			vec![10000000000u128, 1u128],
+			0u128,
+			0u128,
+			0u128,
+			10000u128,
+			2,
+			1,
+			1000000000000000000u128,
+		));
+
+		assert_noop!(
+			StableAsset::set_base_pool(1, Some(2)),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			StableAsset::set_base_pool(1, Some(1)),
+			Error::<Test>::InvalidBasePool
+		);
+		// The pool asset of pool 1 is not in pool 0.
+		assert_noop!(
+			StableAsset::set_base_pool(0, Some(1)),
+			Error::<Test>::InvalidBasePool
+		);
+		assert_noop!(
+			StableAsset::set_base_pool(1, None),
+			Error::<Test>::InvalidBasePool
+		);
+
+		assert_eq!(StableAsset::get_token_rate(1, pool_asset), None);
+		assert_ok!(StableAsset::set_base_pool(1, Some(0)));
+		assert_eq!(StableAsset::get_base_pool(1), Some(0));
+		let base_pool_info = Pools::<Test>::get(0).unwrap();
+		let rate = StableAsset::get_virtual_price_rate(&base_pool_info);
+		assert!(rate.is_some());
+		assert_eq!(StableAsset::get_token_rate(1, pool_asset), rate);
+		assert_eq!(StableAsset::get_token_rate(1, coin2), None);
+		assert_eq!(StableAsset::get_token_rate(0, coin0), Some((1, 1)));
+		assert_eq!(StableAsset::get_token_rate(0, coin1), Some((1, 1)));
+
+		// The base pool of a pool with liquidity is not changed.
+		Pools::<Test>::mutate(1, |pool| pool.as_mut().unwrap().total_supply = 1);
+		assert_noop!(
+			StableAsset::set_base_pool(1, None),
+			Error::<Test>::PoolNotEmpty
+		);
+		Pools::<Test>::mutate(1, |pool| pool.as_mut().unwrap().total_supply = 0);
+
+		assert_ok!(StableAsset::set_base_pool(1, None));
+		assert_eq!(StableAsset::get_base_pool(1), None);
+		assert_eq!(StableAsset::get_token_rate(1, pool_asset), None);
+	});
+}
@@ -18,11 +18,13 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::Balance;
+use bifrost_primitives::{AccountId, Balance, BlockNumber, CurrencyId};
+use bifrost_stable_asset::StableAssetPoolInfo;
 use sp_api::decl_runtime_apis;
 use sp_std::vec::Vec;
 
 decl_runtime_apis! {
+	#[api_version(3)]
 	pub trait StablePoolRuntimeApi
 	{
 		fn get_swap_output(
@@ -36,5 +38,58 @@ decl_runtime_apis! {
 			pool_id: u32,
 			amounts: Vec<Balance>,
 		) -> Balance;
+
+		#[api_version(2)]
+		fn get_swap_input(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> Balance;
+
+		#[api_version(2)]
+		fn get_redeem_proportion_amount(
+			pool_id: u32,
+			amount: Balance,
+		) -> Vec<Balance>;
+
+		#[api_version(2)]
+		fn get_redeem_single_amount(
+			pool_id: u32,
+			amount: Balance,
+			i: u32,
+		) -> Balance;
+
+		#[api_version(2)]
+		fn get_redeem_multi_amount(
+			pool_id: u32,
+			amounts: Vec<Balance>,
+		) -> Balance;
+
+		/// `D / total_supply` of the pool, with 18 decimals.
+		#[api_version(2)]
+		fn get_virtual_price(pool_id: u32) -> Balance;
+
+		/// The current amplification coefficient, scaled by `APrecision`.
+		#[api_version(2)]
+		fn get_current_a(pool_id: u32) -> Balance;
+
+		#[api_version(2)]
+		fn get_pool_info(
+			pool_id: u32,
+		) -> Option<StableAssetPoolInfo<CurrencyId, Balance, Balance, AccountId, BlockNumber>>;
+
+		/// Output of a metapool swap between underlying assets, indexed as in
+		/// `get_underlying_assets`.
+		#[api_version(3)]
+		fn get_swap_underlying_output(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> Balance;
+
+		#[api_version(3)]
+		fn get_underlying_assets(pool_id: u32) -> Vec<CurrencyId>;
 	}
 }
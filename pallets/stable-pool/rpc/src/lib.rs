@@ -18,18 +18,21 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use bifrost_primitives::Balance;
+use bifrost_primitives::{AccountId, Balance, BlockNumber, CurrencyId};
+use bifrost_stable_asset::StableAssetPoolInfo;
 pub use bifrost_stable_pool_rpc_runtime_api::{self as runtime_api, StablePoolRuntimeApi};
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
 	types::error::{ErrorCode, ErrorObject},
 };
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::traits::Block as BlockT;
 
+type PoolInfo = StableAssetPoolInfo<CurrencyId, Balance, Balance, AccountId, BlockNumber>;
+
 #[rpc(client, server)]
 pub trait StablePoolRpcApi<BlockHash> {
 	/// rpc method for getting stable_pool swap output amount
@@ -50,6 +53,70 @@ pub trait StablePoolRpcApi<BlockHash> {
 		amounts: Vec<Balance>,
 		at: Option<BlockHash>,
 	) -> RpcResult<NumberOrHex>;
+
+	/// rpc method for getting the stable_pool swap input amount for an exact output amount
+	#[method(name = "stable_pool_getSwapInputAmount")]
+	fn get_swap_input_amount(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "stable_pool_getRedeemProportionAmount")]
+	fn get_redeem_proportion_amount(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<NumberOrHex>>;
+
+	#[method(name = "stable_pool_getRedeemSingleAmount")]
+	fn get_redeem_single_amount(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		i: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "stable_pool_getRedeemMultiAmount")]
+	fn get_redeem_multi_amount(
+		&self,
+		pool_id: u32,
+		amounts: Vec<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	/// rpc method for getting the virtual price of the pool token, with 18 decimals
+	#[method(name = "stable_pool_getVirtualPrice")]
+	fn get_virtual_price(&self, pool_id: u32, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "stable_pool_getCurrentA")]
+	fn get_current_a(&self, pool_id: u32, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "stable_pool_getPoolInfo")]
+	fn get_pool_info(&self, pool_id: u32, at: Option<BlockHash>) -> RpcResult<Option<PoolInfo>>;
+
+	/// rpc method for getting the output of a metapool swap between underlying assets
+	#[method(name = "stable_pool_getSwapUnderlyingOutputAmount")]
+	fn get_swap_underlying_output_amount(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "stable_pool_getUnderlyingAssets")]
+	fn get_underlying_assets(
+		&self,
+		pool_id: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<CurrencyId>>;
 }
 
 #[derive(Clone, Debug)]
@@ -118,4 +185,245 @@ where
 			)),
 		}
 	}
+
+	fn get_swap_input_amount(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Balance, _> =
+			lm_rpc_api.get_swap_input(at, pool_id, currency_id_in, currency_id_out, amount);
+
+		match rs {
+			Ok(amount) => Ok(NumberOrHex::Hex(amount.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool swap input amount.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_redeem_proportion_amount(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<NumberOrHex>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Vec<Balance>, _> =
+			lm_rpc_api.get_redeem_proportion_amount(at, pool_id, amount);
+
+		match rs {
+			Ok(amounts) => Ok(amounts
+				.into_iter()
+				.map(|amount| NumberOrHex::Hex(amount.into()))
+				.collect()),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool redeem proportion amount.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_redeem_single_amount(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		i: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Balance, _> = lm_rpc_api.get_redeem_single_amount(at, pool_id, amount, i);
+
+		match rs {
+			Ok(amount) => Ok(NumberOrHex::Hex(amount.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool redeem single amount.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_redeem_multi_amount(
+		&self,
+		pool_id: u32,
+		amounts: Vec<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Balance, _> = lm_rpc_api.get_redeem_multi_amount(at, pool_id, amounts);
+
+		match rs {
+			Ok(amount) => Ok(NumberOrHex::Hex(amount.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool redeem multi amount.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_virtual_price(
+		&self,
+		pool_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Balance, _> = lm_rpc_api.get_virtual_price(at, pool_id);
+
+		match rs {
+			Ok(price) => Ok(NumberOrHex::Hex(price.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool virtual price.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_current_a(
+		&self,
+		pool_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Balance, _> = lm_rpc_api.get_current_a(at, pool_id);
+
+		match rs {
+			Ok(a) => Ok(NumberOrHex::Hex(a.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool current A.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_pool_info(
+		&self,
+		pool_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolInfo>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 2)?;
+
+		let rs: Result<Option<PoolInfo>, _> = lm_rpc_api.get_pool_info(at, pool_id);
+
+		match rs {
+			Ok(pool_info) => Ok(pool_info),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool pool info.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_swap_underlying_output_amount(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 3)?;
+
+		let rs: Result<Balance, _> = lm_rpc_api.get_swap_underlying_output(
+			at,
+			pool_id,
+			currency_id_in,
+			currency_id_out,
+			amount,
+		);
+
+		match rs {
+			Ok(amount) => Ok(NumberOrHex::Hex(amount.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool swap underlying output amount.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_underlying_assets(
+		&self,
+		pool_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<CurrencyId>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		ensure_api_version(&*self.client, at, 3)?;
+
+		let rs: Result<Vec<CurrencyId>, _> = lm_rpc_api.get_underlying_assets(at, pool_id);
+
+		match rs {
+			Ok(assets) => Ok(assets),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool underlying assets.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+}
+
+/// Ensures the runtime at `at` implements the version of `StablePoolRuntimeApi` a method was
+/// added in.
+fn ensure_api_version<C, Block>(client: &C, at: Block::Hash, required: u32) -> RpcResult<()>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: StablePoolRuntimeApi<Block>,
+{
+	let api_version = client
+		.runtime_api()
+		.api_version::<dyn StablePoolRuntimeApi<Block>>(at)
+		.map_err(|e| {
+			ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool runtime API version.",
+				Some(format!("{:?}", e)),
+			)
+		})?;
+	match api_version {
+		Some(version) if version >= required => Ok(()),
+		_ => Err(ErrorObject::owned(
+			ErrorCode::MethodNotFound.code(),
+			"Runtime API method not supported.",
+			Some(format!(
+				"requires StablePoolRuntimeApi version {}, found {:?}",
+				required, api_version
+			)),
+		)),
+	}
 }
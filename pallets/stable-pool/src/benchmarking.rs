@@ -18,7 +18,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use crate::{Pallet as StablePool, *};
-pub use bifrost_primitives::{BNC, DOT, KSM, VDOT};
+pub use bifrost_primitives::{CurrencyId, BNC, DOT, KSM, VDOT};
 use frame_benchmarking::v2::*;
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
@@ -473,6 +473,7 @@ mod benchmarks {
 			Some(1000u128.into()),
 			Some(1000u128.into()),
 			Some(1000u128.into()),
+			Some(20_000_000_000u128.into()),
 		);
 
 		Ok(())
@@ -509,6 +510,129 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_base_pool() -> Result<(), BenchmarkError> {
+		let fee_account: T::AccountId = account("seed", 1, 1);
+
+		assert_ok!(StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![BNC.into(), KSM.into()],
+			vec![1u128.into(), 1u128.into()],
+			0u128.into(),
+			0u128.into(),
+			0u128.into(),
+			220u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000u128.into()
+		));
+		assert_ok!(StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![DOT.into(), CurrencyId::BLP(0).into()],
+			vec![1u128.into(), 1u128.into()],
+			0u128.into(),
+			0u128.into(),
+			0u128.into(),
+			220u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000u128.into()
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 1, Some(0));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn swap_underlying() -> Result<(), BenchmarkError> {
+		let fee_account: T::AccountId = account("seed", 1, 1);
+
+		for currency_id in [BNC, KSM, DOT] {
+			T::MultiCurrency::deposit(
+				currency_id.into(),
+				&fee_account,
+				<T as bifrost_stable_asset::Config>::Balance::from(1000_000_000_000u128.into()),
+			)?;
+		}
+
+		assert_ok!(StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![BNC.into(), KSM.into()],
+			vec![1u128.into(), 1u128.into()],
+			0u128.into(),
+			0u128.into(),
+			0u128.into(),
+			220u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000u128.into()
+		));
+		assert_ok!(StablePool::<T>::edit_token_rate(
+			RawOrigin::Root.into(),
+			0,
+			vec![
+				(BNC.into(), (1u128.into(), 1u128.into())),
+				(KSM.into(), (1u128.into(), 1u128.into()))
+			]
+		));
+		assert_ok!(StablePool::<T>::add_liquidity(
+			RawOrigin::Signed(fee_account.clone()).into(),
+			0,
+			vec![
+				<T as bifrost_stable_asset::Config>::Balance::from(100_000_000_000u128.into()),
+				<T as bifrost_stable_asset::Config>::Balance::from(100_000_000_000u128.into()),
+			],
+			<T as bifrost_stable_asset::Config>::Balance::zero()
+		));
+
+		assert_ok!(StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![DOT.into(), CurrencyId::BLP(0).into()],
+			vec![1u128.into(), 1u128.into()],
+			0u128.into(),
+			0u128.into(),
+			0u128.into(),
+			220u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000u128.into()
+		));
+		assert_ok!(StablePool::<T>::edit_token_rate(
+			RawOrigin::Root.into(),
+			1,
+			vec![(DOT.into(), (1u128.into(), 1u128.into()))]
+		));
+		assert_ok!(StablePool::<T>::set_base_pool(
+			RawOrigin::Root.into(),
+			1,
+			Some(0)
+		));
+		assert_ok!(StablePool::<T>::add_liquidity(
+			RawOrigin::Signed(fee_account.clone()).into(),
+			1,
+			vec![
+				<T as bifrost_stable_asset::Config>::Balance::from(100_000_000_000u128.into()),
+				<T as bifrost_stable_asset::Config>::Balance::from(100_000_000_000u128.into()),
+			],
+			<T as bifrost_stable_asset::Config>::Balance::zero()
+		));
+
+		// DOT to BNC, through the base pool.
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(fee_account),
+			1,
+			0,
+			1,
+			<T as bifrost_stable_asset::Config>::Balance::from(1_000_000_000u128.into()),
+			<T as bifrost_stable_asset::Config>::Balance::zero(),
+		);
+
+		Ok(())
+	}
+
 	// This line generates test cases for benchmarking, and could be run by:
 	//   `cargo test -p pallet-example-basic --all-features`, you will see one line per case:
 	//   `test benchmarking::bench_sort_vector ... ok`
@@ -36,14 +36,14 @@ use bifrost_primitives::{
 };
 pub use bifrost_stable_asset::{
 	MintResult, PoolCount, PoolTokenIndex, Pools, RedeemMultiResult, RedeemProportionResult,
-	RedeemSingleResult, StableAsset, StableAssetPoolId, StableAssetPoolInfo, SwapResult,
-	TokenRateHardcap,
+	RedeemSingleResult, StableAsset, StableAssetPoolId, StableAssetPoolInfo, SwapFeeMultipliers,
+	SwapResult, TokenRateHardcap,
 };
 use frame_support::{self, pallet_prelude::*, sp_runtime::traits::Zero, transactional};
 use frame_system::pallet_prelude::*;
 use orml_traits::MultiCurrency;
 use sp_core::U256;
-use sp_runtime::{Permill, SaturatedConversion};
+use sp_runtime::{traits::CheckedSub, FixedPointNumber, FixedU128, Permill, SaturatedConversion};
 use sp_std::prelude::*;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -114,6 +114,8 @@ pub mod pallet {
 		RedeemOverMax,
 		/// The token rate is not set, preventing related operations.
 		TokenRateNotSet,
+		/// The pool is not a metapool.
+		NotMetaPool,
 	}
 
 	#[pallet::call]
@@ -253,6 +255,11 @@ pub mod pallet {
 		}
 
 		/// Modifies the fees of a specified liquidity pool.
+		///
+		/// `swap_fee_multiplier` is the off-peg multiplier of the swap fee, in `FeePrecision`
+		/// units: swaps between unbalanced assets pay up to `swap_fee * multiplier`, so swaps that
+		/// worsen the balance pay more and swaps that restore it pay less. A multiplier of
+		/// `FeePrecision` switches the pool back to a flat swap fee.
 		#[pallet::call_index(7)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::modify_fees())]
 		pub fn modify_fees(
@@ -261,6 +268,7 @@ pub mod pallet {
 			mint_fee: Option<T::AtLeast64BitUnsigned>,
 			swap_fee: Option<T::AtLeast64BitUnsigned>,
 			redeem_fee: Option<T::AtLeast64BitUnsigned>,
+			swap_fee_multiplier: Option<T::AtLeast64BitUnsigned>,
 		) -> DispatchResult {
 			// Ensure the caller has the necessary control origin
 			T::ControlOrigin::ensure_origin(origin)?;
@@ -270,7 +278,10 @@ pub mod pallet {
 			ensure!(
 				mint_fee.map(|x| x < fee_denominator).unwrap_or(true)
 					&& swap_fee.map(|x| x < fee_denominator).unwrap_or(true)
-					&& redeem_fee.map(|x| x < fee_denominator).unwrap_or(true),
+					&& redeem_fee.map(|x| x < fee_denominator).unwrap_or(true)
+					&& swap_fee_multiplier
+						.map(|x| x >= fee_denominator)
+						.unwrap_or(true),
 				bifrost_stable_asset::Error::<T>::ArgumentsError
 			);
 
@@ -288,6 +299,20 @@ pub mod pallet {
 				if let Some(fee) = redeem_fee {
 					pool_info.redeem_fee = fee;
 				}
+				if let Some(multiplier) = swap_fee_multiplier {
+					let multiplier = (multiplier != fee_denominator).then_some(multiplier);
+					SwapFeeMultipliers::<T>::set(pool_id, multiplier);
+					bifrost_stable_asset::Pallet::<T>::deposit_event(
+						bifrost_stable_asset::Event::<T>::SwapFeeMultiplierModified {
+							pool_id,
+							multiplier,
+						},
+					);
+				}
+				bifrost_stable_asset::Pallet::<T>::ensure_max_swap_fee(
+					pool_id,
+					pool_info.swap_fee,
+				)?;
 				// Emit an event indicating that the fees have been modified
 				bifrost_stable_asset::Pallet::<T>::deposit_event(
 					bifrost_stable_asset::Event::<T>::FeeModified {
@@ -403,9 +428,54 @@ pub mod pallet {
 			);
 			Ok(())
 		}
+
+		/// Sets the base pool of a metapool, or removes it with `None`.
+		///
+		/// The pool token of the base pool must be one of the metapool assets. It is priced at the
+		/// virtual price of the base pool, so no token rate is needed for it. The metapool must
+		/// have no liquidity.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_base_pool())]
+		pub fn set_base_pool(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+			base_pool_id: Option<StableAssetPoolId>,
+		) -> DispatchResult {
+			// Ensure the caller has the necessary control origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			T::StableAsset::set_base_pool(pool_id, base_pool_id)
+		}
+
+		/// Swaps between the underlying assets of a metapool, routing through the base pool
+		/// when one side is a base pool asset.
+		///
+		/// `i` and `j` index the underlying assets of the metapool, see
+		/// `get_underlying_assets`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_underlying())]
+		pub fn swap_underlying(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+			i: PoolTokenIndex,
+			j: PoolTokenIndex,
+			dx: T::Balance,
+			min_dy: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::swap_underlying_inner(&who, pool_id, i, j, dx, min_dy)?;
+			Ok(())
+		}
 	}
 }
 
+/// Where an underlying asset of a metapool is swapped.
+enum UnderlyingIndex {
+	/// The asset index in the metapool.
+	Meta(PoolTokenIndex),
+	/// The asset index in the base pool.
+	Base(PoolTokenIndex),
+}
+
 impl<T: Config> Pallet<T> {
 	fn ensure_can_refresh(
 		token_in: AssetIdOf<T>,
@@ -476,10 +546,105 @@ impl<T: Config> Pallet<T> {
 		None
 	}
 
+	/// Whether the new rate of `vtoken` in `pool_id` moved from its current rate by more than the
+	/// hardcap, upwards or downwards.
+	fn exceeds_token_rate_hardcap(
+		pool_id: StableAssetPoolId,
+		vtoken: AssetIdOf<T>,
+		vtoken_issuance: AtLeast64BitUnsignedOf<T>,
+		token_pool_amount: AtLeast64BitUnsignedOf<T>,
+		hardcap: Permill,
+	) -> bool {
+		let Some((demoninator, numerator)) =
+			bifrost_stable_asset::Pallet::<T>::get_token_rate(pool_id, vtoken)
+		else {
+			return false;
+		};
+		let fee_denominator = U256::from(T::FeePrecision::get().saturated_into::<u128>());
+		let numerator_u256 = U256::from(numerator.saturated_into::<u128>());
+		let demoninator_u256 = U256::from(demoninator.saturated_into::<u128>());
+		let new_price = fee_denominator
+			.saturating_mul(U256::from(token_pool_amount.saturated_into::<u128>()))
+			.checked_div(U256::from(vtoken_issuance.saturated_into::<u128>()));
+		let delta = U256::from(hardcap * T::FeePrecision::get().saturated_into::<u128>());
+		let max_price = fee_denominator
+			.saturating_add(delta)
+			.saturating_mul(numerator_u256)
+			.checked_div(demoninator_u256);
+		let min_price = fee_denominator
+			.saturating_sub(delta)
+			.saturating_mul(numerator_u256)
+			.checked_div(demoninator_u256);
+		match (new_price, max_price, min_price) {
+			(Some(new_price), Some(max_price), Some(min_price)) => {
+				new_price > max_price || new_price < min_price
+			}
+			_ => false,
+		}
+	}
+
 	fn get_token_rate_hardcap(vtoken: AssetIdOf<T>) -> Option<Permill> {
 		TokenRateHardcap::<T>::get(vtoken)
 	}
 
+	/// Refreshes the token rate of `vtoken` in every pool holding it, after vtoken-minting
+	/// reports that `vtoken_amount` of it is now worth `token_amount`.
+	///
+	/// Only vTokens configured with `config_vtoken_auto_refresh` are refreshed. A rate that went
+	/// down is kept as it is, like on swaps. A rate that went up or down by more than the hardcap
+	/// is not applied and raises `TokenRateHardcapExceeded` instead.
+	pub fn on_vtoken_rate_changed(
+		vtoken: AssetIdOf<T>,
+		vtoken_amount: T::Balance,
+		token_amount: T::Balance,
+	) -> Weight {
+		let Some(hardcap) = Self::get_token_rate_hardcap(vtoken) else {
+			return T::DbWeight::get().reads(1);
+		};
+		let vtoken_amount: AtLeast64BitUnsignedOf<T> = vtoken_amount.into();
+		let token_amount: AtLeast64BitUnsignedOf<T> = token_amount.into();
+		if vtoken_amount.is_zero() || token_amount.is_zero() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut reads: u64 = 2;
+		let mut writes: u64 = 0;
+		for pool_id in bifrost_stable_asset::TokenRatePools::<T>::get(vtoken) {
+			reads += 2;
+			if bifrost_stable_asset::Pallet::<T>::get_token_rate(pool_id, vtoken).is_none() {
+				continue;
+			}
+			if Self::refresh_token_rate(pool_id, vtoken, vtoken_amount, token_amount, hardcap)
+				.is_some()
+			{
+				reads += 3;
+				writes += 2;
+			} else if Self::exceeds_token_rate_hardcap(
+				pool_id,
+				vtoken,
+				vtoken_amount,
+				token_amount,
+				hardcap,
+			) {
+				bifrost_stable_asset::Pallet::<T>::deposit_event(
+					bifrost_stable_asset::Event::<T>::TokenRateHardcapExceeded {
+						pool_id,
+						vtoken,
+						token_rate: (vtoken_amount, token_amount),
+					},
+				)
+			}
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// The upper bound of the weight of `on_vtoken_rate_changed`, refreshing the token rate of
+	/// the vToken in `MAX_TOKEN_RATE_POOLS` pools.
+	pub fn on_vtoken_rate_changed_weight() -> Weight {
+		let pools = bifrost_stable_asset::MAX_TOKEN_RATE_POOLS as u64;
+		T::DbWeight::get().reads_writes(2 + 5 * pools, 2 * pools)
+	}
+
 	#[transactional]
 	fn mint_inner(
 		who: &AccountIdOf<T>,
@@ -1068,6 +1233,323 @@ impl<T: Config> Pallet<T> {
 		Ok(mint_amount)
 	}
 
+	pub fn get_redeem_proportion_amount(
+		pool_id: StableAssetPoolId,
+		amount: T::Balance,
+	) -> Result<Vec<T::Balance>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let RedeemProportionResult { mut amounts, .. } =
+			bifrost_stable_asset::Pallet::<T>::get_redeem_proportion_amount(&pool_info, amount)?;
+		for (i, amount) in amounts.iter_mut().enumerate() {
+			*amount = Self::downscale(
+				*amount,
+				pool_id,
+				*pool_info
+					.assets
+					.get(i as usize)
+					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+			)?;
+		}
+
+		Ok(amounts)
+	}
+
+	pub fn get_redeem_single_amount(
+		pool_id: StableAssetPoolId,
+		amount: T::Balance,
+		i: PoolTokenIndex,
+	) -> Result<T::Balance, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let RedeemSingleResult { dy, .. } =
+			bifrost_stable_asset::Pallet::<T>::get_redeem_single_amount(&pool_info, amount, i)?;
+		let downscale_out = Self::downscale(
+			dy,
+			pool_id,
+			*pool_info
+				.assets
+				.get(i as usize)
+				.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+		)?;
+
+		Ok(downscale_out)
+	}
+
+	pub fn get_redeem_multi_amount(
+		pool_id: StableAssetPoolId,
+		mut amounts: Vec<T::Balance>,
+	) -> Result<T::Balance, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		for (i, amount) in amounts.iter_mut().enumerate() {
+			*amount = Self::upscale(
+				*amount,
+				pool_id,
+				*pool_info
+					.assets
+					.get(i as usize)
+					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+			)?;
+		}
+		let RedeemMultiResult { redeem_amount, .. } =
+			bifrost_stable_asset::Pallet::<T>::get_redeem_multi_amount(&pool_info, &amounts)?;
+
+		Ok(redeem_amount)
+	}
+
+	/// The current amplification coefficient of the pool, taking an ongoing ramp into account.
+	/// It is scaled by `APrecision`.
+	pub fn get_current_a(
+		pool_id: StableAssetPoolId,
+	) -> Result<AtLeast64BitUnsignedOf<T>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let a = bifrost_stable_asset::Pallet::<T>::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)
+		.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+
+		Ok(a)
+	}
+
+	/// The value of one pool token, `D / total_supply`, with the pool balances refreshed from
+	/// the pool account and the current token rates.
+	pub fn get_virtual_price(pool_id: StableAssetPoolId) -> Result<FixedU128, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let updated_pool_info =
+			bifrost_stable_asset::Pallet::<T>::get_balance_update_amount(&pool_info)?;
+		let balances = bifrost_stable_asset::Pallet::<T>::convert_vec_balance_to_number(
+			updated_pool_info.balances,
+		);
+		let d = bifrost_stable_asset::Pallet::<T>::get_d(&balances, Self::get_current_a(pool_id)?)
+			.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+
+		FixedU128::checked_from_rational(
+			d.saturated_into::<u128>(),
+			pool_info.total_supply.saturated_into::<u128>(),
+		)
+		.ok_or(bifrost_stable_asset::Error::<T>::Math.into())
+	}
+
+	/// The underlying assets of a metapool: the metapool assets without the base pool token,
+	/// followed by the base pool assets.
+	pub fn get_underlying_assets(
+		pool_id: StableAssetPoolId,
+	) -> Result<Vec<AssetIdOf<T>>, DispatchError> {
+		let (pool_info, base_pool_info) = Self::get_meta_pool_infos(pool_id)?;
+		Ok(pool_info
+			.assets
+			.into_iter()
+			.filter(|asset| *asset != base_pool_info.pool_asset)
+			.chain(base_pool_info.assets)
+			.collect())
+	}
+
+	/// Quotes the output of `swap_underlying`.
+	pub fn get_swap_underlying_output(
+		pool_id: StableAssetPoolId,
+		i: PoolTokenIndex,
+		j: PoolTokenIndex,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		let (base_pool_id, lp_index, i, j) = Self::get_underlying_indexes(pool_id, i, j)?;
+		match (i, j) {
+			(UnderlyingIndex::Meta(i), UnderlyingIndex::Meta(j)) => {
+				Self::get_swap_output(pool_id, i, j, amount)
+			}
+			(UnderlyingIndex::Base(i), UnderlyingIndex::Base(j)) => {
+				Self::get_swap_output(base_pool_id, i, j, amount)
+			}
+			(UnderlyingIndex::Meta(i), UnderlyingIndex::Base(j)) => {
+				let lp_amount = Self::get_swap_output(pool_id, i, lp_index, amount)?;
+				Self::get_redeem_single_amount(base_pool_id, lp_amount, j)
+			}
+			(UnderlyingIndex::Base(i), UnderlyingIndex::Meta(j)) => {
+				let lp_amount = Self::add_liquidity_amount(
+					base_pool_id,
+					Self::single_asset_amounts(base_pool_id, i, amount)?,
+				)?;
+				Self::get_swap_output(pool_id, lp_index, j, lp_amount)
+			}
+		}
+	}
+
+	#[transactional]
+	fn swap_underlying_inner(
+		who: &AccountIdOf<T>,
+		pool_id: StableAssetPoolId,
+		i: PoolTokenIndex,
+		j: PoolTokenIndex,
+		dx: T::Balance,
+		min_dy: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		let (base_pool_id, lp_index, i, j) = Self::get_underlying_indexes(pool_id, i, j)?;
+		let dy = match (i, j) {
+			(UnderlyingIndex::Meta(i), UnderlyingIndex::Meta(j)) => {
+				Self::swap_and_get_output(who, pool_id, i, j, dx)?
+			}
+			(UnderlyingIndex::Base(i), UnderlyingIndex::Base(j)) => {
+				Self::swap_and_get_output(who, base_pool_id, i, j, dx)?
+			}
+			(UnderlyingIndex::Meta(i), UnderlyingIndex::Base(j)) => {
+				let lp_amount = Self::swap_and_get_output(who, pool_id, i, lp_index, dx)?;
+				let asset_length = T::StableAsset::pool(base_pool_id)
+					.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?
+					.assets
+					.len() as u32;
+				Self::redeem_single_inner(
+					who,
+					base_pool_id,
+					lp_amount,
+					j,
+					Zero::zero(),
+					asset_length,
+				)?
+				.1
+			}
+			(UnderlyingIndex::Base(i), UnderlyingIndex::Meta(j)) => {
+				let pool_asset = T::StableAsset::pool(base_pool_id)
+					.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?
+					.pool_asset;
+				let balance_before = T::MultiCurrency::free_balance(pool_asset, who);
+				Self::mint_inner(
+					who,
+					base_pool_id,
+					Self::single_asset_amounts(base_pool_id, i, dx)?,
+					Zero::zero(),
+				)?;
+				let lp_amount = Self::get_balance_increase(pool_asset, who, balance_before)?;
+				Self::swap_and_get_output(who, pool_id, lp_index, j, lp_amount)?
+			}
+		};
+		ensure!(dy >= min_dy, Error::<T>::SwapUnderMin);
+		Ok(dy)
+	}
+
+	/// Swaps in the pool without a minimum output and returns the amount received.
+	fn swap_and_get_output(
+		who: &AccountIdOf<T>,
+		pool_id: StableAssetPoolId,
+		i: PoolTokenIndex,
+		j: PoolTokenIndex,
+		dx: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		let asset_out = *T::StableAsset::pool(pool_id)
+			.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?
+			.assets
+			.get(j as usize)
+			.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?;
+		let balance_before = T::MultiCurrency::free_balance(asset_out, who);
+		Self::on_swap(who, pool_id, i, j, dx, Zero::zero())?;
+		Self::get_balance_increase(asset_out, who, balance_before)
+	}
+
+	fn get_balance_increase(
+		currency_id: AssetIdOf<T>,
+		who: &AccountIdOf<T>,
+		balance_before: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		let balance_after: AtLeast64BitUnsignedOf<T> =
+			T::MultiCurrency::free_balance(currency_id, who).into();
+		let increase = balance_after
+			.checked_sub(&balance_before.into())
+			.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+		Ok(increase.into())
+	}
+
+	fn single_asset_amounts(
+		pool_id: StableAssetPoolId,
+		i: PoolTokenIndex,
+		amount: T::Balance,
+	) -> Result<Vec<T::Balance>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let mut amounts = sp_std::vec![Zero::zero(); pool_info.assets.len()];
+		*amounts
+			.get_mut(i as usize)
+			.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)? = amount;
+		Ok(amounts)
+	}
+
+	fn get_meta_pool_infos(
+		pool_id: StableAssetPoolId,
+	) -> Result<
+		(
+			StableAssetPoolInfo<
+				AssetIdOf<T>,
+				AtLeast64BitUnsignedOf<T>,
+				T::Balance,
+				AccountIdOf<T>,
+				BlockNumberFor<T>,
+			>,
+			StableAssetPoolInfo<
+				AssetIdOf<T>,
+				AtLeast64BitUnsignedOf<T>,
+				T::Balance,
+				AccountIdOf<T>,
+				BlockNumberFor<T>,
+			>,
+		),
+		DispatchError,
+	> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let base_pool_info = T::StableAsset::get_base_pool(pool_id)
+			.and_then(T::StableAsset::pool)
+			.ok_or(Error::<T>::NotMetaPool)?;
+		Ok((pool_info, base_pool_info))
+	}
+
+	/// Resolves the underlying indexes `i` and `j` of a metapool, together with the base pool id
+	/// and the index of the base pool token in the metapool.
+	fn get_underlying_indexes(
+		pool_id: StableAssetPoolId,
+		i: PoolTokenIndex,
+		j: PoolTokenIndex,
+	) -> Result<
+		(
+			StableAssetPoolId,
+			PoolTokenIndex,
+			UnderlyingIndex,
+			UnderlyingIndex,
+		),
+		DispatchError,
+	> {
+		ensure!(i != j, bifrost_stable_asset::Error::<T>::ArgumentsError);
+		let (pool_info, base_pool_info) = Self::get_meta_pool_infos(pool_id)?;
+		let lp_index = pool_info
+			.assets
+			.iter()
+			.position(|asset| *asset == base_pool_info.pool_asset)
+			.ok_or(bifrost_stable_asset::Error::<T>::InvalidBasePool)?
+			as PoolTokenIndex;
+		let meta_len = pool_info.assets.len() as PoolTokenIndex - 1;
+		let base_len = base_pool_info.assets.len() as PoolTokenIndex;
+		let to_underlying_index =
+			|index: PoolTokenIndex| -> Result<UnderlyingIndex, DispatchError> {
+				if index < lp_index {
+					Ok(UnderlyingIndex::Meta(index))
+				} else if index < meta_len {
+					Ok(UnderlyingIndex::Meta(index + 1))
+				} else if index < meta_len + base_len {
+					Ok(UnderlyingIndex::Base(index - meta_len))
+				} else {
+					Err(bifrost_stable_asset::Error::<T>::ArgumentsMismatch.into())
+				}
+			};
+		Ok((
+			base_pool_info.pool_id,
+			lp_index,
+			to_underlying_index(i)?,
+			to_underlying_index(j)?,
+		))
+	}
+
 	fn get_pool_id(
 		currency_id_in: &AssetIdOf<T>,
 		currency_id_out: &AssetIdOf<T>,
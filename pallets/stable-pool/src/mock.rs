@@ -283,6 +283,7 @@ impl bifrost_vtoken_minting::Config for Test {
 	type RelayChainToken = RelayCurrencyId;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = OnVtokenRateChanged;
 	type XcmTransfer = XTokens;
 	type MoonbeamChainId = MoonbeamChainId;
 	type BifrostSlpx = SlpxInterface;
@@ -293,6 +294,21 @@ impl bifrost_vtoken_minting::Config for Test {
 	type BlockNumberProvider = System;
 }
 
+pub struct OnVtokenRateChanged;
+impl bifrost_vtoken_minting::OnVtokenRateChanged<CurrencyId, Balance> for OnVtokenRateChanged {
+	fn on_vtoken_rate_changed(
+		vtoken_id: CurrencyId,
+		vtoken_amount: Balance,
+		token_amount: Balance,
+	) -> Weight {
+		StablePool::on_vtoken_rate_changed(vtoken_id, vtoken_amount, token_amount)
+	}
+
+	fn on_vtoken_rate_changed_weight() -> Weight {
+		StablePool::on_vtoken_rate_changed_weight()
+	}
+}
+
 pub struct Slp;
 // Functions to be called by other pallets.
 impl SlpOperator<CurrencyId> for Slp {
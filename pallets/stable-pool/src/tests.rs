@@ -16,11 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 use crate::{mock::*, AssetIdOf, AtLeast64BitUnsignedOf, Error};
-use bifrost_primitives::{StableAssetPalletId, VtokenMintingOperator};
-use bifrost_stable_asset::{PoolCount, Pools, StableAssetPoolInfo};
+use bifrost_primitives::{StableAssetPalletId, VtokenMintingOperator, KSM};
+use bifrost_stable_asset::{PoolCount, Pools, StableAssetPoolInfo, SwapFeeMultipliers};
 use frame_support::{assert_noop, assert_ok, BoundedVec};
 use orml_traits::MultiCurrency;
-use sp_runtime::{traits::AccountIdConversion, Permill};
+use sp_runtime::{
+	traits::AccountIdConversion, DispatchError, FixedPointNumber, FixedU128, Permill,
+};
 
 pub const BALANCE_OFF: u128 = 0;
 
@@ -783,7 +785,8 @@ fn redeem_single() {
 					0,
 					Some(10_000_000_000),
 					Some(10_000_000_000),
-					Some(10_000_000_000)
+					Some(10_000_000_000),
+					None
 				),
 				bifrost_stable_asset::Error::<Test>::ArgumentsError
 			);
@@ -793,6 +796,7 @@ fn redeem_single() {
 				Some(9_999_999_999),
 				Some(9_999_999_999),
 				Some(9_999_999_999),
+				None,
 			));
 			assert_ok!(StablePool::redeem_single(
 				RuntimeOrigin::signed(6).into(),
@@ -809,6 +813,7 @@ fn redeem_single() {
 				Some(9_999_999_999),
 				Some(9_999_999_999),
 				Some(999_999_999),
+				None,
 			));
 			assert_ok!(StablePool::redeem_single(
 				RuntimeOrigin::signed(6).into(),
@@ -1032,6 +1037,7 @@ fn edit_token_rate() {
 				)>>(),
 				vec![]
 			);
+			assert!(bifrost_stable_asset::TokenRatePools::<Test>::get(VBNC).is_empty());
 			assert_ok!(StablePool::edit_token_rate(
 				RuntimeOrigin::root(),
 				0,
@@ -1234,14 +1240,13 @@ fn remove_vtoken_auto_refresh_should_work() {
 				VDOT,
 				Permill::from_percent(20)
 			));
-			assert_ok!(<Test as crate::Config>::VtokenMinting::increase_token_pool(
-				DOT, 20_000_000
-			));
-
 			assert_ok!(StablePool::remove_vtoken_auto_refresh(
 				RuntimeOrigin::root(),
 				VDOT
 			));
+			assert_ok!(<Test as crate::Config>::VtokenMinting::increase_token_pool(
+				DOT, 20_000_000
+			));
 			assert_ok!(StablePool::on_swap(&3u128, 0, 0, 1, 5000000u128, 0));
 			assert_eq!(
 				bifrost_stable_asset::TokenRateCaches::<Test>::iter_prefix(0).collect::<Vec<(
@@ -1266,3 +1271,457 @@ fn remove_vtoken_auto_refresh_should_work() {
 			);
 		});
 }
+
+#[test]
+fn vtoken_rate_change_should_refresh_token_rate() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let (coin0, coin1, _pool_asset, _swap_id) = init();
+
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(coin0, (1, 1)), (coin1, (1, 1))]
+			));
+			assert_eq!(
+				bifrost_stable_asset::TokenRatePools::<Test>::get(coin1).into_inner(),
+				vec![0]
+			);
+			assert_ok!(StablePool::config_vtoken_auto_refresh(
+				RuntimeOrigin::root(),
+				VDOT,
+				Permill::from_percent(10)
+			));
+
+			// The new rate is picked up without any swap.
+			assert_ok!(<Test as crate::Config>::VtokenMinting::increase_token_pool(
+				DOT, 1000
+			));
+			assert_eq!(
+				bifrost_stable_asset::TokenRateCaches::<Test>::get(0, coin1),
+				Some((100000000, 100001000))
+			);
+
+			// Beyond the hardcap the rate is kept and an alert is raised.
+			assert_ok!(<Test as crate::Config>::VtokenMinting::increase_token_pool(
+				DOT, 20_000_000
+			));
+			assert_eq!(
+				bifrost_stable_asset::TokenRateCaches::<Test>::get(0, coin1),
+				Some((100000000, 100001000))
+			);
+			System::assert_has_event(RuntimeEvent::StableAsset(
+				bifrost_stable_asset::Event::TokenRateHardcapExceeded {
+					pool_id: 0,
+					vtoken: coin1,
+					token_rate: (100000000, 120001000),
+				},
+			));
+
+			// A rate that went down is kept without an alert.
+			System::reset_events();
+			assert_ok!(<Test as crate::Config>::VtokenMinting::decrease_token_pool(
+				DOT, 20_001_000
+			));
+			assert_eq!(
+				bifrost_stable_asset::TokenRateCaches::<Test>::get(0, coin1),
+				Some((100000000, 100001000))
+			);
+			assert!(!System::events().iter().any(|record| matches!(
+				record.event,
+				RuntimeEvent::StableAsset(
+					bifrost_stable_asset::Event::TokenRateHardcapExceeded { .. }
+				)
+			)));
+
+			// A rate that went down beyond the hardcap raises an alert as well.
+			assert_ok!(<Test as crate::Config>::VtokenMinting::decrease_token_pool(
+				DOT, 20_000_000
+			));
+			assert_eq!(
+				bifrost_stable_asset::TokenRateCaches::<Test>::get(0, coin1),
+				Some((100000000, 100001000))
+			);
+			System::assert_has_event(RuntimeEvent::StableAsset(
+				bifrost_stable_asset::Event::TokenRateHardcapExceeded {
+					pool_id: 0,
+					vtoken: coin1,
+					token_rate: (100000000, 80000000),
+				},
+			));
+		});
+}
+
+#[test]
+fn quotes_should_match_execution() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let coin0 = DOT;
+			let coin1 = VDOT;
+			let pool_asset = CurrencyId::BLP(0);
+
+			assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(
+				coin0.into(),
+				&6,
+				1_000_000_000_000u128
+			));
+			assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(
+				coin1.into(),
+				&6,
+				1_000_000_000_000u128
+			));
+			assert_ok!(StablePool::create_pool(
+				RuntimeOrigin::root(),
+				vec![coin0.into(), coin1.into()],
+				vec![1u128.into(), 1u128.into()],
+				10000000u128.into(),
+				20000000u128.into(),
+				50000000u128.into(),
+				220u128.into(),
+				5,
+				5,
+				1000000000000u128.into()
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(DOT, (1, 1)), (VDOT, (10, 11))]
+			));
+			assert_ok!(StablePool::add_liquidity(
+				RuntimeOrigin::signed(6).into(),
+				0,
+				vec![100_000_000_000u128, 100_000_000_000u128],
+				0
+			));
+
+			assert_eq!(StablePool::get_current_a(0).ok(), Some(220));
+			assert!(StablePool::get_current_a(3).is_err());
+			let virtual_price = StablePool::get_virtual_price(0).unwrap();
+			assert!(virtual_price >= FixedU128::from_rational(999, 1000));
+			assert!(virtual_price <= FixedU128::from_rational(1001, 1000));
+
+			// Exact-out swap
+			let dy = 1_000_000_000u128;
+			let dx = StablePool::get_swap_input(0, 0, 1, dy).unwrap();
+			let coin0_before = Tokens::free_balance(coin0, &6);
+			assert_ok!(StablePool::swap(RuntimeOrigin::signed(6), 0, 0, 1, dx, dy));
+			assert_eq!(coin0_before - Tokens::free_balance(coin0, &6), dx);
+
+			// Redeem single
+			let amount = StablePool::get_redeem_single_amount(0, 1_000_000_000, 1).unwrap();
+			let coin1_before = Tokens::free_balance(coin1, &6);
+			assert_ok!(StablePool::redeem_single(
+				RuntimeOrigin::signed(6),
+				0,
+				1_000_000_000,
+				1,
+				amount,
+				2
+			));
+			assert_eq!(Tokens::free_balance(coin1, &6) - coin1_before, amount);
+
+			// Redeem proportion
+			let amounts = StablePool::get_redeem_proportion_amount(0, 1_000_000_000).unwrap();
+			let coin0_before = Tokens::free_balance(coin0, &6);
+			let coin1_before = Tokens::free_balance(coin1, &6);
+			assert_ok!(StablePool::redeem_proportion(
+				RuntimeOrigin::signed(6),
+				0,
+				1_000_000_000,
+				amounts.clone()
+			));
+			assert_eq!(Tokens::free_balance(coin0, &6) - coin0_before, amounts[0]);
+			assert_eq!(Tokens::free_balance(coin1, &6) - coin1_before, amounts[1]);
+
+			// Redeem multi
+			let redeem_amount =
+				StablePool::get_redeem_multi_amount(0, vec![500_000_000, 500_000_000]).unwrap();
+			assert_noop!(
+				StablePool::redeem_multi(
+					RuntimeOrigin::signed(6),
+					0,
+					vec![500_000_000, 500_000_000],
+					redeem_amount - 1,
+				),
+				Error::<Test>::RedeemOverMax
+			);
+			let pool_asset_before = Tokens::free_balance(pool_asset, &6);
+			assert_ok!(StablePool::redeem_multi(
+				RuntimeOrigin::signed(6),
+				0,
+				vec![500_000_000, 500_000_000],
+				redeem_amount,
+			));
+			assert_eq!(
+				pool_asset_before - Tokens::free_balance(pool_asset, &6),
+				redeem_amount
+			);
+		});
+}
+
+#[test]
+fn swap_underlying_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let coin0 = DOT;
+			let coin1 = VDOT;
+			let meta_coin = KSM;
+			let base_pool_asset = CurrencyId::BLP(0);
+
+			for currency_id in [coin0, coin1, meta_coin] {
+				assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(
+					currency_id.into(),
+					&6,
+					1_000_000_000_000u128
+				));
+			}
+			assert_ok!(StablePool::create_pool(
+				RuntimeOrigin::root(),
+				vec![coin0.into(), coin1.into()],
+				vec![1u128.into(), 1u128.into()],
+				0u128.into(),
+				0u128.into(),
+				0u128.into(),
+				220u128.into(),
+				5,
+				5,
+				1000000000000u128.into()
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(DOT, (1, 1)), (VDOT, (10, 11))]
+			));
+			assert_ok!(StablePool::add_liquidity(
+				RuntimeOrigin::signed(6).into(),
+				0,
+				vec![100_000_000_000u128, 100_000_000_000u128],
+				0
+			));
+
+			assert_ok!(StablePool::create_pool(
+				RuntimeOrigin::root(),
+				vec![meta_coin.into(), base_pool_asset.into()],
+				vec![1u128.into(), 1u128.into()],
+				0u128.into(),
+				0u128.into(),
+				0u128.into(),
+				220u128.into(),
+				5,
+				5,
+				1000000000000u128.into()
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				1,
+				vec![(KSM, (1, 1))]
+			));
+			assert_noop!(
+				StablePool::swap_underlying(RuntimeOrigin::signed(6), 1, 0, 1, 1_000_000, 0),
+				Error::<Test>::NotMetaPool
+			);
+			assert_noop!(
+				StablePool::set_base_pool(RuntimeOrigin::signed(6), 1, Some(0)),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(StablePool::set_base_pool(RuntimeOrigin::root(), 1, Some(0)));
+			assert_ok!(StablePool::add_liquidity(
+				RuntimeOrigin::signed(6).into(),
+				1,
+				vec![100_000_000_000u128, 100_000_000_000u128],
+				0
+			));
+
+			assert_eq!(
+				StablePool::get_underlying_assets(1),
+				Ok(vec![meta_coin, coin0, coin1])
+			);
+
+			// Metapool asset to base pool asset
+			let dx = 1_000_000_000u128;
+			let dy = StablePool::get_swap_underlying_output(1, 0, 2, dx).unwrap();
+			assert_noop!(
+				StablePool::swap_underlying(RuntimeOrigin::signed(6), 1, 0, 2, dx, dy + 1),
+				Error::<Test>::SwapUnderMin
+			);
+			let meta_coin_before = Tokens::free_balance(meta_coin, &6);
+			let coin1_before = Tokens::free_balance(coin1, &6);
+			let base_pool_asset_before = Tokens::free_balance(base_pool_asset, &6);
+			assert_ok!(StablePool::swap_underlying(
+				RuntimeOrigin::signed(6),
+				1,
+				0,
+				2,
+				dx,
+				dy
+			));
+			assert_eq!(meta_coin_before - Tokens::free_balance(meta_coin, &6), dx);
+			assert_eq!(Tokens::free_balance(coin1, &6) - coin1_before, dy);
+			assert_eq!(
+				Tokens::free_balance(base_pool_asset, &6),
+				base_pool_asset_before
+			);
+
+			// Base pool asset to metapool asset
+			let dy = StablePool::get_swap_underlying_output(1, 1, 0, dx).unwrap();
+			let coin0_before = Tokens::free_balance(coin0, &6);
+			let meta_coin_before = Tokens::free_balance(meta_coin, &6);
+			assert_ok!(StablePool::swap_underlying(
+				RuntimeOrigin::signed(6),
+				1,
+				1,
+				0,
+				dx,
+				dy
+			));
+			assert_eq!(coin0_before - Tokens::free_balance(coin0, &6), dx);
+			assert_eq!(Tokens::free_balance(meta_coin, &6) - meta_coin_before, dy);
+			assert_eq!(
+				Tokens::free_balance(base_pool_asset, &6),
+				base_pool_asset_before
+			);
+
+			assert_noop!(
+				StablePool::swap_underlying(RuntimeOrigin::signed(6), 1, 1, 1, dx, 0),
+				bifrost_stable_asset::Error::<Test>::ArgumentsMismatch
+			);
+			assert_noop!(
+				StablePool::swap_underlying(RuntimeOrigin::signed(6), 1, 0, 3, dx, 0),
+				bifrost_stable_asset::Error::<Test>::ArgumentsMismatch
+			);
+		});
+}
+
+#[test]
+fn dynamic_swap_fee_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let coin0 = DOT;
+			let coin1 = VDOT;
+
+			for currency_id in [coin0, coin1] {
+				assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(
+					currency_id.into(),
+					&6,
+					1_000_000_000_000u128
+				));
+			}
+			assert_ok!(StablePool::create_pool(
+				RuntimeOrigin::root(),
+				vec![coin0.into(), coin1.into()],
+				vec![1u128.into(), 1u128.into()],
+				0u128.into(),
+				20000000u128.into(),
+				0u128.into(),
+				220u128.into(),
+				5,
+				5,
+				1000000000000u128.into()
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(DOT, (1, 1)), (VDOT, (1, 1))]
+			));
+			// VDOT is the scarce side of the pool.
+			assert_ok!(StablePool::add_liquidity(
+				RuntimeOrigin::signed(6).into(),
+				0,
+				vec![100_000_000_000u128, 20_000_000_000u128],
+				0
+			));
+
+			let dx = 1_000_000_000u128;
+			let flat_worsen = StablePool::get_swap_output(0, 0, 1, dx).unwrap();
+			let flat_restore = StablePool::get_swap_output(0, 1, 0, dx).unwrap();
+
+			assert_noop!(
+				StablePool::modify_fees(
+					RuntimeOrigin::root(),
+					0,
+					None,
+					None,
+					None,
+					Some(9_999_999_999)
+				),
+				bifrost_stable_asset::Error::<Test>::ArgumentsError
+			);
+			// The highest swap fee would reach 100%.
+			assert_noop!(
+				StablePool::modify_fees(
+					RuntimeOrigin::root(),
+					0,
+					None,
+					None,
+					None,
+					Some(5_000_000_000_000)
+				),
+				bifrost_stable_asset::Error::<Test>::ArgumentsError
+			);
+			assert_ok!(StablePool::modify_fees(
+				RuntimeOrigin::root(),
+				0,
+				None,
+				None,
+				None,
+				Some(50_000_000_000)
+			));
+			assert_eq!(SwapFeeMultipliers::<Test>::get(0), Some(50_000_000_000));
+			assert_noop!(
+				StablePool::modify_fees(
+					RuntimeOrigin::root(),
+					0,
+					None,
+					Some(2_000_000_000),
+					None,
+					None
+				),
+				bifrost_stable_asset::Error::<Test>::ArgumentsError
+			);
+
+			// Swaps that worsen the balance pay more than the flat fee, swaps that restore it pay
+			// less.
+			let dynamic_worsen = StablePool::get_swap_output(0, 0, 1, dx).unwrap();
+			let dynamic_restore = StablePool::get_swap_output(0, 1, 0, dx).unwrap();
+			assert!(dynamic_worsen < flat_worsen);
+			assert!(dynamic_restore > flat_restore);
+
+			// Execution matches the quotes.
+			let coin1_before = Tokens::free_balance(coin1, &6);
+			assert_ok!(StablePool::swap(
+				RuntimeOrigin::signed(6),
+				0,
+				0,
+				1,
+				dx,
+				dynamic_worsen
+			));
+			assert_eq!(
+				Tokens::free_balance(coin1, &6) - coin1_before,
+				dynamic_worsen
+			);
+
+			let dy = 1_000_000_000u128;
+			let dx = StablePool::get_swap_input(0, 0, 1, dy).unwrap();
+			assert_ok!(StablePool::swap(RuntimeOrigin::signed(6), 0, 0, 1, dx, dy));
+
+			// Back to a flat swap fee.
+			assert_ok!(StablePool::modify_fees(
+				RuntimeOrigin::root(),
+				0,
+				None,
+				None,
+				None,
+				Some(10_000_000_000)
+			));
+			assert_eq!(SwapFeeMultipliers::<Test>::get(0), None);
+		});
+}
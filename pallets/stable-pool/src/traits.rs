@@ -86,6 +86,8 @@ pub trait StablePoolHandler {
 		currency_id_in: &Self::CurrencyId,
 		currency_id_out: &Self::CurrencyId,
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)>;
+
+	fn get_all_pools() -> Vec<(StableAssetPoolId, Vec<Self::CurrencyId>)>;
 }
 
 impl<T: Config> StablePoolHandler for Pallet<T> {
@@ -185,6 +187,12 @@ impl<T: Config> StablePoolHandler for Pallet<T> {
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
 		Self::get_pool_id(currency_id_in, currency_id_out)
 	}
+
+	fn get_all_pools() -> Vec<(StableAssetPoolId, Vec<Self::CurrencyId>)> {
+		Pools::<T>::iter()
+			.map(|(pool_id, pool_info)| (pool_id, pool_info.assets))
+			.collect()
+	}
 }
 
 impl StablePoolHandler for () {
@@ -272,4 +280,8 @@ impl StablePoolHandler for () {
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
 		None
 	}
+
+	fn get_all_pools() -> Vec<(StableAssetPoolId, Vec<Self::CurrencyId>)> {
+		Vec::new()
+	}
 }
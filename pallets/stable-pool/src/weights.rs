@@ -65,6 +65,8 @@ pub trait WeightInfo {
 	fn modify_a() -> Weight;
 	fn modify_fees() -> Weight;
 	fn modify_recipients() -> Weight;
+	fn set_base_pool() -> Weight;
+	fn swap_underlying() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -259,4 +261,34 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `StableAsset::Pools` (r:2 w:0)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::BasePools` (r:2 w:1)
+	/// Proof: `StableAsset::BasePools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_base_pool() -> Weight {
+		Weight::from_parts(15_874_000, 6832)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `StableAsset::Pools` (r:2 w:2)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::BasePools` (r:1 w:0)
+	/// Proof: `StableAsset::BasePools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::TokenRateCaches` (r:3 w:0)
+	/// Proof: `StableAsset::TokenRateCaches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRegistry::CurrencyMetadatas` (r:2 w:0)
+	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::TotalIssuance` (r:2 w:2)
+	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::TokenRateHardcap` (r:2 w:0)
+	/// Proof: `StableAsset::TokenRateHardcap` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_underlying() -> Weight {
+		Weight::from_parts(245_126_000, 11196)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
 }
@@ -0,0 +1,124 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use bifrost_primitives::{Balance, CurrencyId, SwapHop};
+pub use bifrost_swap_router_rpc_runtime_api::{self as runtime_api, SwapRouterRuntimeApi};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{ErrorCode, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+#[rpc(client, server)]
+pub trait SwapRouterRpcApi<BlockHash> {
+	/// rpc method for finding the best swap route and its output amount
+	#[method(name = "swap_router_findBestRoute")]
+	fn find_best_route(
+		&self,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<(Vec<SwapHop>, NumberOrHex)>>;
+
+	#[method(name = "swap_router_getAmountOut")]
+	fn get_amount_out(
+		&self,
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: Vec<SwapHop>,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapRouterRpc<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> SwapRouterRpc<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: PhantomData,
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block> SwapRouterRpcApiServer<<Block as BlockT>::Hash> for SwapRouterRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
+{
+	fn find_best_route(
+		&self,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<(Vec<SwapHop>, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<Option<(Vec<SwapHop>, Balance)>, _> =
+			api.find_best_route(at, currency_in, currency_out, amount_in);
+
+		match rs {
+			Ok(best_route) => {
+				Ok(best_route
+					.map(|(route, amount_out)| (route, NumberOrHex::Hex(amount_out.into()))))
+			}
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to find the best swap route.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_amount_out(
+		&self,
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: Vec<SwapHop>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<Balance, _> = api.get_amount_out(at, currency_in, amount_in, route);
+
+		match rs {
+			Ok(amount) => Ok(NumberOrHex::Hex(amount.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get swap route amount out.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+}
@@ -0,0 +1,492 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Swaps along a route mixing stable pool hops and zenlink pair hops, atomically and with a
+//! single minimum output.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bifrost_primitives::{Balance, CurrencyId, SwapHop, SwapPool};
+use bifrost_stable_pool::traits::StablePoolHandler;
+use cumulus_primitives_core::ParaId;
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::Zero;
+use sp_std::{vec, vec::Vec};
+use zenlink_protocol::{AssetId, ExportZenlink};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Currency operations handler
+		type MultiCurrency: MultiCurrency<
+			AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+			Balance = Balance,
+		>;
+		/// Stable pools the route can swap in
+		type StablePoolHandler: StablePoolHandler<
+			Balance = Balance,
+			AccountId = AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+		>;
+		/// Zenlink DEX the route can swap in
+		type DexOperator: ExportZenlink<AccountIdOf<Self>, AssetId>;
+		/// The only origin that can enable zenlink pairs for routes
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		#[pallet::constant]
+		type ParachainId: Get<ParaId>;
+		/// The maximum number of hops of a route
+		#[pallet::constant]
+		type MaxHops: Get<u32>;
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A route has been swapped.
+		Swapped {
+			/// The account which swapped.
+			who: AccountIdOf<T>,
+			/// The currency paid into the first hop.
+			currency_in: CurrencyId,
+			/// The amount of `currency_in` paid.
+			amount_in: Balance,
+			/// The currency received from the last hop.
+			currency_out: CurrencyId,
+			/// The amount of `currency_out` received.
+			amount_out: Balance,
+			/// The hops swapped through, in order.
+			route: Vec<SwapHop>,
+		},
+		/// A zenlink pair has been enabled or disabled for routes.
+		ZenlinkPairSet {
+			/// One currency of the pair.
+			currency_a: CurrencyId,
+			/// The other currency of the pair.
+			currency_b: CurrencyId,
+			/// Whether routes can swap through the pair, in both directions.
+			enabled: bool,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The route has no hops.
+		EmptyRoute,
+		/// A hop swaps a currency into itself.
+		SameCurrency,
+		/// A stable pool hop's currencies are not in the pool.
+		CurrencyNotInPool,
+		/// The currency has no zenlink asset id.
+		ConversionError,
+		/// The route output is below `min_amount_out`.
+		AmountOutBelowMinimum,
+		/// A zenlink hop swaps through a pair which is not enabled.
+		ZenlinkPairNotEnabled,
+	}
+
+	/// Zenlink pairs routes can swap through, in both directions. A pair is stored once, under
+	/// the order of currencies it was last enabled with.
+	///
+	/// ZenlinkPairs: map (CurrencyId, CurrencyId) => ()
+	#[pallet::storage]
+	pub type ZenlinkPairs<T: Config> =
+		StorageMap<_, Twox64Concat, (CurrencyId, CurrencyId), (), OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Swaps `amount_in` of `currency_in` along `route`, failing the whole route if it
+		/// yields less than `min_amount_out`.
+		///
+		/// Parameters:
+		/// - `currency_in`: The currency paid into the first hop.
+		/// - `amount_in`: The amount of `currency_in` paid.
+		/// - `route`: The hops to swap through, each zenlink hop must be an enabled pair.
+		/// - `min_amount_out`: The minimum amount received from the last hop.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::swap(route.len() as u32))]
+		pub fn swap(
+			origin: OriginFor<T>,
+			currency_in: CurrencyId,
+			amount_in: Balance,
+			route: BoundedVec<SwapHop, T::MaxHops>,
+			min_amount_out: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::swap_by_route(&who, currency_in, amount_in, &route, min_amount_out)?;
+			Ok(())
+		}
+
+		/// Enables or disables a zenlink pair for routes, in both directions.
+		///
+		/// Parameters:
+		/// - `currency_a`: One currency of the pair.
+		/// - `currency_b`: The other currency of the pair.
+		/// - `enabled`: Whether routes can swap through the pair.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_zenlink_pair())]
+		pub fn set_zenlink_pair(
+			origin: OriginFor<T>,
+			currency_a: CurrencyId,
+			currency_b: CurrencyId,
+			enabled: bool,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(currency_a != currency_b, Error::<T>::SameCurrency);
+			Self::get_currency_asset_id(currency_a)?;
+			Self::get_currency_asset_id(currency_b)?;
+
+			ZenlinkPairs::<T>::remove((currency_b, currency_a));
+			if enabled {
+				ZenlinkPairs::<T>::insert((currency_a, currency_b), ());
+			} else {
+				ZenlinkPairs::<T>::remove((currency_a, currency_b));
+			}
+
+			Self::deposit_event(Event::ZenlinkPairSet {
+				currency_a,
+				currency_b,
+				enabled,
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Swaps along `route` and returns the amount of the last hop's currency received.
+	#[transactional]
+	pub fn swap_by_route(
+		who: &AccountIdOf<T>,
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: &[SwapHop],
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		let last_hop = route.last().ok_or(Error::<T>::EmptyRoute)?;
+
+		let mut currency = currency_in;
+		let mut amount = amount_in;
+		for hop in route {
+			let balance_before = T::MultiCurrency::free_balance(hop.currency_out, who);
+			match hop.pool {
+				SwapPool::StablePool(pool_id) => {
+					let (index_in, index_out) =
+						Self::get_pool_token_indexes(pool_id, currency, hop.currency_out)?;
+					T::StablePoolHandler::swap(who, pool_id, index_in, index_out, amount, 0)?;
+				}
+				SwapPool::Zenlink => {
+					let path = Self::get_zenlink_path(currency, hop.currency_out)?;
+					T::DexOperator::inner_swap_exact_assets_for_assets(who, amount, 0, &path, who)?;
+				}
+			}
+			amount = T::MultiCurrency::free_balance(hop.currency_out, who)
+				.saturating_sub(balance_before);
+			currency = hop.currency_out;
+		}
+		ensure!(amount >= min_amount_out, Error::<T>::AmountOutBelowMinimum);
+
+		Self::deposit_event(Event::Swapped {
+			who: who.clone(),
+			currency_in,
+			amount_in,
+			currency_out: last_hop.currency_out,
+			amount_out: amount,
+			route: route.to_vec(),
+		});
+		Ok(amount)
+	}
+
+	/// Quotes the amount received for swapping `amount_in` of `currency_in` along `route`.
+	pub fn get_amount_out(
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: &[SwapHop],
+	) -> Result<Balance, DispatchError> {
+		ensure!(!route.is_empty(), Error::<T>::EmptyRoute);
+
+		let mut currency = currency_in;
+		let mut amount = amount_in;
+		for hop in route {
+			amount = Self::get_hop_amount_out(currency, amount, hop)?;
+			currency = hop.currency_out;
+		}
+		Ok(amount)
+	}
+
+	/// Finds the route of at most `MaxHops` hops giving the most `currency_out` for
+	/// `amount_in` of `currency_in`, over all stable pools and the enabled zenlink pairs.
+	pub fn find_best_route(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+	) -> Option<(Vec<SwapHop>, Balance)> {
+		if currency_in == currency_out || amount_in.is_zero() {
+			return None;
+		}
+
+		let mut edges: Vec<(CurrencyId, SwapHop)> = Vec::new();
+		for (pool_id, assets) in T::StablePoolHandler::get_all_pools() {
+			for currency_from in assets.iter() {
+				for currency_to in assets.iter().filter(|currency| *currency != currency_from) {
+					edges.push((
+						*currency_from,
+						SwapHop {
+							pool: SwapPool::StablePool(pool_id),
+							currency_out: *currency_to,
+						},
+					));
+				}
+			}
+		}
+		for ((currency_a, currency_b), _) in ZenlinkPairs::<T>::iter() {
+			edges.push((
+				currency_a,
+				SwapHop {
+					pool: SwapPool::Zenlink,
+					currency_out: currency_b,
+				},
+			));
+			edges.push((
+				currency_b,
+				SwapHop {
+					pool: SwapPool::Zenlink,
+					currency_out: currency_a,
+				},
+			));
+		}
+
+		let mut best = None;
+		Self::search_routes(
+			&edges,
+			currency_in,
+			currency_out,
+			amount_in,
+			&mut vec![currency_in],
+			&mut Vec::new(),
+			&mut best,
+		);
+		best
+	}
+
+	fn search_routes(
+		edges: &[(CurrencyId, SwapHop)],
+		currency: CurrencyId,
+		currency_out: CurrencyId,
+		amount: Balance,
+		visited: &mut Vec<CurrencyId>,
+		route: &mut Vec<SwapHop>,
+		best: &mut Option<(Vec<SwapHop>, Balance)>,
+	) {
+		if route.len() >= T::MaxHops::get() as usize {
+			return;
+		}
+		for (_, hop) in edges.iter().filter(|(currency_from, hop)| {
+			*currency_from == currency && !visited.contains(&hop.currency_out)
+		}) {
+			let Ok(amount_out) = Self::get_hop_amount_out(currency, amount, hop) else {
+				continue;
+			};
+			if amount_out.is_zero() {
+				continue;
+			}
+
+			route.push(*hop);
+			if hop.currency_out == currency_out {
+				if best
+					.as_ref()
+					.map_or(true, |(_, best_amount)| amount_out > *best_amount)
+				{
+					*best = Some((route.clone(), amount_out));
+				}
+			} else {
+				visited.push(hop.currency_out);
+				Self::search_routes(
+					edges,
+					hop.currency_out,
+					currency_out,
+					amount_out,
+					visited,
+					route,
+					best,
+				);
+				visited.pop();
+			}
+			route.pop();
+		}
+	}
+
+	fn get_hop_amount_out(
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		hop: &SwapHop,
+	) -> Result<Balance, DispatchError> {
+		match hop.pool {
+			SwapPool::StablePool(pool_id) => {
+				let (index_in, index_out) =
+					Self::get_pool_token_indexes(pool_id, currency_in, hop.currency_out)?;
+				T::StablePoolHandler::get_swap_output(pool_id, index_in, index_out, amount_in)
+			}
+			SwapPool::Zenlink => {
+				let path = Self::get_zenlink_path(currency_in, hop.currency_out)?;
+				T::DexOperator::get_amount_out_by_path(amount_in, &path)?
+					.last()
+					.copied()
+					.ok_or(Error::<T>::ConversionError.into())
+			}
+		}
+	}
+
+	fn get_pool_token_indexes(
+		pool_id: u32,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+	) -> Result<(u32, u32), Error<T>> {
+		ensure!(currency_in != currency_out, Error::<T>::SameCurrency);
+		let index_in = T::StablePoolHandler::get_pool_token_index(pool_id, currency_in)
+			.ok_or(Error::<T>::CurrencyNotInPool)?;
+		let index_out = T::StablePoolHandler::get_pool_token_index(pool_id, currency_out)
+			.ok_or(Error::<T>::CurrencyNotInPool)?;
+		Ok((index_in, index_out))
+	}
+
+	fn get_zenlink_path(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+	) -> Result<Vec<AssetId>, Error<T>> {
+		ensure!(currency_in != currency_out, Error::<T>::SameCurrency);
+		ensure!(
+			ZenlinkPairs::<T>::contains_key((currency_in, currency_out))
+				|| ZenlinkPairs::<T>::contains_key((currency_out, currency_in)),
+			Error::<T>::ZenlinkPairNotEnabled
+		);
+		Ok(vec![
+			Self::get_currency_asset_id(currency_in)?,
+			Self::get_currency_asset_id(currency_out)?,
+		])
+	}
+
+	fn get_currency_asset_id(currency_id: CurrencyId) -> Result<AssetId, Error<T>> {
+		AssetId::try_convert_from(currency_id, T::ParachainId::get().into())
+			.map_err(|_| Error::<T>::ConversionError)
+	}
+}
+
+/// Swap routing for other pallets.
+pub trait SwapRouter<AccountId> {
+	/// Swaps along `route`, returning the amount received.
+	fn swap(
+		who: &AccountId,
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: &[SwapHop],
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	fn get_amount_out(
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: &[SwapHop],
+	) -> Result<Balance, DispatchError>;
+
+	fn find_best_route(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+	) -> Option<(Vec<SwapHop>, Balance)>;
+}
+
+impl<T: Config> SwapRouter<AccountIdOf<T>> for Pallet<T> {
+	fn swap(
+		who: &AccountIdOf<T>,
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: &[SwapHop],
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Self::swap_by_route(who, currency_in, amount_in, route, min_amount_out)
+	}
+
+	fn get_amount_out(
+		currency_in: CurrencyId,
+		amount_in: Balance,
+		route: &[SwapHop],
+	) -> Result<Balance, DispatchError> {
+		Self::get_amount_out(currency_in, amount_in, route)
+	}
+
+	fn find_best_route(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+	) -> Option<(Vec<SwapHop>, Balance)> {
+		Self::find_best_route(currency_in, currency_out, amount_in)
+	}
+}
+
+impl<AccountId> SwapRouter<AccountId> for () {
+	fn swap(
+		_who: &AccountId,
+		_currency_in: CurrencyId,
+		_amount_in: Balance,
+		_route: &[SwapHop],
+		_min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("Swap router is not supported"))
+	}
+
+	fn get_amount_out(
+		_currency_in: CurrencyId,
+		_amount_in: Balance,
+		_route: &[SwapHop],
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("Swap router is not supported"))
+	}
+
+	fn find_best_route(
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_amount_in: Balance,
+	) -> Option<(Vec<SwapHop>, Balance)> {
+		None
+	}
+}
@@ -0,0 +1,291 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{mock::*, Error, Event, SwapHop, SwapPool, ZenlinkPairs};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use orml_traits::MultiCurrency;
+use sp_runtime::DispatchError::BadOrigin;
+use zenlink_protocol::AssetId;
+
+fn asset_id(currency_id: CurrencyId) -> AssetId {
+	AssetId::try_convert_from(currency_id, 2001).unwrap()
+}
+
+fn create_zenlink_pair(currency_a: CurrencyId, currency_b: CurrencyId, amount: Balance) {
+	assert_ok!(ZenlinkProtocol::create_pair(
+		RuntimeOrigin::root(),
+		asset_id(currency_a),
+		asset_id(currency_b),
+		1
+	));
+	assert_ok!(ZenlinkProtocol::add_liquidity(
+		RuntimeOrigin::signed(1),
+		asset_id(currency_a),
+		asset_id(currency_b),
+		amount,
+		amount,
+		1,
+		1,
+		100
+	));
+}
+
+fn basic_setup() {
+	assert_ok!(StablePool::create_pool(
+		RuntimeOrigin::root(),
+		vec![DOT, VDOT],
+		vec![1u128, 1u128],
+		0u128,
+		0u128,
+		0u128,
+		220u128,
+		5,
+		5,
+		unit(1)
+	));
+	assert_ok!(StablePool::edit_token_rate(
+		RuntimeOrigin::root(),
+		0,
+		vec![(DOT, (1, 1)), (VDOT, (1, 1))]
+	));
+	assert_ok!(StablePool::add_liquidity(
+		RuntimeOrigin::signed(1),
+		0,
+		vec![unit(100), unit(100)],
+		0
+	));
+
+	create_zenlink_pair(BNC, DOT, unit(100));
+	create_zenlink_pair(BNC, VDOT, unit(10));
+}
+
+fn vdot_to_bnc_via_dot() -> Vec<SwapHop> {
+	vec![
+		SwapHop {
+			pool: SwapPool::StablePool(0),
+			currency_out: DOT,
+		},
+		SwapHop {
+			pool: SwapPool::Zenlink,
+			currency_out: BNC,
+		},
+	]
+}
+
+#[test]
+fn swap_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			basic_setup();
+			assert_ok!(SwapRouter::set_zenlink_pair(
+				RuntimeOrigin::root(),
+				BNC,
+				DOT,
+				true
+			));
+
+			let route = vdot_to_bnc_via_dot();
+			let amount_out = SwapRouter::get_amount_out(VDOT, unit(1), &route).unwrap();
+			let vdot_before = Currencies::free_balance(VDOT, &1);
+			let dot_before = Currencies::free_balance(DOT, &1);
+			let bnc_before = Currencies::free_balance(BNC, &1);
+
+			assert_ok!(SwapRouter::swap(
+				RuntimeOrigin::signed(1),
+				VDOT,
+				unit(1),
+				BoundedVec::try_from(route.clone()).unwrap(),
+				amount_out
+			));
+
+			assert_eq!(Currencies::free_balance(VDOT, &1), vdot_before - unit(1));
+			assert_eq!(Currencies::free_balance(DOT, &1), dot_before);
+			assert_eq!(Currencies::free_balance(BNC, &1), bnc_before + amount_out);
+			System::assert_last_event(RuntimeEvent::SwapRouter(Event::Swapped {
+				who: 1,
+				currency_in: VDOT,
+				amount_in: unit(1),
+				currency_out: BNC,
+				amount_out,
+				route,
+			}));
+		});
+}
+
+#[test]
+fn swap_below_min_amount_out_should_fail() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			basic_setup();
+
+			// Routes only swap through the enabled zenlink pairs
+			let route = vdot_to_bnc_via_dot();
+			assert_noop!(
+				SwapRouter::swap(
+					RuntimeOrigin::signed(1),
+					VDOT,
+					unit(1),
+					BoundedVec::try_from(route.clone()).unwrap(),
+					0
+				),
+				Error::<Test>::ZenlinkPairNotEnabled
+			);
+			assert_ok!(SwapRouter::set_zenlink_pair(
+				RuntimeOrigin::root(),
+				DOT,
+				BNC,
+				true
+			));
+			let amount_out = SwapRouter::get_amount_out(VDOT, unit(1), &route).unwrap();
+
+			assert_noop!(
+				SwapRouter::swap(
+					RuntimeOrigin::signed(1),
+					VDOT,
+					unit(1),
+					BoundedVec::try_from(route).unwrap(),
+					amount_out + 1
+				),
+				Error::<Test>::AmountOutBelowMinimum
+			);
+			assert_noop!(
+				SwapRouter::swap(
+					RuntimeOrigin::signed(1),
+					VDOT,
+					unit(1),
+					BoundedVec::default(),
+					0
+				),
+				Error::<Test>::EmptyRoute
+			);
+			assert_noop!(
+				SwapRouter::swap(
+					RuntimeOrigin::signed(1),
+					VDOT,
+					unit(1),
+					BoundedVec::try_from(vec![SwapHop {
+						pool: SwapPool::StablePool(0),
+						currency_out: BNC
+					}])
+					.unwrap(),
+					0
+				),
+				Error::<Test>::CurrencyNotInPool
+			);
+		});
+}
+
+#[test]
+fn set_zenlink_pair_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				SwapRouter::set_zenlink_pair(RuntimeOrigin::signed(1), BNC, DOT, true),
+				BadOrigin
+			);
+			assert_noop!(
+				SwapRouter::set_zenlink_pair(RuntimeOrigin::root(), BNC, BNC, true),
+				Error::<Test>::SameCurrency
+			);
+
+			assert_ok!(SwapRouter::set_zenlink_pair(
+				RuntimeOrigin::root(),
+				BNC,
+				DOT,
+				true
+			));
+			assert!(ZenlinkPairs::<Test>::contains_key((BNC, DOT)));
+
+			assert_ok!(SwapRouter::set_zenlink_pair(
+				RuntimeOrigin::root(),
+				DOT,
+				BNC,
+				false
+			));
+			assert!(!ZenlinkPairs::<Test>::contains_key((BNC, DOT)));
+			assert!(!ZenlinkPairs::<Test>::contains_key((DOT, BNC)));
+		});
+}
+
+#[test]
+fn find_best_route_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			basic_setup();
+
+			// Only the stable pool is known until zenlink pairs are registered.
+			assert_eq!(SwapRouter::find_best_route(VDOT, BNC, unit(1)), None);
+			assert_eq!(
+				SwapRouter::find_best_route(VDOT, DOT, unit(1)),
+				Some((
+					vec![SwapHop {
+						pool: SwapPool::StablePool(0),
+						currency_out: DOT
+					}],
+					SwapRouter::get_amount_out(
+						VDOT,
+						unit(1),
+						&[SwapHop {
+							pool: SwapPool::StablePool(0),
+							currency_out: DOT
+						}]
+					)
+					.unwrap()
+				))
+			);
+
+			assert_ok!(SwapRouter::set_zenlink_pair(
+				RuntimeOrigin::root(),
+				BNC,
+				DOT,
+				true
+			));
+			assert_ok!(SwapRouter::set_zenlink_pair(
+				RuntimeOrigin::root(),
+				BNC,
+				VDOT,
+				true
+			));
+
+			// The direct BNC-VDOT pair is shallower than going through the stable pool and BNC-DOT.
+			let direct = vec![SwapHop {
+				pool: SwapPool::Zenlink,
+				currency_out: BNC,
+			}];
+			let direct_amount_out = SwapRouter::get_amount_out(VDOT, unit(1), &direct).unwrap();
+			let route = vdot_to_bnc_via_dot();
+			let amount_out = SwapRouter::get_amount_out(VDOT, unit(1), &route).unwrap();
+			assert!(amount_out > direct_amount_out);
+			assert_eq!(
+				SwapRouter::find_best_route(VDOT, BNC, unit(1)),
+				Some((route, amount_out))
+			);
+
+			assert_eq!(SwapRouter::find_best_route(VDOT, VDOT, unit(1)), None);
+			assert_eq!(SwapRouter::find_best_route(VDOT, BNC, 0), None);
+		});
+}
@@ -0,0 +1,71 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for bifrost_swap_router
+//!
+//! These weights are placeholders, they are to be replaced by the output of the
+//! `bifrost_swap_router` benchmarks run on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for bifrost_swap_router.
+pub trait WeightInfo {
+	fn swap(n: u32, ) -> Weight;
+	fn set_zenlink_pair() -> Weight;
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `StableAsset::Pools` (r:1 w:1)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::TokenRateCaches` (r:2 w:0)
+	/// Proof: `StableAsset::TokenRateCaches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SwapRouter::ZenlinkPairs` (r:2 w:0)
+	/// Proof: `SwapRouter::ZenlinkPairs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:1 w:1)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 4]`.
+	fn swap(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 11350)
+			.saturating_add(Weight::from_parts(112_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5186).saturating_mul(n.into()))
+	}
+	/// Storage: `SwapRouter::ZenlinkPairs` (r:0 w:2)
+	/// Proof: `SwapRouter::ZenlinkPairs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_zenlink_pair() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -90,3 +90,31 @@ pub struct MarketStatus<Balance> {
 	pub total_reserves: Balance,
 	pub borrow_index: FixedU128,
 }
+
+/// AccountPosition contains the positions of an account across the markets of LendMarket
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AccountPosition<CurrencyId, Balance> {
+	/// Liquidation threshold value of the collaterals over the borrowed value, `None` without
+	/// borrows
+	pub health_factor: Option<FixedU128>,
+	/// Reward accrued but not yet claimed
+	pub reward_accrued: Balance,
+	pub markets: Vec<MarketPosition<CurrencyId, Balance>>,
+}
+
+/// MarketPosition contains the position of an account in a single market
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct MarketPosition<CurrencyId, Balance> {
+	pub asset_id: CurrencyId,
+	/// Underlying amount supplied, interest included
+	pub supplied: Balance,
+	/// Amount borrowed, interest included
+	pub borrowed: Balance,
+	pub is_collateral: bool,
+	/// Total interest earned by the supply
+	pub earned: Balance,
+	pub max_borrowable: Balance,
+	pub max_withdrawable: Balance,
+}
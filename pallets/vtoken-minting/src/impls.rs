@@ -18,10 +18,10 @@
 
 use crate::{
 	AccountIdOf, BalanceOf, Config, CurrencyIdOf, Error, Event, Fees, HookIterationLimit,
-	MinTimeUnit, MinimumMint, MinimumRedeem, MintWithLockBlocks, OnRedeemSuccess, OngoingTimeUnit,
-	Pallet, RedeemTo, SupportedEth, TimeUnitUnlockLedger, TokenPool, TokenUnlockLedger,
-	TokenUnlockNextId, UnlockDuration, UnlockId, UnlockingTotal, UserUnlockLedger,
-	VtokenIncentiveCoef, VtokenLockLedger, WeightInfo,
+	MinTimeUnit, MinimumMint, MinimumRedeem, MintWithLockBlocks, OnRedeemSuccess,
+	OnVtokenRateChanged, OngoingTimeUnit, Pallet, RedeemTo, SupportedEth, TimeUnitUnlockLedger,
+	TokenPool, TokenUnlockLedger, TokenUnlockNextId, UnlockDuration, UnlockId, UnlockingTotal,
+	UserUnlockLedger, VtokenIncentiveCoef, VtokenLockLedger, WeightInfo,
 };
 use bb_bnc::traits::BbBNCInterface;
 use bifrost_primitives::{
@@ -62,11 +62,13 @@ impl<T: Config> Pallet<T> {
 	/// - `currency_id`: The currency id.
 	/// - `currency_amount`: The currency amount.
 	/// - `operation`: The operation type. Set, Add, Sub.
+	/// Returns:
+	/// - `Result`: The weight of notifying the new exchange rate of the vtoken.
 	pub fn update_token_pool(
 		currency_id: &CurrencyId,
 		currency_amount: &BalanceOf<T>,
 		operation: Operation,
-	) -> DispatchResult {
+	) -> Result<Weight, DispatchError> {
 		TokenPool::<T>::mutate(currency_id, |token_pool_amount| -> DispatchResult {
 			match operation {
 				Operation::Set => *token_pool_amount = *currency_amount,
@@ -82,7 +84,52 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 			Ok(())
-		})
+		})?;
+		Ok(Self::notify_vtoken_rate_changed(currency_id))
+	}
+
+	/// Charges the weight of notifying a new exchange rate to the current block, for the
+	/// `VtokenMintingOperator` calls whose callers cannot account for it.
+	fn register_rate_changed_weight(weight: Weight) {
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			weight,
+			DispatchClass::Mandatory,
+		);
+	}
+
+	/// Notify the runtime of the current exchange rate of the vtoken of `currency_id`.
+	/// Parameters:
+	/// - `currency_id`: The currency id.
+	/// Returns:
+	/// - `Weight`: The weight of the notification.
+	pub fn notify_vtoken_rate_changed(currency_id: &CurrencyId) -> Weight {
+		let v_currency_id = if SupportedEth::<T>::get().contains(currency_id) {
+			V_WETH
+		} else {
+			match currency_id.to_vtoken() {
+				Ok(v_currency_id) => v_currency_id,
+				Err(_) => return T::DbWeight::get().reads(1),
+			}
+		};
+		let v_currency_total_issuance = T::MultiCurrency::total_issuance(v_currency_id);
+		if let Ok(currency_amount) = <Self as VtokenMintingInterface<
+			AccountIdOf<T>,
+			CurrencyIdOf<T>,
+			BalanceOf<T>,
+		>>::get_currency_amount_by_v_currency_amount(
+			*currency_id,
+			v_currency_id,
+			v_currency_total_issuance,
+		) {
+			T::OnVtokenRateChanged::on_vtoken_rate_changed(
+				v_currency_id,
+				v_currency_total_issuance,
+				currency_amount,
+			)
+			.saturating_add(T::DbWeight::get().reads(3))
+		} else {
+			T::DbWeight::get().reads(3)
+		}
 	}
 
 	/// Update the unlocking total amount.
@@ -711,7 +758,8 @@ impl<T: Config> Pallet<T> {
 			.ok_or(Error::<T>::UnlockDurationNotFound)?;
 
 		// Decrease the token pool amount
-		Self::update_token_pool(&currency_id, &currency_amount, Operation::Sub)?;
+		let rate_changed_weight =
+			Self::update_token_pool(&currency_id, &currency_amount, Operation::Sub)?;
 
 		TokenUnlockNextId::<T>::mutate(&currency_id, |next_id| -> DispatchResultWithPostInfo {
 			Self::update_unlock_ledger(
@@ -746,7 +794,7 @@ impl<T: Config> Pallet<T> {
 				v_currency_amount,
 				redeem_fee,
 			);
-			Ok(Some(T::WeightInfo::redeem() + extra_weight).into())
+			Ok(Some(T::WeightInfo::redeem() + rate_changed_weight + extra_weight).into())
 		})
 	}
 
@@ -845,7 +893,7 @@ impl<T: Config> Pallet<T> {
 
 		// get the veBNC balance of the minter
 		let minter_vebnc_balance =
-			T::BbBNC::balance_of(minter, None).map_err(|_| Error::<T>::VeBNCCheckingError)?;
+			T::BbBNC::own_balance_of(minter, None).map_err(|_| Error::<T>::VeBNCCheckingError)?;
 		ensure!(
 			minter_vebnc_balance > BalanceOf::<T>::zero(),
 			Error::<T>::NotEnoughBalance
@@ -911,14 +959,18 @@ impl<T: Config> VtokenMintingOperator<CurrencyId, BalanceOf<T>, AccountIdOf<T>,
 		currency_id: CurrencyId,
 		currency_amount: BalanceOf<T>,
 	) -> DispatchResult {
-		Self::update_token_pool(&currency_id, &currency_amount, Operation::Add)
+		let weight = Self::update_token_pool(&currency_id, &currency_amount, Operation::Add)?;
+		Self::register_rate_changed_weight(weight);
+		Ok(())
 	}
 
 	fn decrease_token_pool(
 		currency_id: CurrencyId,
 		currency_amount: BalanceOf<T>,
 	) -> DispatchResult {
-		Self::update_token_pool(&currency_id, &currency_amount, Operation::Sub)
+		let weight = Self::update_token_pool(&currency_id, &currency_amount, Operation::Sub)?;
+		Self::register_rate_changed_weight(weight);
+		Ok(())
 	}
 
 	fn update_ongoing_time_unit(currency_id: CurrencyId, time_unit: TimeUnit) -> DispatchResult {
@@ -926,6 +978,7 @@ impl<T: Config> VtokenMintingOperator<CurrencyId, BalanceOf<T>, AccountIdOf<T>,
 			*time_unit_old = Some(time_unit);
 			Ok(())
 		})?;
+		Self::register_rate_changed_weight(Self::notify_vtoken_rate_changed(&currency_id));
 
 		Ok(())
 	}
@@ -1074,6 +1127,11 @@ impl<T: Config> VtokenMintingInterface<AccountIdOf<T>, CurrencyIdOf<T>, BalanceO
 		MinimumRedeem::<T>::get(v_currency_id)
 	}
 
+	fn get_mint_fee(currency_amount: BalanceOf<T>) -> BalanceOf<T> {
+		let (mint_rate, _) = Fees::<T>::get();
+		mint_rate.mul_floor(currency_amount)
+	}
+
 	fn get_token_pool(currency_id: CurrencyId) -> BalanceOf<T> {
 		if SupportedEth::<T>::get().contains(&currency_id) {
 			let mut token_pool_amount = BalanceOf::<T>::zero();
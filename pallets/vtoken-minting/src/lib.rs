@@ -90,6 +90,10 @@ pub mod pallet {
 			CurrencyIdOf<Self>,
 			BalanceOf<Self>,
 		>;
+		/// Handler to notify the runtime when the exchange rate of a vtoken may have changed,
+		/// i.e. its token pool or ongoing time unit is updated.
+		/// If you don't need it, you can specify the type `()`.
+		type OnVtokenRateChanged: OnVtokenRateChanged<CurrencyIdOf<Self>, BalanceOf<Self>>;
 		/// Xtokens xcm transfer interface
 		type XcmTransfer: XcmTransfer<AccountIdOf<Self>, BalanceOf<Self>, CurrencyIdOf<Self>>;
 		/// Slpx operator
@@ -522,7 +526,10 @@ pub mod pallet {
 		/// - `remark`: The remark of minting.
 		/// - `channel_id`: The channel id of minting.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::mint())]
+		#[pallet::weight(
+			T::WeightInfo::mint()
+				.saturating_add(T::OnVtokenRateChanged::on_vtoken_rate_changed_weight())
+		)]
 		pub fn mint(
 			origin: OriginFor<T>,
 			currency_id: CurrencyIdOf<T>,
@@ -542,7 +549,10 @@ pub mod pallet {
 		/// - `v_currency_id`: The v_currency to redeem.
 		/// - `v_currency_amount`: The amount of v_currency to redeem.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::redeem())]
+		#[pallet::weight(
+			T::WeightInfo::redeem()
+				.saturating_add(T::OnVtokenRateChanged::on_vtoken_rate_changed_weight())
+		)]
 		pub fn redeem(
 			origin: OriginFor<T>,
 			currency_id: Option<CurrencyIdOf<T>>,
@@ -566,7 +576,10 @@ pub mod pallet {
 		/// - `currency_amount`: The amount of currency to rebond. The amount should be less than or
 		///   equal to the redeem amount.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::rebond())]
+		#[pallet::weight(
+			T::WeightInfo::rebond()
+				.saturating_add(T::OnVtokenRateChanged::on_vtoken_rate_changed_weight())
+		)]
 		pub fn rebond(
 			origin: OriginFor<T>,
 			currency_id: CurrencyIdOf<T>,
@@ -646,7 +659,10 @@ pub mod pallet {
 		/// - `currency_id`: The currency to rebond.
 		/// - `unlock_id`: The unlock_id to rebond.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::rebond_by_unlock_id())]
+		#[pallet::weight(
+			T::WeightInfo::rebond_by_unlock_id()
+				.saturating_add(T::OnVtokenRateChanged::on_vtoken_rate_changed_weight())
+		)]
 		pub fn rebond_by_unlock_id(
 			origin: OriginFor<T>,
 			currency_id: CurrencyIdOf<T>,
@@ -906,28 +922,38 @@ pub mod pallet {
 		/// - `currency_id`: The currency to set ongoing time unit.
 		/// - `time_unit`: The ongoing time unit to set.
 		#[pallet::call_index(13)]
-		#[pallet::weight(T::WeightInfo::set_ongoing_time_unit())]
+		#[pallet::weight(
+			T::WeightInfo::set_ongoing_time_unit()
+				.saturating_add(T::OnVtokenRateChanged::on_vtoken_rate_changed_weight())
+		)]
 		pub fn set_ongoing_time_unit(
 			origin: OriginFor<T>,
 			currency_id: CurrencyIdOf<T>,
 			time_unit: TimeUnit,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			T::ControlOrigin::ensure_origin(origin)?;
 
 			OngoingTimeUnit::<T>::mutate(&currency_id, |old_time_unit| {
 				*old_time_unit = Some(time_unit.clone())
 			});
+			let rate_changed_weight = Self::notify_vtoken_rate_changed(&currency_id);
 
 			Self::deposit_event(Event::SetOngoingTimeUnit {
 				currency_id,
 				time_unit,
 			});
-			Ok(())
+			Ok(
+				Some(T::WeightInfo::set_ongoing_time_unit().saturating_add(rate_changed_weight))
+					.into(),
+			)
 		}
 
 		// mint with lock to get incentive vtoken
 		#[pallet::call_index(14)]
-		#[pallet::weight(T::WeightInfo::mint_with_lock())]
+		#[pallet::weight(
+			T::WeightInfo::mint_with_lock()
+				.saturating_add(T::OnVtokenRateChanged::on_vtoken_rate_changed_weight())
+		)]
 		pub fn mint_with_lock(
 			origin: OriginFor<T>,
 			currency_id: CurrencyIdOf<T>,
@@ -949,8 +975,8 @@ pub mod pallet {
 			);
 
 			// check whether the user has veBNC
-			let vebnc_balance =
-				T::BbBNC::balance_of(&minter, None).map_err(|_| Error::<T>::VeBNCCheckingError)?;
+			let vebnc_balance = T::BbBNC::own_balance_of(&minter, None)
+				.map_err(|_| Error::<T>::VeBNCCheckingError)?;
 			ensure!(
 				vebnc_balance > BalanceOf::<T>::zero(),
 				Error::<T>::NotEnoughBalance
@@ -183,6 +183,7 @@ impl vtoken_minting::Config for Runtime {
 	type RelayChainToken = RelayCurrencyId;
 	type WeightInfo = ();
 	type OnRedeemSuccess = ();
+	type OnVtokenRateChanged = ();
 	type XcmTransfer = MockXcmTransfer;
 	type MoonbeamChainId = MoonbeamChainId;
 	type ChannelCommission = ();
@@ -305,6 +306,13 @@ impl BbBNCInterface<AccountId, CurrencyId, Balance, BlockNumber> for BbBNC {
 		Ok(100)
 	}
 
+	fn own_balance_of(
+		_addr: &AccountId,
+		_time: Option<BlockNumber>,
+	) -> Result<Balance, DispatchError> {
+		Ok(100)
+	}
+
 	fn total_supply(_t: BlockNumber) -> Result<Balance, DispatchError> {
 		Ok(10000)
 	}
@@ -70,3 +70,31 @@ impl<AccountId, CurrencyId, Balance> OnRedeemSuccess<AccountId, CurrencyId, Bala
 		Weight::zero()
 	}
 }
+
+pub trait OnVtokenRateChanged<CurrencyId, Balance> {
+	/// Called when the exchange rate of `vtoken_id` may have changed: `vtoken_amount` of it is
+	/// now worth `token_amount` of its token.
+	fn on_vtoken_rate_changed(
+		vtoken_id: CurrencyId,
+		vtoken_amount: Balance,
+		token_amount: Balance,
+	) -> Weight;
+
+	/// The upper bound of the weight of `on_vtoken_rate_changed`, added to the weight of the
+	/// calls changing the exchange rate.
+	fn on_vtoken_rate_changed_weight() -> Weight;
+}
+
+impl<CurrencyId, Balance> OnVtokenRateChanged<CurrencyId, Balance> for () {
+	fn on_vtoken_rate_changed(
+		_vtoken_id: CurrencyId,
+		_vtoken_amount: Balance,
+		_token_amount: Balance,
+	) -> Weight {
+		Weight::zero()
+	}
+
+	fn on_vtoken_rate_changed_weight() -> Weight {
+		Weight::zero()
+	}
+}
@@ -24,6 +24,7 @@ use frame_support::{parameter_types, PalletId};
 use hex_literal::hex;
 use parity_scale_codec::MaxEncodedLen;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_core::{Decode, Encode, RuntimeDebug, H160};
 use sp_runtime::{
 	generic,
@@ -239,6 +240,63 @@ impl<AccountId> Default for RedeemType<AccountId> {
 	}
 }
 
+// For flexible-fee
+/// A currency the fee of a transaction can be paid in.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct FeeOption<AccountId, Balance> {
+	pub currency_id: CurrencyId,
+	/// The fee amount in `currency_id`.
+	pub fee_amount: Balance,
+	/// The extra fees of the call in `currency_id`, with their receivers. Extra fees which
+	/// cannot be priced in `currency_id` are left out.
+	pub extra_fees: Vec<(Balance, AccountId)>,
+	/// Whether the account can pay the fee and all the extra fees in `currency_id`.
+	pub affordable: bool,
+	/// The sponsor paying the fee, in which case `fee_amount` is zero.
+	pub sponsor: Option<AccountId>,
+}
+
+// For swap-router
+/// The pool a swap hop is executed in.
+#[derive(
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+pub enum SwapPool {
+	/// A stable pool, by its pool id.
+	StablePool(PoolId),
+	/// The zenlink pair of the hop's input and output currencies.
+	Zenlink,
+}
+
+/// One hop of a swap route, swapping the output of the previous hop into `currency_out`.
+#[derive(
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+pub struct SwapHop {
+	pub pool: SwapPool,
+	pub currency_out: CurrencyId,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
 pub enum XcmOperationType {
 	// SALP operations
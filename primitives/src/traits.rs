@@ -242,6 +242,8 @@ pub trait FarmingInfo<Balance, CurrencyId, AccountId> {
 	fn get_token_shares(pool_id: PoolId, currency_id: CurrencyId) -> Balance;
 	/// Update the token shares for the gauge pool.
 	fn refresh_gauge_pool(exchanger: &AccountId) -> DispatchResult;
+	/// Update the boost votes of the current round to the voting power of `who`.
+	fn refresh_boost_votes(who: &AccountId) -> DispatchResult;
 }
 
 pub trait VtokenMintingInterface<AccountId, CurrencyId, Balance> {
@@ -275,6 +277,8 @@ pub trait VtokenMintingInterface<AccountId, CurrencyId, Balance> {
 	) -> Result<Balance, DispatchError>;
 	fn get_token_pool(currency_id: CurrencyId) -> Balance;
 	fn get_minimums_redeem(vtoken_id: CurrencyId) -> Balance;
+	/// The fee charged, in token, for minting vtoken with `token_amount`.
+	fn get_mint_fee(token_amount: Balance) -> Balance;
 	fn get_moonbeam_parachain_id() -> u32;
 }
 
@@ -332,6 +336,10 @@ impl<AccountId, CurrencyId, Balance: Zero> VtokenMintingInterface<AccountId, Cur
 		Zero::zero()
 	}
 
+	fn get_mint_fee(_token_amount: Balance) -> Balance {
+		Zero::zero()
+	}
+
 	fn get_moonbeam_parachain_id() -> u32 {
 		0
 	}
@@ -515,4 +523,8 @@ impl<AccountId, CurrencyId, Balance: Zero> FarmingInfo<Balance, CurrencyId, Acco
 	fn refresh_gauge_pool(_exchanger: &AccountId) -> DispatchResult {
 		Ok(())
 	}
+	/// Update the boost votes of the current round to the voting power of `who`.
+	fn refresh_boost_votes(_who: &AccountId) -> DispatchResult {
+		Ok(())
+	}
 }
@@ -31,6 +31,7 @@ pub mod bifrost_salp;
 pub mod bifrost_slp;
 pub mod bifrost_slpx;
 pub mod bifrost_stable_pool;
+pub mod bifrost_swap_router;
 pub mod bifrost_system_staking;
 pub mod bifrost_token_issuer;
 pub mod bifrost_vbnc_convert;
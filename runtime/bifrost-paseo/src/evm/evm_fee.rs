@@ -16,10 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Currencies;
-use bifrost_primitives::{
-	AccountFeeCurrency, Balance, CurrencyId, OraclePriceProvider, Price, WETH,
-};
+use bifrost_flexible_fee::impls::on_charge_transaction::PaymentInfo;
+use bifrost_primitives::{AccountFeeCurrency, Balance, OraclePriceProvider, Price, BNC, WETH};
 use frame_support::traits::TryDrop;
 use orml_traits::MultiCurrency;
 use pallet_evm::{AddressMapping, Error, OnChargeEVMTransaction};
@@ -27,28 +25,26 @@ use sp_core::{H160, U256};
 use sp_runtime::traits::UniqueSaturatedInto;
 use sp_std::marker::PhantomData;
 
-#[derive(Copy, Debug, Clone, Default, PartialEq)]
-pub struct EvmPaymentInfo {
-	fee_amount: Balance,
-	fee_currency: CurrencyId,
-	fee_currency_price: Price,
-	weth_price: Price,
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvmPaymentInfo<AccountId> {
+	/// Paid in WETH, the currency the gas is priced in, without any conversion
+	Weth(Balance),
+	/// Paid through flexible-fee, with the gas priced in BNC by the oracle
+	Converted {
+		payment: PaymentInfo<AccountId>,
+		bnc_fee: Balance,
+		weth_price: Price,
+		bnc_price: Price,
+	},
 }
 
-impl EvmPaymentInfo {
-	pub fn merge(self, other: Self) -> Self {
-		EvmPaymentInfo {
-			fee_amount: self.fee_amount.saturating_add(other.fee_amount),
-			fee_currency: self.fee_currency,
-			fee_currency_price: self.fee_currency_price,
-			weth_price: self.weth_price,
-		}
-	}
-}
-
-impl TryDrop for EvmPaymentInfo {
+impl<AccountId> TryDrop for EvmPaymentInfo<AccountId> {
 	fn try_drop(self) -> Result<(), Self> {
-		if self.fee_amount == 0 {
+		let fee_amount = match self {
+			EvmPaymentInfo::Weth(fee_amount) => fee_amount,
+			EvmPaymentInfo::Converted { ref payment, .. } => payment.fee_currency_and_amount().1,
+		};
+		if fee_amount == 0 {
 			Ok(())
 		} else {
 			Err(self)
@@ -58,18 +54,19 @@ impl TryDrop for EvmPaymentInfo {
 
 /// Implements the transaction payment for EVM transactions.
 /// Supports multi-currency fees based on what is provided by AccountFeeCurrency - account currency.
-pub struct TransferEvmFees<AC, MC, Price>(PhantomData<(AC, MC, Price)>);
+/// The gas is charged as is from accounts paying in WETH. For other currencies it is priced in
+/// BNC by the oracle and charged the same way as the fees of extrinsics, through the DEX or the
+/// oracle.
+pub struct TransferEvmFees<AC, Price>(PhantomData<(AC, Price)>);
 
-impl<T, AC, MC, Price> OnChargeEVMTransaction<T> for TransferEvmFees<AC, MC, Price>
+impl<T, AC, Price> OnChargeEVMTransaction<T> for TransferEvmFees<AC, Price>
 where
-	T: pallet_evm::Config,
+	T: pallet_evm::Config + bifrost_flexible_fee::Config,
 	AC: AccountFeeCurrency<T::AccountId>, // AccountCurrency
 	Price: OraclePriceProvider,           // PriceProvider
-	MC: MultiCurrency<T::AccountId, CurrencyId = CurrencyId, Balance = Balance>,
 	U256: UniqueSaturatedInto<Balance>,
-	sp_runtime::AccountId32: From<<T as frame_system::Config>::AccountId>,
 {
-	type LiquidityInfo = Option<EvmPaymentInfo>;
+	type LiquidityInfo = Option<EvmPaymentInfo<T::AccountId>>;
 
 	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, Error<T>> {
 		if fee.is_zero() {
@@ -80,37 +77,53 @@ where
 		let fee_currency =
 			AC::get_fee_currency(&account_id, fee).map_err(|_| Error::<T>::BalanceLow)?;
 
-		let Some((fee_amount, weth_price, fee_currency_price)) =
+		// the gas is priced in WETH, it needs no oracle price to be paid in WETH
+		if fee_currency == WETH {
+			let fee_amount: Balance = fee.unique_saturated_into();
+			<T as bifrost_flexible_fee::Config>::MultiCurrency::withdraw(
+				WETH,
+				&account_id,
+				fee_amount,
+			)
+			.map_err(|_| Error::<T>::WithdrawFailed)?;
+			return Ok(Some(EvmPaymentInfo::Weth(fee_amount)));
+		}
+
+		let Some((bnc_fee, weth_price, bnc_price)) =
 			Price::get_oracle_amount_by_currency_and_amount_in(
 				&WETH,
 				fee.unique_saturated_into(),
-				&fee_currency,
+				&BNC,
 			)
 		else {
 			return Err(Error::<T>::WithdrawFailed);
 		};
 
 		// Ensure that converted fee is not zero
-		if fee_amount == 0 {
+		if bnc_fee == 0 {
 			return Err(Error::<T>::WithdrawFailed);
 		}
 
+		let payment = bifrost_flexible_fee::Pallet::<T>::withdraw_fee_in_currency(
+			&account_id,
+			fee_currency,
+			bnc_fee,
+		)
+		.map_err(|_| Error::<T>::WithdrawFailed)?;
+
 		log::debug!(
 			target: "evm",
 			"Withdrew fee from account {:?} in currency {:?} amount {:?}",
 			account_id,
 			fee_currency,
-			fee_amount
+			payment.fee_currency_and_amount().1
 		);
 
-		MC::withdraw(fee_currency, &account_id, fee_amount)
-			.map_err(|_| Error::<T>::WithdrawFailed)?;
-
-		Ok(Some(EvmPaymentInfo {
-			fee_amount,
-			fee_currency,
-			fee_currency_price,
+		Ok(Some(EvmPaymentInfo::Converted {
+			payment,
+			bnc_fee,
 			weth_price,
+			bnc_price,
 		}))
 	}
 
@@ -120,49 +133,47 @@ where
 		_base_fee: U256,
 		already_withdrawn: Self::LiquidityInfo,
 	) -> Self::LiquidityInfo {
-		if let Some(payment_info) = already_withdrawn {
-			let account_id = T::AddressMapping::into_account_id(*who);
-
-			let adjusted_paid = if let Some(converted_corrected_fee) = Price::get_amount_by_prices(
-				&WETH,
-				corrected_fee.unique_saturated_into(),
-				payment_info.weth_price,
-				&payment_info.fee_currency,
-				payment_info.fee_currency_price,
-			) {
-				// Calculate how much refund we should return
-				let refund_amount = payment_info
-					.fee_amount
-					.saturating_sub(converted_corrected_fee);
-
-				// refund to the account that paid the fees. If this fails, the
-				// account might have dropped below the existential balance. In
-				// that case we don't refund anything.
-				let refund_imbalance =
-					match MC::deposit(payment_info.fee_currency, &account_id, refund_amount) {
-						Ok(_) => 0,
-						Err(_) => refund_amount,
-					};
-				// figure out how much is left to mint back
-				// refund_amount already minted back to account, imbalance is what is left to mint
-				// if any
-				payment_info
-					.fee_amount
-					.saturating_sub(refund_amount)
-					.saturating_add(refund_imbalance)
-			} else {
-				// if conversion failed for some reason, we refund the whole amount back to treasury
-				payment_info.fee_amount
-			};
-
-			// We can simply refund all the remaining amount back to treasury
-			let result = Currencies::deposit(
-				payment_info.fee_currency,
-				&crate::BifrostTreasuryAccount::get(),
-				adjusted_paid,
-			);
-			debug_assert_eq!(result, Ok(()));
-		}
+		let account_id = T::AddressMapping::into_account_id(*who);
+		let result = match already_withdrawn {
+			Some(EvmPaymentInfo::Weth(fee_amount)) => {
+				let corrected_fee: Balance = corrected_fee.unique_saturated_into();
+				let corrected_fee = corrected_fee.min(fee_amount);
+				bifrost_flexible_fee::Pallet::<T>::deposit_refund_and_fee(
+					&account_id,
+					WETH,
+					fee_amount.saturating_sub(corrected_fee),
+					corrected_fee,
+				)
+			}
+			Some(EvmPaymentInfo::Converted {
+				payment,
+				bnc_fee,
+				weth_price,
+				bnc_price,
+			}) => {
+				// if conversion failed for some reason, we deposit the whole amount to the
+				// treasury
+				let corrected_bnc_fee = Price::get_amount_by_prices(
+					&WETH,
+					corrected_fee.unique_saturated_into(),
+					weth_price,
+					&BNC,
+					bnc_price,
+				)
+				.unwrap_or(bnc_fee);
+
+				// refund to the account that paid the fees in the currency it paid, the rest goes
+				// to the treasury
+				bifrost_flexible_fee::Pallet::<T>::refund_and_deposit_fee(
+					&account_id,
+					corrected_bnc_fee,
+					0,
+					payment,
+				)
+			}
+			None => Ok(()),
+		};
+		debug_assert_eq!(result, Ok(()));
 		None
 	}
 
@@ -104,7 +104,6 @@ impl pallet_evm::Config for Runtime {
 	>;
 	type OnChargeTransaction = evm_fee::TransferEvmFees<
 		crate::FlexibleFee, // Get account's fee payment asset
-		crate::Currencies,  // Multi currency support
 		crate::Prices,      // Price provider
 	>;
 	type OnCreate = ();
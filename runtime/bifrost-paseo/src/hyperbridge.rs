@@ -22,9 +22,16 @@ use crate::{BncDecimals, Currencies};
 use crate::{TokenGateway, Treasury};
 use bifrost_asset_registry::AssetIdMaps;
 use bifrost_primitives::{AccountId, Balance};
+use bifrost_slpx::types::HyperbridgeOracleDispatcher;
 use frame_support::parameter_types;
-use ismp::{host::StateMachine, module::IsmpModule, router::IsmpRouter};
-use sp_core::Get;
+use ismp::{
+	dispatcher::{DispatchPost, DispatchRequest, FeeMetadata, IsmpDispatcher},
+	host::StateMachine,
+	module::IsmpModule,
+	router::IsmpRouter,
+};
+use sp_core::{Get, H160};
+use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::boxed::Box;
 use sp_std::vec::Vec;
 
@@ -120,3 +127,41 @@ impl pallet_token_gateway::Config for Runtime {
 	type ControlOrigin = TechAdminOrCouncil;
 	type CurrencyIdConvert = AssetIdMaps<Runtime>;
 }
+
+parameter_types! {
+	// Module id of the slpx oracle pushes on the EVM chains
+	pub const SlpxOracleModuleId: &'static [u8] = b"slpx-oracle";
+	// Oracle pushes that are not delivered within one hour are timed out
+	pub const SlpxOracleTimeout: u64 = 3600;
+}
+
+/// Dispatch slpx oracle pushes to EVM chains through pallet-ismp, the relayer fee is paid by the
+/// payer.
+pub struct SlpxOracleDispatcher;
+impl HyperbridgeOracleDispatcher<AccountId> for SlpxOracleDispatcher {
+	fn dispatch(
+		payer: &AccountId,
+		evm_chain_id: u32,
+		contract: H160,
+		body: Vec<u8>,
+		relayer_fee: u128,
+	) -> DispatchResult {
+		let post = DispatchPost {
+			dest: StateMachine::Evm(evm_chain_id),
+			from: SlpxOracleModuleId::get().to_vec(),
+			to: contract.as_bytes().to_vec(),
+			timeout: SlpxOracleTimeout::get(),
+			body,
+		};
+		Ismp::default()
+			.dispatch_request(
+				DispatchRequest::Post(post),
+				FeeMetadata {
+					payer: payer.clone(),
+					fee: relayer_fee,
+				},
+			)
+			.map(|_| ())
+			.map_err(|_| DispatchError::Other("Failed to dispatch the slpx oracle request"))
+	}
+}
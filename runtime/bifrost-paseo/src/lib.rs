@@ -981,10 +981,14 @@ impl bifrost_vesting::Config for Runtime {
 parameter_types! {
 	pub MaxFeeCurrencyOrderListLen: u32 = 50;
 	pub AllowVBNCAsFee: bool = true;
+	pub const SponsorshipRuleDeposit: Balance = 10 * BNCS;
+	pub const MaxSponsorshipRules: u32 = 16;
+	pub const MaxSponsorsPerCall: u32 = 16;
 }
 
 impl bifrost_flexible_fee::Config for Runtime {
 	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = StablePool;
 	type RuntimeEvent = RuntimeEvent;
 	type MultiCurrency = Currencies;
 	type TreasuryAccount = BifrostTreasuryAccount;
@@ -1003,6 +1007,69 @@ impl bifrost_flexible_fee::Config for Runtime {
 	type EvmPermit = evm::permit::EvmPermitHandler<Runtime>;
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type AllowVBNCAsFee = AllowVBNCAsFee;
+	type BlocksPerDay = ConstU32<DAYS>;
+	type SponsorshipRuleDeposit = SponsorshipRuleDeposit;
+	type MaxSponsorshipRules = MaxSponsorshipRules;
+	type MaxSponsorsPerCall = MaxSponsorsPerCall;
+	type InspectExtraFeeCall = ExtraFeeCallInspector;
+}
+
+/// Reads the arguments of the XCM calls priced by the extra fee rules of flexible-fee.
+pub struct ExtraFeeCallInspector;
+impl ExtraFeeCallInspector {
+	fn slpx_destination(
+		target_chain: &bifrost_slpx::types::TargetChain<AccountId>,
+	) -> Option<Location> {
+		use bifrost_primitives::{
+			AstarChainId, HydrationChainId, InterlayChainId, MantaChainId, MoonbeamChainId,
+		};
+		use bifrost_slpx::types::TargetChain;
+
+		let para_id = match target_chain {
+			TargetChain::Astar(_) => AstarChainId::get(),
+			TargetChain::Moonbeam(_) => MoonbeamChainId::get(),
+			TargetChain::Hydradx(_) => HydrationChainId::get(),
+			TargetChain::Interlay(_) => InterlayChainId::get(),
+			TargetChain::Manta(_) => MantaChainId::get(),
+		};
+		Some(Location::new(1, [Parachain(para_id)]))
+	}
+}
+
+impl bifrost_flexible_fee::InspectExtraFeeCall<RuntimeCall> for ExtraFeeCallInspector {
+	fn call_args(call: &RuntimeCall) -> bifrost_flexible_fee::ExtraFeeCallArgs {
+		match call {
+			RuntimeCall::Slpx(bifrost_slpx::Call::mint {
+				currency_id,
+				target_chain,
+				..
+			})
+			| RuntimeCall::Slpx(bifrost_slpx::Call::mint_with_channel_id {
+				currency_id,
+				target_chain,
+				..
+			})
+			| RuntimeCall::Slpx(bifrost_slpx::Call::redeem {
+				vtoken_id: currency_id,
+				target_chain,
+				..
+			}) => bifrost_flexible_fee::ExtraFeeCallArgs {
+				currency_id: Some(*currency_id),
+				destination: Self::slpx_destination(target_chain),
+				amount: None,
+			},
+			RuntimeCall::XcmInterface(bifrost_xcm_interface::Call::transfer_ethereum_assets {
+				currency_id,
+				amount,
+				..
+			}) => bifrost_flexible_fee::ExtraFeeCallArgs {
+				currency_id: Some(*currency_id),
+				destination: Some(bifrost_primitives::EthereumLocation::get()),
+				amount: Some(*amount),
+			},
+			_ => Default::default(),
+		}
+	}
 }
 
 parameter_types! {
@@ -1294,6 +1361,8 @@ impl bifrost_slpx::Config for Runtime {
 	type WeightInfo = weights::bifrost_slpx::BifrostWeight<Runtime>;
 	type MaxOrderSize = ConstU32<500>;
 	type BlockNumberProvider = System;
+	type OraclePayloadEncoder = bifrost_slpx::types::SetTokenAmountEncoder;
+	type HyperbridgeOracleDispatcher = hyperbridge::SlpxOracleDispatcher;
 }
 
 pub struct EnsurePoolAssetId;
@@ -1332,6 +1401,17 @@ impl bifrost_stable_pool::Config for Runtime {
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
 }
 
+impl bifrost_swap_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiCurrency = Currencies;
+	type StablePoolHandler = StablePool;
+	type DexOperator = ZenlinkProtocol;
+	type ControlOrigin = TechAdminOrCouncil;
+	type ParachainId = ParachainInfo;
+	type MaxHops = ConstU32<4>;
+	type WeightInfo = weights::bifrost_swap_router::BifrostWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const QueryTimeout: BlockNumber = 100;
 	pub const ReferendumCheckInterval: BlockNumber = 300;
@@ -1419,6 +1499,21 @@ impl bifrost_vtoken_minting::OnRedeemSuccess<AccountId, CurrencyId, Balance> for
 	}
 }
 
+pub struct OnVtokenRateChanged;
+impl bifrost_vtoken_minting::OnVtokenRateChanged<CurrencyId, Balance> for OnVtokenRateChanged {
+	fn on_vtoken_rate_changed(
+		vtoken_id: CurrencyId,
+		vtoken_amount: Balance,
+		token_amount: Balance,
+	) -> Weight {
+		StablePool::on_vtoken_rate_changed(vtoken_id, vtoken_amount, token_amount)
+	}
+
+	fn on_vtoken_rate_changed_weight() -> Weight {
+		StablePool::on_vtoken_rate_changed_weight()
+	}
+}
+
 parameter_types! {
 	pub const MaximumUnlockIdOfUser: u32 = 10;
 	pub const MaximumUnlockIdOfTimeUnit: u32 = 1000;
@@ -1438,6 +1533,7 @@ impl bifrost_vtoken_minting::Config for Runtime {
 	type BifrostSlpx = Slpx;
 	type WeightInfo = weights::bifrost_vtoken_minting::BifrostWeight<Runtime>;
 	type OnRedeemSuccess = OnRedeemSuccess;
+	type OnVtokenRateChanged = OnVtokenRateChanged;
 	type RelayChainToken = RelayCurrencyId;
 	type XcmTransfer = XTokens;
 	type MoonbeamChainId = MoonbeamChainId;
@@ -1550,12 +1646,20 @@ impl pallet_prices::Config for Runtime {
 	type WeightInfo = pallet_prices::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const LendMarketFlashLoanFee: Ratio = Ratio::from_parts(900);
+	pub const LendMarketMaxFlashLoanCalls: u32 = 10;
+	// 1 dollar
+	pub const LendMarketDustCollateralValue: Balance = 1_000_000_000_000_000_000;
+}
+
 impl lend_market::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PalletId = LendMarketPalletId;
 	type OraclePriceProvider = Prices;
 	type ReserveOrigin = TechAdminOrCouncil;
 	type UpdateOrigin = TechAdminOrCouncil;
+	type ControlOrigin = TechAdminOrCouncil;
 	type WeightInfo = weights::lend_market::BifrostWeight<Runtime>;
 	type UnixTime = Timestamp;
 	type Assets = Currencies;
@@ -1563,6 +1667,13 @@ impl lend_market::Config for Runtime {
 	type LiquidationFreeAssetId = RelayCurrencyId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanFee = LendMarketFlashLoanFee;
+	type MaxFlashLoanCalls = LendMarketMaxFlashLoanCalls;
+	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = StablePool;
+	type ParachainId = ParachainInfo;
+	type DustCollateralValue = LendMarketDustCollateralValue;
 }
 
 parameter_types! {
@@ -1582,6 +1693,10 @@ impl pallet_membership::Config<pallet_membership::Instance3> for Runtime {
 	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const LeverageStakingKeeperFee: Ratio = Ratio::from_parts(5_000);
+}
+
 impl leverage_staking::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = leverage_staking::weights::SubstrateWeight<Runtime>;
@@ -1590,6 +1705,7 @@ impl leverage_staking::Config for Runtime {
 	type LendMarket = LendMarket;
 	type StablePoolHandler = StablePool;
 	type CurrencyIdConversion = AssetIdMaps<Runtime>;
+	type KeeperFee = LeverageStakingKeeperFee;
 }
 
 parameter_types! {
@@ -1864,6 +1980,7 @@ construct_runtime! {
 		CloudsConvert: bifrost_clouds_convert = 137,
 		BuyBack: bifrost_buy_back = 138,
 		SlpV2: bifrost_slp_v2 = 139,
+		SwapRouter: bifrost_swap_router = 141,
 	}
 }
 
@@ -1955,6 +2072,8 @@ pub mod migrations {
 	pub type Unreleased = (
 		// permanent migration, do not remove
 		pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
+		bifrost_slpx::migration::v3::MigrateToV3<Runtime>,
+		bifrost_stable_asset::migration::v1::MigrateToV1<Runtime>,
 		bifrost_channel_commission::migrations::v1::MigrateToV1<Runtime>,
 	);
 }
@@ -2562,6 +2681,10 @@ impl_runtime_apis! {
 				_ => (BNC, Zero::zero()),
 			}
 		}
+
+		fn get_fee_options(who: AccountId, fee: Balance, utx: <Block as BlockT>::Extrinsic) -> Vec<bifrost_primitives::FeeOption<AccountId, Balance>> {
+			FlexibleFee::get_fee_options(&who, fee, &utx.0.function)
+		}
 	}
 
 	// zenlink runtime outer apis
@@ -2667,6 +2790,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl lend_market_rpc_runtime_api::LendMarketApi<Block, AccountId, Balance> for Runtime {
 		fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidity(&account)
@@ -2679,8 +2803,27 @@ impl_runtime_apis! {
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidation_threshold_liquidity(&account)
 		}
+
+		fn get_bad_debt(account: AccountId) -> Result<Vec<(CurrencyId, Balance)>, DispatchError> {
+			LendMarket::get_bad_debt(&account)
+		}
+
+		fn get_account_position(account: AccountId) -> Result<lend_market_rpc_runtime_api::AccountPosition<CurrencyId, Balance>, DispatchError> {
+			LendMarket::get_account_position(&account)
+		}
+	}
+
+	impl leverage_staking_rpc_runtime_api::LeverageStakingApi<Block, AccountId, Balance> for Runtime {
+		fn get_leverage_position(account: AccountId, asset_id: CurrencyId) -> Result<leverage_staking_rpc_runtime_api::LeveragePosition<Balance>, DispatchError> {
+			LeverageStaking::get_leverage_position(&account, asset_id)
+		}
+
+		fn simulate_leverage_position(account: AccountId, asset_id: CurrencyId, rate: Rate) -> Result<leverage_staking_rpc_runtime_api::LeveragePosition<Balance>, DispatchError> {
+			LeverageStaking::simulate_leverage_position(&account, asset_id, rate)
+		}
 	}
 
+	#[api_version(3)]
 	impl bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi<Block> for Runtime {
 		fn get_swap_output(
 			pool_id: u32,
@@ -2697,6 +2840,78 @@ impl_runtime_apis! {
 		) -> Balance {
 			StablePool::add_liquidity_amount(pool_id, amounts).unwrap_or(Zero::zero())
 		}
+
+		fn get_swap_input(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> Balance {
+			StablePool::get_swap_input(pool_id, currency_id_in, currency_id_out, amount).unwrap_or(Zero::zero())
+		}
+
+		fn get_redeem_proportion_amount(
+			pool_id: u32,
+			amount: Balance,
+		) -> Vec<Balance> {
+			StablePool::get_redeem_proportion_amount(pool_id, amount).unwrap_or_default()
+		}
+
+		fn get_redeem_single_amount(
+			pool_id: u32,
+			amount: Balance,
+			i: u32,
+		) -> Balance {
+			StablePool::get_redeem_single_amount(pool_id, amount, i).unwrap_or(Zero::zero())
+		}
+
+		fn get_redeem_multi_amount(
+			pool_id: u32,
+			amounts: Vec<Balance>,
+		) -> Balance {
+			StablePool::get_redeem_multi_amount(pool_id, amounts).unwrap_or(Zero::zero())
+		}
+
+		fn get_virtual_price(pool_id: u32) -> Balance {
+			StablePool::get_virtual_price(pool_id).map(|price| price.into_inner()).unwrap_or(Zero::zero())
+		}
+
+		fn get_current_a(pool_id: u32) -> Balance {
+			StablePool::get_current_a(pool_id).unwrap_or(Zero::zero())
+		}
+
+		fn get_pool_info(
+			pool_id: u32,
+		) -> Option<bifrost_stable_asset::StableAssetPoolInfo<CurrencyId, Balance, Balance, AccountId, BlockNumber>> {
+			bifrost_stable_asset::Pools::<Runtime>::get(pool_id)
+		}
+
+		fn get_swap_underlying_output(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> Balance {
+			StablePool::get_swap_underlying_output(pool_id, currency_id_in, currency_id_out, amount).unwrap_or(Zero::zero())
+		}
+
+		fn get_underlying_assets(pool_id: u32) -> Vec<CurrencyId> {
+			StablePool::get_underlying_assets(pool_id).unwrap_or_default()
+		}
+	}
+
+	impl bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi<Block> for Runtime {
+		fn find_best_route(
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount_in: Balance,
+		) -> Option<(Vec<bifrost_primitives::SwapHop>, Balance)> {
+			SwapRouter::find_best_route(currency_in, currency_out, amount_in)
+		}
+
+		fn get_amount_out(currency_in: CurrencyId, amount_in: Balance, route: Vec<bifrost_primitives::SwapHop>) -> Balance {
+			SwapRouter::get_amount_out(currency_in, amount_in, &route).unwrap_or(Zero::zero())
+		}
 	}
 
 	impl bifrost_vtoken_minting_rpc_runtime_api::VtokenMintingRuntimeApi<Block, CurrencyId, Balance> for Runtime {
@@ -74,4 +74,53 @@ impl<T: frame_system::Config> bifrost_flexible_fee::WeightInfo for BifrostWeight
 		Weight::from_parts(10_160_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Storage: FlexibleFee DexFeeConversionPremium (r:0 w:1)
+	// Proof: FlexibleFee DexFeeConversionPremium (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn set_dex_fee_conversion() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	// Storage: FlexibleFee SponsorBudgets (r:1 w:1)
+	// Proof Skipped: FlexibleFee SponsorBudgets (max_values: None, max_size: None, mode: Measured)
+	fn fund_sponsor_budget() -> Weight {
+		Weight::from_parts(53_804_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: FlexibleFee SponsorBudgets (r:1 w:1)
+	// Proof Skipped: FlexibleFee SponsorBudgets (max_values: None, max_size: None, mode: Measured)
+	// Storage: System Account (r:2 w:2)
+	// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_sponsor_budget() -> Weight {
+		Weight::from_parts(54_970_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: FlexibleFee SponsorshipRules (r:1 w:1)
+	// Proof Skipped: FlexibleFee SponsorshipRules (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsorshipRuleCount (r:1 w:1)
+	// Proof Skipped: FlexibleFee SponsorshipRuleCount (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsorsByCall (r:1 w:1)
+	// Proof Skipped: FlexibleFee SponsorsByCall (max_values: None, max_size: None, mode: Measured)
+	// Storage: System Account (r:2 w:2)
+	// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn set_sponsorship_rule() -> Weight {
+		Weight::from_parts(60_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: FlexibleFee ExtraFeeRules (r:0 w:1)
+	// Proof Skipped: FlexibleFee ExtraFeeRules (max_values: None, max_size: None, mode: Measured)
+	fn set_extra_fee_rule() -> Weight {
+		Weight::from_parts(10_581_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Swaps the fee of a transaction collected by the treasury into BNC through the DEX.
+	fn swap_fee_to_native() -> Weight {
+		Weight::from_parts(80_000_000, 12000)
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
 }
@@ -243,5 +243,34 @@ impl<T: frame_system::Config> bifrost_stable_pool::WeightInfo for BifrostWeight<
 		Weight::from_parts(43_753_000, 3911)
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
+	}	// Storage: StableAsset Pools (r:2 w:0)
+	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset BasePools (r:2 w:1)
+	// Proof Skipped: StableAsset BasePools (max_values: None, max_size: None, mode: Measured)
+	fn set_base_pool() -> Weight {
+		Weight::from_parts(53_904_000, 6832)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: StableAsset Pools (r:2 w:2)
+	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset BasePools (r:1 w:0)
+	// Proof Skipped: StableAsset BasePools (max_values: None, max_size: None, mode: Measured)
+	// Storage: System Account (r:2 w:2)
+	// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	// Storage: StableAsset TokenRateCaches (r:3 w:0)
+	// Proof Skipped: StableAsset TokenRateCaches (max_values: None, max_size: None, mode: Measured)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	// Storage: AssetRegistry CurrencyMetadatas (r:2 w:0)
+	// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	// Storage: Tokens TotalIssuance (r:2 w:2)
+	// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: StableAsset TokenRateHardcap (r:2 w:0)
+	// Proof Skipped: StableAsset TokenRateHardcap (max_values: None, max_size: None, mode: Measured)
+	fn swap_underlying() -> Weight {
+		Weight::from_parts(421_530_000, 11196)
+			.saturating_add(T::DbWeight::get().reads(18))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
 }
@@ -549,4 +549,46 @@ impl<T: frame_system::Config> bb_bnc::WeightInfo for BifrostWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(16))
 			.saturating_add(T::DbWeight::get().writes(10))
 	}
+	fn delegate() -> Weight {
+		Weight::from_parts(62_934_000, 0)
+			.saturating_add(Weight::from_parts(0, 7282))
+			.saturating_add(T::DbWeight::get().reads(16))
+			.saturating_add(T::DbWeight::get().writes(13))
+	}
+	fn undelegate() -> Weight {
+		Weight::from_parts(64_371_000, 0)
+			.saturating_add(Weight::from_parts(0, 7427))
+			.saturating_add(T::DbWeight::get().reads(16))
+			.saturating_add(T::DbWeight::get().writes(13))
+	}
+	fn split_position() -> Weight {
+		Weight::from_parts(106_312_000, 0)
+			.saturating_add(Weight::from_parts(0, 7902))
+			.saturating_add(T::DbWeight::get().reads(20))
+			.saturating_add(T::DbWeight::get().writes(14))
+	}
+	fn merge_positions() -> Weight {
+		Weight::from_parts(103_486_000, 0)
+			.saturating_add(Weight::from_parts(0, 8044))
+			.saturating_add(T::DbWeight::get().reads(19))
+			.saturating_add(T::DbWeight::get().writes(14))
+	}
+	fn transfer_position() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3700))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn accept_position_transfer() -> Weight {
+		Weight::from_parts(169_843_000, 0)
+			.saturating_add(Weight::from_parts(0, 8317))
+			.saturating_add(T::DbWeight::get().reads(32))
+			.saturating_add(T::DbWeight::get().writes(25))
+	}
+	fn cancel_position_transfer() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3700))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }